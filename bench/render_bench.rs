@@ -1,5 +1,3 @@
-#![allow(dead_code)]
-
 //! Headless microbenchmark for the dock's per-frame software renderer.
 //!
 //! `Renderer::render` is the hot path that runs every frame while the dock is
@@ -8,25 +6,21 @@
 //! a fixed set of items with a procedurally-generated icon fixture, swept by a
 //! synthetic magnification cursor over a fixed number of frames.
 //!
-//! It links the production `config` and `renderer` modules directly (via
-//! `#[path]`) so it measures the real code, and is gated behind the `bench`
-//! cargo feature so normal builds never compile it.
+//! It drives the production renderer through the `rdock` library, so it
+//! measures the real code, and is gated behind the `bench` cargo feature so
+//! normal builds never compile it.
 //!
 //! Output: `METRIC` lines on stdout. Primary metric is microseconds per frame
 //! (lower is better), taken as the minimum over many timed batches to suppress
-//! scheduler noise.
-
-#[path = "../src/config.rs"]
-mod config;
-#[path = "../src/renderer.rs"]
-mod renderer;
+//! scheduler noise. The same workload is then timed at the reduced `effects`
+//! levels ("lite" and "none") for comparison.
 
 use std::hint::black_box;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use config::{Config, DockItem, DockSettings};
-use renderer::Renderer;
+use rdock::config::{Config, DockItem, DockSettings, Effects};
+use rdock::renderer::Renderer;
 
 // Deterministic workload parameters. Changing these changes the workload, so
 // they are fixed constants, never read from the environment.
@@ -65,7 +59,7 @@ fn write_icon_fixture() -> PathBuf {
 
 /// Construct a representative dock: a mix of icon items, separators, and items
 /// without icons (placeholder path). Half are marked "running".
-fn build_items(icon: &PathBuf) -> Vec<DockItem> {
+fn build_items(icon: &Path) -> Vec<DockItem> {
     let mut items = Vec::new();
     for i in 0..16usize {
         if i == 5 || i == 11 {
@@ -75,10 +69,20 @@ fn build_items(icon: &PathBuf) -> Vec<DockItem> {
         items.push(DockItem {
             name: format!("item{i}"),
             path: PathBuf::new(),
-            icon: Some(icon.clone()),
+            icon: Some(icon.to_path_buf()),
             args: Vec::new(),
             separator: false,
+            region: false,
+            spacer: None,
             special: None,
+            match_pattern: None,
+            confirm: None,
+            command: None,
+            tooltip: None,
+            badge: None,
+            hidden: false,
+            children: Vec::new(),
+            included_from: None,
         });
     }
     items
@@ -111,7 +115,7 @@ fn main() {
         items: items.clone(),
     };
 
-    let renderer = Renderer::new(&config, &items).expect("build renderer");
+    let mut renderer = Renderer::new(&config, &items).expect("build renderer");
     let n = items.len();
     let magnification = config.dock.magnification;
 
@@ -122,10 +126,35 @@ fn main() {
     // buffer pages happens outside the measured region.
     for f in 0..WARMUP_FRAMES {
         let scales = scales_for_frame(f, n, magnification);
-        renderer.render(&mut buffer, &items, &running, None, &scales, None);
+        renderer.render(&mut buffer, &items, &running, &[], None, &scales, None);
         black_box(buffer.as_ptr());
     }
 
+    let (min_us_per_frame, mean_us_per_frame) = time_frames(&renderer, &mut buffer, &items, &running, magnification);
+    let min_fps = 1_000_000.0 / min_us_per_frame;
+
+    // Primary metric first. Lower is better.
+    println!("METRIC render_us_per_frame={min_us_per_frame:.3}");
+    println!("METRIC render_mean_us_per_frame={mean_us_per_frame:.3}");
+    println!("METRIC render_min_fps={min_fps:.1}");
+
+    // The reduced `effects` levels, same workload
+    for (effects, name) in [(Effects::Lite, "lite"), (Effects::None, "none")] {
+        renderer.effects = effects;
+        let (min_us, _) = time_frames(&renderer, &mut buffer, &items, &running, magnification);
+        println!("METRIC render_{name}_us_per_frame={min_us:.3}");
+    }
+
+    // Full effects with icons composited in linear light
+    renderer.effects = Effects::Full;
+    renderer.gamma_correct_blending = true;
+    let (min_us, _) = time_frames(&renderer, &mut buffer, &items, &running, magnification);
+    println!("METRIC render_gamma_us_per_frame={min_us:.3}");
+}
+
+/// Minimum and mean microseconds per frame over the timed batches
+fn time_frames(renderer: &Renderer, buffer: &mut [u32], items: &[DockItem], running: &[bool], magnification: f32) -> (f64, f64) {
+    let n = items.len();
     // Timed batches. Report the minimum per-frame time (most stable estimator
     // for a CPU microbenchmark) plus mean for context.
     let mut min_batch_ns = u128::MAX;
@@ -135,7 +164,7 @@ fn main() {
         let start = Instant::now();
         for _ in 0..FRAMES_PER_BATCH {
             let scales = scales_for_frame(black_box(frame), n, magnification);
-            renderer.render(&mut buffer, &items, &running, None, &scales, None);
+            renderer.render(buffer, items, running, &[], None, &scales, None);
             black_box(buffer.as_ptr());
             frame = frame.wrapping_add(1);
         }
@@ -147,10 +176,5 @@ fn main() {
     let min_us_per_frame = min_batch_ns as f64 / FRAMES_PER_BATCH as f64 / 1000.0;
     let mean_us_per_frame =
         total_ns as f64 / (BATCHES as f64 * FRAMES_PER_BATCH as f64) / 1000.0;
-    let min_fps = 1_000_000.0 / min_us_per_frame;
-
-    // Primary metric first. Lower is better.
-    println!("METRIC render_us_per_frame={min_us_per_frame:.3}");
-    println!("METRIC render_mean_us_per_frame={mean_us_per_frame:.3}");
-    println!("METRIC render_min_fps={min_fps:.1}");
+    (min_us_per_frame, mean_us_per_frame)
 }
@@ -0,0 +1,421 @@
+//! Searchable "all apps" launcher for the `app_launcher` special item
+//!
+//! Lists the `.lnk` shortcuts under the per-user and all-users Start Menu
+//! Programs folders in a popup with a search box. Typing filters the list as
+//! you go; Up/Down move the selection and Enter or a double click launches it.
+//! Only the best `MAX_RESULTS` matches are listed, and each row's icon is
+//! fetched the first time the row is drawn, so long lists stay responsive.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    DrawTextW, FillRect, GetSysColor, GetSysColorBrush, SetBkMode, SetTextColor, DT_END_ELLIPSIS,
+    DT_LEFT, DT_NOPREFIX, DT_SINGLELINE, DT_VCENTER, HBRUSH, SYS_COLOR_INDEX, TRANSPARENT,
+};
+use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Controls::{DRAWITEMSTRUCT, MEASUREITEMSTRUCT, ODS_SELECTED};
+use windows::Win32::UI::Input::KeyboardAndMouse::{SetFocus, VK_DOWN, VK_UP};
+use windows::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_SMALLICON};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+/// Most matches listed at once
+const MAX_RESULTS: usize = 50;
+
+/// Height of one list row in pixels
+const ROW_HEIGHT: u32 = 24;
+
+// Control IDs
+const ID_SEARCH_EDIT: i32 = 101;
+const ID_RESULT_LIST: i32 = 102;
+const ID_OK: i32 = 1;
+const ID_CANCEL: i32 = 2;
+
+// Style constants
+const ES_AUTOHSCROLL: u32 = 0x0080;
+const LBS_NOTIFY: u32 = 0x0001;
+const LBS_OWNERDRAWFIXED: u32 = 0x0010;
+const LBS_NOINTEGRALHEIGHT: u32 = 0x0100;
+const EN_CHANGE: u32 = 0x0300;
+const LBN_DBLCLK: u32 = 2;
+const COLOR_BTNFACE: u32 = 15;
+const COLOR_WINDOW: i32 = 5;
+const COLOR_WINDOWTEXT: i32 = 8;
+const COLOR_HIGHLIGHT: i32 = 13;
+const COLOR_HIGHLIGHTTEXT: i32 = 14;
+
+/// A Start Menu shortcut
+#[derive(Debug, Clone)]
+pub struct InstalledApp {
+    pub name: String,
+    /// The `.lnk` file
+    pub path: PathBuf,
+}
+
+struct LauncherState {
+    apps: Vec<InstalledApp>,
+    /// Indices into `apps` currently listed, best match first
+    results: Vec<usize>,
+    /// Small icons by index into `apps`, loaded as rows are drawn
+    icons: HashMap<usize, HICON>,
+    chosen: Option<PathBuf>,
+}
+
+thread_local! {
+    static LAUNCHER: RefCell<Option<LauncherState>> = const { RefCell::new(None) };
+}
+
+/// Every shortcut in the Start Menu Programs folders, as `listed` trims them
+pub fn installed_apps() -> Vec<InstalledApp> {
+    let roots = [("APPDATA", r"Microsoft\Windows\Start Menu\Programs"), ("ProgramData", r"Microsoft\Windows\Start Menu\Programs")];
+    let mut apps = Vec::new();
+    for (var, sub) in roots {
+        if let Some(base) = std::env::var_os(var) {
+            collect_shortcuts(&PathBuf::from(base).join(sub), &mut apps);
+        }
+    }
+    listed(apps)
+}
+
+/// `apps` sorted by name. Shortcuts with the same name (per-user and
+/// all-users copies) are listed once, and uninstallers are left out.
+fn listed(mut apps: Vec<InstalledApp>) -> Vec<InstalledApp> {
+    apps.retain(|app| !app.name.to_lowercase().contains("uninstall"));
+    apps.sort_by_key(|app| app.name.to_lowercase());
+    apps.dedup_by_key(|app| app.name.to_lowercase());
+    apps
+}
+
+fn collect_shortcuts(dir: &Path, apps: &mut Vec<InstalledApp>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_shortcuts(&path, apps);
+        } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("lnk")) {
+            if let Some(stem) = path.file_stem() {
+                apps.push(InstalledApp { name: stem.to_string_lossy().into_owned(), path });
+            }
+        }
+    }
+}
+
+/// Indices of the apps whose names contain every word of `query`
+/// (case-insensitive), names starting with the query first, at most
+/// `MAX_RESULTS`. An empty query lists the first apps alphabetically.
+pub fn filter_apps(apps: &[InstalledApp], query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let mut matches: Vec<(bool, usize)> = apps.iter().enumerate()
+        .filter_map(|(i, app)| {
+            let name = app.name.to_lowercase();
+            words.iter().all(|word| name.contains(word)).then(|| (!name.starts_with(&query), i))
+        })
+        .collect();
+    // Stable, so each group stays alphabetical
+    matches.sort_by_key(|&(later, _)| later);
+    matches.into_iter().take(MAX_RESULTS).map(|(_, i)| i).collect()
+}
+
+/// Show the launcher and wait for a choice. Returns the chosen shortcut.
+pub fn show_app_launcher() -> Option<PathBuf> {
+    let apps = installed_apps();
+    let results = filter_apps(&apps, "");
+    LAUNCHER.with(|cell| {
+        *cell.borrow_mut() = Some(LauncherState { apps, results, icons: HashMap::new(), chosen: None });
+    });
+
+    unsafe {
+        let hinstance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default().0 as *mut _;
+        let hinstance = windows::Win32::Foundation::HINSTANCE(hinstance);
+
+        let class_name: Vec<u16> = "RDockAppLauncher\0".encode_utf16().collect();
+        let wc = WNDCLASSEXW {
+            cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+            style: CS_HREDRAW | CS_VREDRAW,
+            lpfnWndProc: Some(launcher_proc),
+            cbClsExtra: 0,
+            cbWndExtra: 0,
+            hInstance: hinstance,
+            hIcon: HICON::default(),
+            hCursor: LoadCursorW(None, IDC_ARROW).unwrap_or_default(),
+            hbrBackground: HBRUSH((COLOR_BTNFACE + 1) as *mut _),
+            lpszMenuName: PCWSTR::null(),
+            lpszClassName: PCWSTR(class_name.as_ptr()),
+            hIconSm: HICON::default(),
+        };
+        RegisterClassExW(&wc);
+
+        let width = 420;
+        let height = 480;
+        let screen_w = GetSystemMetrics(SM_CXSCREEN);
+        let screen_h = GetSystemMetrics(SM_CYSCREEN);
+        let title: Vec<u16> = "Apps\0".encode_utf16().collect();
+        let hwnd = CreateWindowExW(
+            WS_EX_DLGMODALFRAME | WS_EX_TOPMOST,
+            PCWSTR(class_name.as_ptr()),
+            PCWSTR(title.as_ptr()),
+            WS_POPUP | WS_CAPTION | WS_SYSMENU,
+            (screen_w - width) / 2, (screen_h - height) / 2, width, height,
+            HWND::default(),
+            HMENU::default(),
+            hinstance,
+            None,
+        ).unwrap_or_default();
+
+        let chosen = if hwnd.is_invalid() {
+            None
+        } else {
+            let _ = ShowWindow(hwnd, SW_SHOW);
+            let _ = SetForegroundWindow(hwnd);
+            if let Ok(edit) = GetDlgItem(hwnd, ID_SEARCH_EDIT) {
+                let _ = SetFocus(edit);
+            }
+
+            // Modal message loop
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).into() {
+                // Up/Down in the search box move the list selection
+                let arrow = msg.message == WM_KEYDOWN
+                    && (msg.wParam.0 == VK_UP.0 as usize || msg.wParam.0 == VK_DOWN.0 as usize);
+                if arrow && GetDlgItem(hwnd, ID_SEARCH_EDIT).is_ok_and(|edit| edit == msg.hwnd) {
+                    move_selection(hwnd, if msg.wParam.0 == VK_UP.0 as usize { -1 } else { 1 });
+                } else if !IsDialogMessageW(hwnd, &msg).as_bool() {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+
+                if !IsWindow(hwnd).as_bool() {
+                    break;
+                }
+            }
+            LAUNCHER.with(|cell| cell.borrow_mut().as_mut().and_then(|state| state.chosen.take()))
+        };
+
+        // Free the icons loaded while drawing
+        if let Some(state) = LAUNCHER.with(|cell| cell.borrow_mut().take()) {
+            for icon in state.icons.into_values() {
+                let _ = DestroyIcon(icon);
+            }
+        }
+        chosen
+    }
+}
+
+unsafe extern "system" fn launcher_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_CREATE => {
+            create_controls(hwnd);
+            refresh_list(hwnd);
+            LRESULT(0)
+        }
+        WM_MEASUREITEM => {
+            let measure = &mut *(lparam.0 as *mut MEASUREITEMSTRUCT);
+            measure.itemHeight = ROW_HEIGHT;
+            LRESULT(1)
+        }
+        WM_DRAWITEM => {
+            draw_row(&*(lparam.0 as *const DRAWITEMSTRUCT));
+            LRESULT(1)
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xFFFF) as i32;
+            let code = ((wparam.0 >> 16) & 0xFFFF) as u32;
+            match (id, code) {
+                (ID_SEARCH_EDIT, EN_CHANGE) => refresh_list(hwnd),
+                (ID_RESULT_LIST, LBN_DBLCLK) | (ID_OK, _) => choose_selection(hwnd),
+                (ID_CANCEL, _) => {
+                    let _ = DestroyWindow(hwnd);
+                }
+                _ => {}
+            }
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            let _ = DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+unsafe fn create_controls(hwnd: HWND) {
+    let hinstance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default().0 as *mut _;
+    let hinstance = windows::Win32::Foundation::HINSTANCE(hinstance);
+
+    let mut client = RECT::default();
+    let _ = GetClientRect(hwnd, &mut client);
+    let width = client.right - 20;
+
+    let edit_class: Vec<u16> = "EDIT\0".encode_utf16().collect();
+    let listbox_class: Vec<u16> = "LISTBOX\0".encode_utf16().collect();
+    let _ = CreateWindowExW(
+        WS_EX_CLIENTEDGE, PCWSTR(edit_class.as_ptr()), PCWSTR::null(),
+        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL),
+        10, 10, width, 24, hwnd, HMENU(ID_SEARCH_EDIT as *mut _), hinstance, None
+    );
+    let _ = CreateWindowExW(
+        WS_EX_CLIENTEDGE, PCWSTR(listbox_class.as_ptr()), PCWSTR::null(),
+        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WS_VSCROLL
+            | WINDOW_STYLE(LBS_NOTIFY | LBS_OWNERDRAWFIXED | LBS_NOINTEGRALHEIGHT),
+        10, 44, width, client.bottom - 54, hwnd, HMENU(ID_RESULT_LIST as *mut _), hinstance, None
+    );
+}
+
+/// Re-run the search for the current text and relist the matches
+unsafe fn refresh_list(hwnd: HWND) {
+    let Ok(list) = GetDlgItem(hwnd, ID_RESULT_LIST) else { return };
+    let query = get_edit_text(hwnd, ID_SEARCH_EDIT);
+    let count = LAUNCHER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let Some(state) = cell.as_mut() else { return 0 };
+        state.results = filter_apps(&state.apps, &query);
+        state.results.len()
+    });
+
+    SendMessageW(list, WM_SETREDRAW, WPARAM(0), LPARAM(0));
+    SendMessageW(list, LB_RESETCONTENT, WPARAM(0), LPARAM(0));
+    // Rows are drawn from `results`, so each row's data is just its position
+    for row in 0..count {
+        SendMessageW(list, LB_ADDSTRING, WPARAM(0), LPARAM(row as isize));
+    }
+    if count > 0 {
+        SendMessageW(list, LB_SETCURSEL, WPARAM(0), LPARAM(0));
+    }
+    SendMessageW(list, WM_SETREDRAW, WPARAM(1), LPARAM(0));
+    let _ = windows::Win32::Graphics::Gdi::InvalidateRect(list, None, true);
+}
+
+unsafe fn move_selection(hwnd: HWND, delta: isize) {
+    let Ok(list) = GetDlgItem(hwnd, ID_RESULT_LIST) else { return };
+    let count = SendMessageW(list, LB_GETCOUNT, WPARAM(0), LPARAM(0)).0;
+    if count <= 0 {
+        return;
+    }
+    let current = SendMessageW(list, LB_GETCURSEL, WPARAM(0), LPARAM(0)).0;
+    let next = (current + delta).clamp(0, count - 1);
+    SendMessageW(list, LB_SETCURSEL, WPARAM(next as usize), LPARAM(0));
+}
+
+/// Remember the selected app and close
+unsafe fn choose_selection(hwnd: HWND) {
+    let Ok(list) = GetDlgItem(hwnd, ID_RESULT_LIST) else { return };
+    let row = SendMessageW(list, LB_GETCURSEL, WPARAM(0), LPARAM(0)).0;
+    if row < 0 {
+        return;
+    }
+    LAUNCHER.with(|cell| {
+        if let Some(state) = cell.borrow_mut().as_mut() {
+            state.chosen = state.results.get(row as usize).map(|&i| state.apps[i].path.clone());
+        }
+    });
+    let _ = DestroyWindow(hwnd);
+}
+
+unsafe fn draw_row(draw: &DRAWITEMSTRUCT) {
+    if draw.itemID == u32::MAX {
+        return;
+    }
+    let selected = draw.itemState.0 & ODS_SELECTED.0 != 0;
+    let (back, fore) = if selected { (COLOR_HIGHLIGHT, COLOR_HIGHLIGHTTEXT) } else { (COLOR_WINDOW, COLOR_WINDOWTEXT) };
+    FillRect(draw.hDC, &draw.rcItem, GetSysColorBrush(SYS_COLOR_INDEX(back)));
+
+    let Some((name, icon)) = LAUNCHER.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let state = cell.as_mut()?;
+        let index = *state.results.get(draw.itemData)?;
+        let icon = *state.icons.entry(index).or_insert_with(|| small_icon(&state.apps[index].path));
+        Some((state.apps[index].name.clone(), icon))
+    }) else { return };
+
+    let icon_size = GetSystemMetrics(SM_CXSMICON);
+    let top = draw.rcItem.top + (ROW_HEIGHT as i32 - icon_size) / 2;
+    if !icon.is_invalid() {
+        let _ = DrawIconEx(draw.hDC, draw.rcItem.left + 4, top, icon, icon_size, icon_size, 0, None, DI_NORMAL);
+    }
+
+    let mut text_rect = draw.rcItem;
+    text_rect.left += icon_size + 12;
+    let mut text: Vec<u16> = name.encode_utf16().collect();
+    SetBkMode(draw.hDC, TRANSPARENT);
+    SetTextColor(draw.hDC, windows::Win32::Foundation::COLORREF(GetSysColor(SYS_COLOR_INDEX(fore))));
+    DrawTextW(draw.hDC, &mut text, &mut text_rect, DT_LEFT | DT_VCENTER | DT_SINGLELINE | DT_NOPREFIX | DT_END_ELLIPSIS);
+}
+
+/// The shell's small icon for `path`, or an invalid handle if it has none
+unsafe fn small_icon(path: &Path) -> HICON {
+    let wide_path: Vec<u16> = path.to_string_lossy().encode_utf16().chain(std::iter::once(0)).collect();
+    let mut info = SHFILEINFOW::default();
+    let result = SHGetFileInfoW(
+        PCWSTR(wide_path.as_ptr()),
+        FILE_FLAGS_AND_ATTRIBUTES(0),
+        Some(&mut info),
+        std::mem::size_of::<SHFILEINFOW>() as u32,
+        SHGFI_ICON | SHGFI_SMALLICON,
+    );
+    if result == 0 { HICON::default() } else { info.hIcon }
+}
+
+unsafe fn get_edit_text(hwnd: HWND, id: i32) -> String {
+    let Ok(ctrl) = GetDlgItem(hwnd, id) else { return String::new() };
+    let len = GetWindowTextLengthW(ctrl) as usize;
+    if len == 0 {
+        return String::new();
+    }
+    let mut buf: Vec<u16> = vec![0; len + 1];
+    GetWindowTextW(ctrl, &mut buf);
+    String::from_utf16_lossy(&buf[..len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apps(names: &[&str]) -> Vec<InstalledApp> {
+        names.iter()
+            .map(|name| InstalledApp { name: name.to_string(), path: PathBuf::from(format!(r"C:\Start\{}.lnk", name)) })
+            .collect()
+    }
+
+    fn found<'a>(apps: &'a [InstalledApp], query: &str) -> Vec<&'a str> {
+        filter_apps(apps, query).into_iter().map(|i| apps[i].name.as_str()).collect()
+    }
+
+    #[test]
+    fn every_word_must_match() {
+        let apps = apps(&["Visual Studio Code", "Visual Studio Installer", "Studio One"]);
+        assert_eq!(found(&apps, "studio visual"), ["Visual Studio Code", "Visual Studio Installer"]);
+        assert_eq!(found(&apps, "  CODE "), ["Visual Studio Code"]);
+        assert!(found(&apps, "studio paint").is_empty());
+    }
+
+    #[test]
+    fn prefix_matches_come_first_in_name_order() {
+        let apps = listed(apps(&["Notepad++", "Windows Notepad", "Notepad", "Another Notepad"]));
+        assert_eq!(found(&apps, "notepad"), ["Notepad", "Notepad++", "Another Notepad", "Windows Notepad"]);
+    }
+
+    #[test]
+    fn results_are_capped() {
+        let names: Vec<String> = (0..MAX_RESULTS + 5).map(|i| format!("App {:03}", i)).collect();
+        let apps = apps(&names.iter().map(String::as_str).collect::<Vec<_>>());
+        let results = filter_apps(&apps, "");
+        assert_eq!(results.len(), MAX_RESULTS);
+        assert_eq!(results[0], 0);
+        assert_eq!(filter_apps(&apps, "app").len(), MAX_RESULTS);
+    }
+
+    #[test]
+    fn listed_drops_uninstallers_and_duplicates() {
+        let listed = listed(apps(&["Zoom", "Uninstall Zoom", "firefox", "Firefox", "Zoom UNINSTALLER"]));
+        let names: Vec<&str> = listed.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, ["firefox", "Zoom"]);
+    }
+}
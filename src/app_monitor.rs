@@ -1,57 +1,103 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsString;
 use std::os::windows::ffi::OsStringExt;
 use std::path::Path;
 
+use crate::config::DockItem;
 use windows::Win32::Foundation::{CloseHandle, HANDLE, HWND, BOOL, LPARAM};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
 use windows::Win32::System::ProcessStatus::EnumProcesses;
 use windows::Win32::System::Threading::{
     OpenProcess, QueryFullProcessImageNameW, TerminateProcess, PROCESS_NAME_FORMAT,
     PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    EnumWindows, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, WM_CLOSE,
+    EnumWindows, GetWindowThreadProcessId, IsHungAppWindow, IsWindowVisible, PostMessageW, WM_CLOSE,
 };
 
 /// Snapshot of running executables. Paths are stored pre-lowercased so lookups
 /// are O(1) without per-call allocation.
 pub type RunningSet = HashSet<String>;
 
+/// Lowercased executable path of each running target process, by pid
+pub type RunningProcesses = HashMap<u32, String>;
+
+/// Lowercased executable file names (`foo.exe`) worth resolving to full
+/// paths. `None` means any process could match, so every one is resolved.
+pub type TargetNames = Option<HashSet<String>>;
+
+/// File names of the executables `items` can show as running. A
+/// `match_pattern` with wildcards in its file name part can match anything,
+/// which gives `None`.
+pub fn target_names(items: &[DockItem]) -> TargetNames {
+    let mut names = HashSet::new();
+    for item in items {
+        if item.is_separator() || item.is_group() || item.special.is_some() || item.command.is_some() {
+            continue;
+        }
+        let name = match &item.match_pattern {
+            Some(pattern) => {
+                let pattern = normalize_pattern(pattern);
+                let name = pattern.rsplit('\\').next().unwrap_or_default().to_string();
+                if name.contains(['*', '?']) {
+                    return None;
+                }
+                name
+            }
+            None => match item.path.file_name() {
+                Some(name) => name.to_string_lossy().to_lowercase(),
+                None => continue,
+            },
+        };
+        names.insert(name);
+    }
+    Some(names)
+}
+
 /// Enumerate running processes and return their executable paths (lowercased).
 ///
-/// Uses `PROCESS_QUERY_LIMITED_INFORMATION` + `QueryFullProcessImageNameW`,
-/// which is significantly cheaper than `PROCESS_VM_READ` + `GetModuleFileNameExW`
-/// and also succeeds for elevated processes.
-pub fn get_running_executables() -> RunningSet {
-    let mut running = RunningSet::with_capacity(512);
-    let mut pids: [u32; 2048] = [0; 2048];
-    let mut bytes_returned: u32 = 0;
+/// A Toolhelp snapshot lists every process with its file name, without
+/// opening it. Only processes whose file name is in `targets` are then
+/// opened for the full path (`PROCESS_QUERY_LIMITED_INFORMATION` +
+/// `QueryFullProcessImageNameW`, which also succeeds for elevated processes),
+/// so the periodic check costs a handful of OpenProcess calls, not hundreds.
+pub fn get_running_executables(targets: &TargetNames) -> RunningProcesses {
+    let mut running = RunningProcesses::new();
 
     unsafe {
-        if EnumProcesses(
-            pids.as_mut_ptr(),
-            (pids.len() * std::mem::size_of::<u32>()) as u32,
-            &mut bytes_returned,
-        )
-        .is_err()
-        {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
             return running;
-        }
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
 
-        let num_pids = bytes_returned as usize / std::mem::size_of::<u32>();
-        for &pid in &pids[..num_pids] {
-            if pid == 0 {
-                continue;
-            }
-            if let Some(path) = get_process_path_lower(pid) {
-                running.insert(path);
+        let mut more = Process32FirstW(snapshot, &mut entry).is_ok();
+        while more {
+            let pid = entry.th32ProcessID;
+            if pid != 0 && is_target(&entry.szExeFile, targets) {
+                if let Some(path) = get_process_path_lower(pid) {
+                    running.insert(pid, path);
+                }
             }
+            more = Process32NextW(snapshot, &mut entry).is_ok();
         }
+        let _ = CloseHandle(snapshot);
     }
 
     running
 }
 
+/// Whether a snapshot entry's NUL-terminated file name is one of `targets`
+fn is_target(exe_file: &[u16], targets: &TargetNames) -> bool {
+    let Some(names) = targets else { return true };
+    let len = exe_file.iter().position(|&c| c == 0).unwrap_or(exe_file.len());
+    names.contains(&String::from_utf16_lossy(&exe_file[..len]).to_lowercase())
+}
+
 /// Query a process's image path as a lowercased `String`. Returns `None` on
 /// failure (access denied, protected process, exited, etc).
 fn get_process_path_lower(pid: u32) -> Option<String> {
@@ -79,6 +125,78 @@ pub fn is_running(exe_path: &Path, running: &RunningSet) -> bool {
     running.contains(&normalized)
 }
 
+/// Check if any running executable matches a `match_pattern` glob. O(n) over
+/// the snapshot, so only used for items that opt in.
+pub fn is_running_pattern(pattern: &str, running: &RunningSet) -> bool {
+    let pattern = normalize_pattern(pattern);
+    running.iter().any(|path| glob_match(&pattern, path))
+}
+
+/// Check a single executable path against a `match_pattern` glob.
+pub fn matches_pattern(pattern: &str, exe_path: &Path) -> bool {
+    glob_match(&normalize_pattern(pattern), &exe_path.to_string_lossy().to_lowercase())
+}
+
+/// Patterns are case-insensitive and accept either slash direction.
+fn normalize_pattern(pattern: &str) -> String {
+    pattern.to_lowercase().replace('/', "\\")
+}
+
+/// Minimal wildcard match over the whole string: `*` matches any run of
+/// characters (including path separators), `?` matches exactly one.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Last `*` seen and the text position it is currently absorbing up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, absorbed)) = backtrack {
+            p = star + 1;
+            t = absorbed + 1;
+            backtrack = Some((star, t));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// The paths of `running` processes whose first visible top-level window
+/// the system considers hung. `IsHungAppWindow` only reads the window's
+/// state, so a busy app never holds up the dock, and the paths come from
+/// `get_running_executables` instead of opening each process again.
+pub fn hung_executables(running: &RunningProcesses) -> RunningSet {
+    let mut windows: Vec<(HWND, u32)> = Vec::new();
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_visible_callback),
+            LPARAM(&mut windows as *mut Vec<(HWND, u32)> as isize),
+        );
+    }
+
+    let mut probed = RunningSet::new();
+    let mut hung = RunningSet::new();
+    for (hwnd, pid) in windows {
+        let Some(path) = running.get(&pid) else { continue };
+        if !probed.insert(path.clone()) {
+            continue;
+        }
+        if unsafe { IsHungAppWindow(hwnd) }.as_bool() {
+            hung.insert(path.clone());
+        }
+    }
+    hung
+}
+
 /// Gracefully quit all instances of an application by sending WM_CLOSE to its windows.
 /// Falls back to TerminateProcess if no windows are found.
 pub fn quit_application(exe_path: &Path) {
@@ -117,7 +235,7 @@ pub fn quit_application(exe_path: &Path) {
     
     unsafe {
         let _ = EnumWindows(
-            Some(enum_close_callback),
+            Some(enum_visible_callback),
             LPARAM(&mut windows as *mut Vec<(HWND, u32)> as isize),
         );
     }
@@ -144,7 +262,8 @@ pub fn quit_application(exe_path: &Path) {
     }
 }
 
-unsafe extern "system" fn enum_close_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
+/// EnumWindows callback collecting (window, process id) for visible windows
+unsafe extern "system" fn enum_visible_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
     if IsWindowVisible(hwnd).as_bool() {
         let mut pid: u32 = 0;
         GetWindowThreadProcessId(hwnd, Some(&mut pid));
@@ -155,3 +274,75 @@ unsafe extern "system" fn enum_close_callback(hwnd: HWND, lparam: LPARAM) -> BOO
     }
     BOOL(1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn item(path: &str) -> DockItem {
+        DockItem::from_exe(PathBuf::from(path))
+    }
+
+    fn matching(pattern: &str) -> DockItem {
+        let mut item = item(r"C:\Apps\Placeholder.exe");
+        item.match_pattern = Some(pattern.to_string());
+        item
+    }
+
+    #[test]
+    fn star_spans_path_separators() {
+        assert!(glob_match(r"c:\apps\*.exe", r"c:\apps\tool\v2\tool.exe"));
+        assert!(glob_match(r"*\tool.exe", r"d:\tool.exe"));
+        assert!(!glob_match(r"c:\apps\*.exe", r"c:\other\tool.exe"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(glob_match("app?.exe", "app1.exe"));
+        assert!(!glob_match("app?.exe", "app.exe"));
+        assert!(!glob_match("app?.exe", "app12.exe"));
+    }
+
+    #[test]
+    fn trailing_star_matches_the_rest() {
+        assert!(glob_match(r"c:\apps\*", r"c:\apps\"));
+        assert!(glob_match(r"c:\apps\*", r"c:\apps\tool\tool.exe"));
+        assert!(glob_match("tool**", "tool"));
+        assert!(!glob_match(r"c:\apps\*", r"c:\app"));
+    }
+
+    #[test]
+    fn patterns_ignore_case_and_slash_direction() {
+        assert_eq!(normalize_pattern("C:/Program Files/App/*.EXE"), r"c:\program files\app\*.exe");
+        assert!(matches_pattern("C:/Apps/Tool/*.EXE", Path::new(r"C:\Apps\Tool\bin\Tool.exe")));
+    }
+
+    #[test]
+    fn versioned_folder_pattern() {
+        let pattern = r"C:\Users\me\AppData\Local\Discord\app-*\Discord.exe";
+        assert!(matches_pattern(pattern, Path::new(r"C:\Users\me\AppData\Local\Discord\app-1.0.9163\Discord.exe")));
+        assert!(!matches_pattern(pattern, Path::new(r"C:\Users\me\AppData\Local\Discord\Update.exe")));
+
+        let running: RunningSet = [r"c:\users\me\appdata\local\discord\app-1.0.9164\discord.exe".to_string()].into();
+        assert!(is_running_pattern(pattern, &running));
+    }
+
+    #[test]
+    fn target_names_collects_file_names() {
+        let mut separator = item(r"C:\Apps\Separator.exe");
+        separator.separator = true;
+        let names = target_names(&[
+            item(r"C:\Apps\Tool.EXE"),
+            matching(r"C:/Apps/app-*/Other.exe"),
+            separator,
+        ]).unwrap();
+        assert_eq!(names, ["tool.exe".to_string(), "other.exe".to_string()].into());
+    }
+
+    #[test]
+    fn wildcard_file_name_targets_every_process() {
+        assert_eq!(target_names(&[item(r"C:\Apps\Tool.exe"), matching(r"C:\Apps\*.exe")]), None);
+        assert_eq!(target_names(&[matching(r"C:\Apps\Tool?.exe")]), None);
+    }
+}
@@ -0,0 +1,116 @@
+//! AppBar registration so Windows reserves the bottom edge for the dock
+//!
+//! With `reserve_space` the dock registers like the taskbar does: maximized
+//! windows shrink to leave the dock's strip free. The shell notifies us of
+//! work-area changes (resolution, taskbar moves) through a callback message,
+//! which a window subclass turns into a fresh ABM_SETPOS.
+
+use std::cell::Cell;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::UI::Shell::{
+    DefSubclassProc, RemoveWindowSubclass, SHAppBarMessage, SetWindowSubclass, ABE_BOTTOM,
+    ABM_NEW, ABM_QUERYPOS, ABM_REMOVE, ABM_SETPOS, ABN_POSCHANGED, APPBARDATA,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN, WM_APP, WM_DISPLAYCHANGE,
+};
+
+/// Callback message the shell sends with ABN_* notifications
+const APPBAR_CALLBACK: u32 = WM_APP + 1;
+const SUBCLASS_ID: usize = 0x5244; // "RD"
+
+thread_local! {
+    static RESERVED_HEIGHT: Cell<u32> = const { Cell::new(0) };
+}
+
+pub struct AppBar {
+    hwnd: HWND,
+    height: u32,
+}
+
+impl AppBar {
+    /// Register `hwnd` as a bottom-edge AppBar reserving `height` pixels.
+    pub fn register(hwnd: HWND, height: u32) -> Option<Self> {
+        unsafe {
+            let mut data = appbar_data(hwnd);
+            data.uCallbackMessage = APPBAR_CALLBACK;
+            if SHAppBarMessage(ABM_NEW, &mut data) == 0 {
+                log::error!("Failed to register dock as an AppBar");
+                return None;
+            }
+            let _ = SetWindowSubclass(hwnd, Some(appbar_subclass_proc), SUBCLASS_ID, 0);
+        }
+
+        let mut appbar = Self { hwnd, height: 0 };
+        appbar.set_height(height);
+        Some(appbar)
+    }
+
+    /// Update the reserved strip, e.g. after the dock was resized.
+    pub fn set_height(&mut self, height: u32) {
+        if height == self.height {
+            return;
+        }
+        self.height = height;
+        RESERVED_HEIGHT.with(|h| h.set(height));
+        set_position(self.hwnd, height);
+    }
+
+    /// Give the reserved space back to the desktop.
+    pub fn unregister(self) {
+        unsafe {
+            let _ = RemoveWindowSubclass(self.hwnd, Some(appbar_subclass_proc), SUBCLASS_ID);
+            let mut data = appbar_data(self.hwnd);
+            SHAppBarMessage(ABM_REMOVE, &mut data);
+        }
+    }
+}
+
+fn appbar_data(hwnd: HWND) -> APPBARDATA {
+    APPBARDATA {
+        cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+        hWnd: hwnd,
+        ..Default::default()
+    }
+}
+
+/// Ask the shell for a bottom strip `height` tall across the primary screen.
+/// Only the reservation changes; the dock window positions itself.
+fn set_position(hwnd: HWND, height: u32) {
+    unsafe {
+        let screen_w = GetSystemMetrics(SM_CXSCREEN);
+        let screen_h = GetSystemMetrics(SM_CYSCREEN);
+
+        let mut data = appbar_data(hwnd);
+        data.uEdge = ABE_BOTTOM;
+        data.rc = RECT {
+            left: 0,
+            top: screen_h - height as i32,
+            right: screen_w,
+            bottom: screen_h,
+        };
+        SHAppBarMessage(ABM_QUERYPOS, &mut data);
+        // The shell may raise the bottom (other bars on this edge); keep our height
+        data.rc.top = data.rc.bottom - height as i32;
+        SHAppBarMessage(ABM_SETPOS, &mut data);
+    }
+}
+
+unsafe extern "system" fn appbar_subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    _data: usize,
+) -> LRESULT {
+    let reposition = match msg {
+        APPBAR_CALLBACK => wparam.0 as u32 == ABN_POSCHANGED,
+        WM_DISPLAYCHANGE => true,
+        _ => false,
+    };
+    if reposition {
+        set_position(hwnd, RESERVED_HEIGHT.with(|h| h.get()));
+    }
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}
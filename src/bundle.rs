@@ -0,0 +1,215 @@
+//! `rdock --export`/`--import`: share a config together with its icons
+//!
+//! A bundle is a zip holding `config.toml` and an `icons` folder with every
+//! icon file the items reference, their `icon` paths rewritten relative to the
+//! bundle. Importing extracts it beside the config and points the paths at
+//! the extracted files again. Zipping goes through the `tar.exe` that ships
+//! with Windows 10 and later (bsdtar), which reads and writes zip archives.
+
+use crate::config::{self, Config, DockItem};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Folder inside a bundle holding the icons
+const ICONS_DIR: &str = "icons";
+
+/// Folder beside config.toml that imported bundles are extracted into
+const IMPORT_DIR: &str = "bundles";
+
+/// Write the config at `config_path` and its icons to the zip at `bundle`.
+pub fn export(config_path: &Path, bundle: &Path) -> Result<()> {
+    let mut config = Config::load(config_path)?;
+    let bundle = std::path::absolute(bundle).context("Invalid bundle path")?;
+
+    let staging = std::env::temp_dir().join(format!("rdock-export-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(staging.join(ICONS_DIR))
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+
+    let result = (|| {
+        let mut copied: HashMap<PathBuf, PathBuf> = HashMap::new();
+        for item in config.items.iter_mut() {
+            bundle_icon(item, &staging, &mut copied)?;
+            for child in item.children.iter_mut() {
+                bundle_icon(child, &staging, &mut copied)?;
+            }
+        }
+
+        // Start from the original file so the [dock] comments come along
+        let bundled_config = staging.join("config.toml");
+        std::fs::copy(config_path, &bundled_config)
+            .with_context(|| format!("Failed to copy {}", config_path.display()))?;
+        config.save(&bundled_config)?;
+
+        run_tar(&staging, &["-a", "-c", "-f", &bundle.to_string_lossy(), "config.toml", ICONS_DIR])?;
+        println!("Exported {} items and {} icons to {}", config.items.len(), copied.len(), bundle.display());
+        Ok(())
+    })();
+
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+/// Replace the config at `config_path` with the one in `bundle`. The previous
+/// config is kept as `config.toml.bak`, as on every save.
+pub fn import(bundle: &Path, config_path: &Path) -> Result<()> {
+    let bundle = std::path::absolute(bundle).context("Invalid bundle path")?;
+    if !bundle.exists() {
+        anyhow::bail!("Bundle not found: {}", bundle.display());
+    }
+
+    let name = bundle.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let dest = config_path.with_file_name(IMPORT_DIR).join(name);
+    std::fs::create_dir_all(&dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+    run_tar(&dest, &["-x", "-f", &bundle.to_string_lossy()])?;
+
+    let bundled_config = dest.join("config.toml");
+    let mut config = Config::load(&bundled_config)
+        .with_context(|| format!("{} has no usable config.toml", bundle.display()))?;
+    for item in config.items.iter_mut() {
+        unbundle_icon(item, &dest);
+        for child in item.children.iter_mut() {
+            unbundle_icon(child, &dest);
+        }
+    }
+
+    // Save with the rewritten paths beside the icons, then swap it in whole
+    config.save(&bundled_config)?;
+    let content = std::fs::read_to_string(&bundled_config)
+        .with_context(|| format!("Failed to read {}", bundled_config.display()))?;
+    if config_path.exists() {
+        println!("Previous config saved as {}", config_path.with_extension("toml.bak").display());
+    }
+    config::write_config_file(config_path, &content)?;
+
+    println!("Imported {} items from {} (icons in {})", config.items.len(), bundle.display(), dest.display());
+    Ok(())
+}
+
+/// Copy `item`'s icon into the staging folder and point the icon at the copy.
+/// Icons are copied once each; file names that collide get a number prefix.
+fn bundle_icon(item: &mut DockItem, staging: &Path, copied: &mut HashMap<PathBuf, PathBuf>) -> Result<()> {
+    let Some(icon) = item.icon.clone() else { return Ok(()) };
+    if let Some(relative) = copied.get(&icon) {
+        item.icon = Some(relative.clone());
+        return Ok(());
+    }
+    if !icon.is_file() {
+        println!("Warning: icon for \"{}\" not found, left as {}", item.name, icon.display());
+        return Ok(());
+    }
+
+    let file_name = icon.file_name().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut relative = Path::new(ICONS_DIR).join(&file_name);
+    let mut n = 1;
+    while staging.join(&relative).exists() {
+        n += 1;
+        relative = Path::new(ICONS_DIR).join(format!("{}-{}", n, file_name));
+    }
+    std::fs::copy(&icon, staging.join(&relative))
+        .with_context(|| format!("Failed to copy icon {}", icon.display()))?;
+
+    copied.insert(icon, relative.clone());
+    item.icon = Some(relative);
+    Ok(())
+}
+
+/// Resolve a bundle-relative icon path against the extracted bundle
+fn unbundle_icon(item: &mut DockItem, dest: &Path) {
+    if let Some(icon) = &item.icon {
+        if icon.is_relative() && dest.join(icon).is_file() {
+            item.icon = Some(dest.join(icon));
+        }
+    }
+}
+
+/// Windows' own bsdtar. A GNU tar earlier on PATH (Git for Windows, MSYS)
+/// ignores `-a` and writes a plain tar instead of a zip.
+fn system_tar() -> PathBuf {
+    let root = std::env::var_os("SystemRoot").map_or_else(|| PathBuf::from(r"C:\Windows"), PathBuf::from);
+    root.join("System32").join("tar.exe")
+}
+
+fn run_tar(dir: &Path, args: &[&str]) -> Result<()> {
+    let mut cmd = Command::new(system_tar());
+    cmd.args(args).current_dir(dir);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let output = cmd.output().context("Failed to run tar.exe (included with Windows 10 and later)")?;
+    if !output.status.success() {
+        anyhow::bail!("tar.exe failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty folder under the temp dir, unique to this test run
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rdock_test_bundle_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn item_with_icon(icon: PathBuf) -> DockItem {
+        let mut item = DockItem::from_exe(PathBuf::from(r"C:\Apps\app.exe"));
+        item.icon = Some(icon);
+        item
+    }
+
+    #[test]
+    fn bundled_icons_are_copied_once_and_renamed_on_collision() {
+        let source = temp_dir("source");
+        let staging = temp_dir("staging");
+        std::fs::create_dir_all(source.join("a")).unwrap();
+        std::fs::create_dir_all(source.join("b")).unwrap();
+        std::fs::create_dir_all(staging.join(ICONS_DIR)).unwrap();
+        std::fs::write(source.join("a").join("app.png"), b"a").unwrap();
+        std::fs::write(source.join("b").join("app.png"), b"b").unwrap();
+
+        let mut copied = HashMap::new();
+        let mut first = item_with_icon(source.join("a").join("app.png"));
+        let mut second = item_with_icon(source.join("b").join("app.png"));
+        let mut again = item_with_icon(source.join("a").join("app.png"));
+        let mut missing = item_with_icon(source.join("missing.png"));
+        for item in [&mut first, &mut second, &mut again, &mut missing] {
+            bundle_icon(item, &staging, &mut copied).unwrap();
+        }
+
+        assert_eq!(first.icon, Some(Path::new(ICONS_DIR).join("app.png")));
+        assert_eq!(second.icon, Some(Path::new(ICONS_DIR).join("2-app.png")));
+        assert_eq!(again.icon, first.icon);
+        assert_eq!(missing.icon, Some(source.join("missing.png")));
+        assert_eq!(copied.len(), 2);
+        assert_eq!(std::fs::read(staging.join(ICONS_DIR).join("2-app.png")).unwrap(), b"b");
+
+        let _ = std::fs::remove_dir_all(&source);
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+
+    #[test]
+    fn unbundled_icons_point_into_the_extracted_bundle() {
+        let dest = temp_dir("dest");
+        std::fs::create_dir_all(dest.join(ICONS_DIR)).unwrap();
+        std::fs::write(dest.join(ICONS_DIR).join("app.png"), b"").unwrap();
+
+        let mut bundled = item_with_icon(Path::new(ICONS_DIR).join("app.png"));
+        unbundle_icon(&mut bundled, &dest);
+        assert_eq!(bundled.icon, Some(dest.join(ICONS_DIR).join("app.png")));
+
+        // Icons the bundle doesn't have are left alone
+        let mut absent = item_with_icon(Path::new(ICONS_DIR).join("other.png"));
+        unbundle_icon(&mut absent, &dest);
+        assert_eq!(absent.icon, Some(Path::new(ICONS_DIR).join("other.png")));
+
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// CSS-style spacing: can be single value, [x, y], or [top, right, bottom, left]
 #[derive(Debug, Clone)]
@@ -115,6 +117,77 @@ impl Default for ItemSpacing {
     fn default() -> Self { Self::uniform(12) }
 }
 
+/// Icon size: pixels, or a string like "5%" of the monitor's shorter side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IconSize {
+    Pixels(u32),
+    Percent(f32),
+}
+
+/// Shorter screen side assumed for percentages before the monitor is known
+const REFERENCE_SCREEN_SIDE: u32 = 1080;
+
+impl IconSize {
+    /// Size in pixels on a monitor whose shorter side is `screen_side` pixels
+    pub fn resolve(self, screen_side: u32) -> u32 {
+        match self {
+            IconSize::Pixels(px) => px,
+            IconSize::Percent(pct) => ((screen_side as f32 * pct / 100.0).round() as u32).max(16),
+        }
+    }
+    
+    /// Size in pixels, with percentages taken of a 1080 pixel screen. The dock
+    /// resolves percentages against the real monitor before rendering.
+    pub fn pixels(self) -> u32 {
+        self.resolve(REFERENCE_SCREEN_SIDE)
+    }
+}
+
+impl std::fmt::Display for IconSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IconSize::Pixels(px) => write!(f, "{} px", px),
+            IconSize::Percent(pct) => write!(f, "{}%", pct),
+        }
+    }
+}
+
+impl Serialize for IconSize {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where S: Serializer
+    {
+        match self {
+            IconSize::Pixels(px) => serializer.serialize_u32(*px),
+            IconSize::Percent(pct) => serializer.serialize_str(&format!("{}%", pct)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IconSize {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum IconSizeValue {
+            Pixels(u32),
+            Text(String),
+        }
+        
+        match IconSizeValue::deserialize(deserializer)? {
+            IconSizeValue::Pixels(px) => Ok(IconSize::Pixels(px)),
+            IconSizeValue::Text(text) => text.trim()
+                .strip_suffix('%')
+                .and_then(|pct| pct.trim().parse::<f32>().ok())
+                .filter(|pct| *pct > 0.0 && *pct <= 50.0)
+                .map(IconSize::Percent)
+                .ok_or_else(|| serde::de::Error::custom(format!(
+                    "invalid icon_size {:?}: expected pixels or a percentage like \"5%\"", text
+                ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub dock: DockSettings,
@@ -125,35 +198,511 @@ pub struct Config {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DockSettings {
     #[serde(default = "default_icon_size")]
-    pub icon_size: u32,
+    pub icon_size: IconSize,
     #[serde(default)]
     pub spacing: ItemSpacing,
     #[serde(default)]
     pub padding: Spacing,
     #[serde(default = "default_negative_vertical_offset", alias = "vertical_offset")]
     pub negative_vertical_offset: i32,
+    /// Lift the shown dock this many pixels off the screen edge (a floating
+    /// dock), instead of `negative_vertical_offset`; the reveal zone stays at
+    /// the edge itself
+    #[serde(default)]
+    pub edge_margin: u32,
+    /// Which part of the screen the dock lines up with horizontally
+    #[serde(default)]
+    pub anchor: Anchor,
+    /// Move the dock this many pixels in from its anchored edge (right of
+    /// center for the centered dock; negative moves it left)
+    #[serde(default)]
+    pub anchor_offset_px: i32,
+    /// Open a dock on every monitor instead of only the primary one; each
+    /// hides and reveals at its own monitor's bottom edge
+    #[serde(default)]
+    pub all_monitors: bool,
+    /// What a dock too wide for its monitor does: run past the screen edges
+    /// or show a screen's worth of items and scroll through the rest
+    #[serde(default)]
+    pub overflow: Overflow,
     #[serde(default = "default_background_color")]
     pub background_color: String,
     #[serde(default = "default_background_opacity")]
     pub background_opacity: f32,
+    /// Fill the background with the plain color, without the glass highlight
+    /// and top-to-bottom gradient
+    #[serde(default)]
+    pub flat_background: bool,
+    /// Rise into place and fade in when the dock first appears
+    #[serde(default)]
+    pub intro_animation: bool,
     #[serde(default = "default_indicator_color")]
     pub indicator_color: String,
+    /// Distance in pixels from the bottom of the icons to the running indicator
+    #[serde(default = "default_indicator_offset")]
+    pub indicator_offset: u32,
     #[serde(default = "default_auto_hide")]
     pub auto_hide: bool,
     #[serde(default = "default_auto_hide_delay")]
     pub auto_hide_delay_ms: u64,
     #[serde(default = "default_auto_show_delay")]
     pub auto_show_delay_ms: u64,
+    /// Clicks on an item are ignored this long after it launched, so a slow
+    /// app isn't started twice before its window appears (0 = off)
+    #[serde(default = "default_launch_cooldown")]
+    pub launch_cooldown_ms: u64,
+    /// How far the cursor must move with the button held before a drag starts
+    #[serde(default = "default_drag_threshold")]
+    pub drag_threshold_px: u32,
+    /// Dropping a dragged item well above the dock removes it (after asking)
+    #[serde(default = "default_drag_to_remove")]
+    pub drag_to_remove: bool,
     #[serde(default = "default_corner_radius")]
     pub corner_radius: u32,
     #[serde(default = "default_magnification")]
     pub magnification: f32,
+    /// "wave" (neighbors grow too), "single" (only the hovered icon), or "none"
+    #[serde(default)]
+    pub magnification_mode: MagnificationMode,
+    /// Keep click and hover targets at the unmagnified layout while icons
+    /// still zoom. Off (the default), targets grow with the magnified icons.
+    #[serde(default)]
+    pub fixed_hit_area: bool,
+    /// Scrolling over the dock moves the magnification focus instead of the
+    /// pointer, so a touchpad can preview icons before clicking
+    #[serde(default)]
+    pub scroll_magnify: bool,
     #[serde(default = "default_locked")]
     pub locked: bool,
     #[serde(default = "default_hide_windows_taskbar", alias = "hide_taskbar")]
     pub hide_windows_taskbar: bool,
+    /// "hide", "autohide", or "leave"; overrides `hide_windows_taskbar` when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taskbar_mode: Option<TaskbarMode>,
     #[serde(default = "default_hide_in_fullscreen")]
     pub hide_in_fullscreen: bool,
+    /// Executables (e.g. "vlc.exe") that hide the dock while in the
+    /// foreground, like a fullscreen app does
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hide_when_focused: Vec<String>,
+    /// Don't auto-hide while the foreground window belongs to one of the
+    /// dock's items (fullscreen and `hide_when_focused` still hide it)
+    #[serde(default)]
+    pub stay_visible_when_app_active: bool,
+    /// Keep the dock above other windows; when off it sits in the normal
+    /// Z-order and is raised once each time it is revealed
+    #[serde(default = "default_always_on_top")]
+    pub always_on_top: bool,
+    /// Append running apps that aren't pinned after the pinned items
+    #[serde(default)]
+    pub show_running: bool,
+    /// Collapse adjacent separators and drop ones at either end of the dock.
+    /// Only the dock's display is tidied; the file keeps every separator.
+    #[serde(default)]
+    pub tidy_separators: bool,
+    /// Show a message box when launching an item fails (failures are always logged)
+    #[serde(default)]
+    pub notify_on_error: bool,
+    /// Draw the running indicator in amber while an app isn't responding
+    #[serde(default)]
+    pub show_not_responding: bool,
+    /// Height of the reveal zone at the bottom screen edge (0 disables it)
+    #[serde(default = "default_trigger_distance")]
+    pub trigger_distance_px: u32,
+    /// Screen corner that also reveals the dock when the cursor parks there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hot_corner: Option<HotCorner>,
+    /// How auto-hide hides the dock: slide off the bottom edge or fade out in place
+    #[serde(default)]
+    pub hide_style: HideStyle,
+    /// What marks the dock coming back from auto-hide: nothing, a brief
+    /// brightening of its background, or a short chime
+    #[serde(default)]
+    pub reveal_feedback: RevealFeedback,
+    /// Reserve the dock's strip like the taskbar so maximized windows stop
+    /// above it (ignored while auto_hide is on)
+    #[serde(default)]
+    pub reserve_space: bool,
+    /// How separators are drawn: "line", "dot", or "space" (gap only)
+    #[serde(default)]
+    pub separator_style: SeparatorStyle,
+    /// Width of a separator slot in pixels (default: icon_size / 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator_width: Option<u32>,
+    /// Round icon corners by this many pixels at `icon_size` (0 keeps them square)
+    #[serde(default)]
+    pub icon_corner_radius: u32,
+    /// Folder of replacement icons matched by item name or exe name
+    /// (e.g. `firefox.png`), used for items without an explicit `icon`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_theme: Option<PathBuf>,
+    /// Look of the stand-in tile for items without an icon
+    #[serde(default)]
+    pub placeholder_style: PlaceholderStyle,
+    /// "full", "lite" (no glow, bilinear scaling), or "none" (also no
+    /// reflections), for machines where magnification stutters
+    #[serde(default)]
+    pub effects: Effects,
+    /// Play animated GIF/PNG `icon` files; off keeps the first frame and lets
+    /// the dock idle between redraws
+    #[serde(default)]
+    pub animated_icons: bool,
+    /// Composite icons in linear light instead of directly on sRGB values, so
+    /// soft and anti-aliased edges don't darken against the background
+    #[serde(default)]
+    pub gamma_correct_blending: bool,
+    /// Icons are loaded at this multiple of `icon_size` so magnified icons stay sharp
+    #[serde(default = "default_icon_load_scale")]
+    pub icon_load_scale: u32,
+    /// Upper bound on the loaded icon resolution; memory per icon grows with its square
+    #[serde(default = "default_max_icon_load_px")]
+    pub max_icon_load_px: u32,
+    /// Start in compact mode (toggled from the tray menu)
+    #[serde(default)]
+    pub compact: bool,
+    /// Size multiplier for icons, spacing, and padding in compact mode
+    #[serde(default = "default_compact_scale")]
+    pub compact_scale: f32,
+    /// Save the compact toggle back to `compact` so it survives restarts
+    #[serde(default)]
+    pub remember_compact: bool,
+    /// Global hotkey toggling presentation mode, e.g. "Ctrl+Alt+P"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation_hotkey: Option<String>,
+    /// Extra mouse buttons (by number: 4 = back, 5 = forward) mapped to
+    /// actions like "toggle_dock", while the cursor is over the dock
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub button_bindings: BTreeMap<String, String>,
+    /// What clicking an item does while Shift, Ctrl or Alt is held
+    #[serde(default)]
+    pub modifier_clicks: ModifierClicks,
+    /// How often the dock polls for changes, in milliseconds
+    #[serde(default)]
+    pub intervals: Intervals,
+    /// More config files whose [[items]] are added after this file's,
+    /// relative to the directory of the file that lists them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskbarMode {
+    /// Hide the taskbar window outright (and keep re-hiding it)
+    Hide,
+    /// Turn on the taskbar's own auto-hide setting while rDock runs
+    #[serde(alias = "auto_hide", alias = "auto-hide")]
+    AutoHide,
+    /// Don't touch the taskbar
+    Leave,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeparatorStyle {
+    #[default]
+    Line,
+    Dot,
+    Space,
+}
+
+/// Which icons grow toward `magnification` under the cursor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MagnificationMode {
+    /// The hovered icon and, falling off with distance, its neighbors
+    #[default]
+    Wave,
+    /// Only the icon under the cursor
+    Single,
+    /// Icons keep their size
+    None,
+}
+
+/// How much per-frame detail the software renderer draws
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Effects {
+    /// Glow, bicubic scaling, and reflections
+    #[default]
+    Full,
+    /// No glow; icons and reflections scaled bilinearly
+    Lite,
+    /// Bilinear icons only, no glow or reflections
+    None,
+}
+
+/// How items without a loadable icon are drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceholderStyle {
+    /// Plain rounded tile
+    #[default]
+    Square,
+    /// Tile with the item name's first letter
+    Monogram,
+}
+
+/// How far below the screen's bottom edge the hidden dock's top sits
+const HIDDEN_MARGIN: i32 = 20;
+
+/// Window position of the dock in screen pixels, from `DockSettings::layout`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub x: i32,
+    /// Top of the window while the dock is shown
+    pub y_visible: i32,
+    /// Top of the window while the dock is slid away
+    pub y_hidden: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Anchor {
+    #[default]
+    Center,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HideStyle {
+    #[default]
+    Slide,
+    Fade,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevealFeedback {
+    #[default]
+    None,
+    Pulse,
+    Sound,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Overflow {
+    /// Also accepted as "wrap", which has no multi-row layout of its own
+    #[default]
+    #[serde(alias = "wrap")]
+    Visible,
+    Scroll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HotCorner {
+    #[serde(alias = "bottom_left")]
+    BottomLeft,
+    #[serde(alias = "bottom_right")]
+    BottomRight,
+}
+
+/// Width of a spacer added from the context menu, in pixels
+pub const DEFAULT_SPACER_WIDTH: u32 = 32;
+
+/// Largest accepted `trigger_distance_px`; anything taller eats into normal use
+pub const MAX_TRIGGER_DISTANCE: u32 = 50;
+
+/// `[dock.intervals]`: polling periods. Longer saves power, shorter makes the
+/// running indicators and auto-hide react sooner.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Intervals {
+    /// Running-app check (indicators and `show_running`)
+    #[serde(default = "default_process_check_ms")]
+    pub process_check_ms: u64,
+    /// Cursor position check for the reveal zone and hot corner
+    #[serde(default = "default_mouse_poll_ms")]
+    pub mouse_poll_ms: u64,
+    /// Re-hiding the Windows taskbar after Explorer shows it again
+    #[serde(default = "default_taskbar_check_ms")]
+    pub taskbar_check_ms: u64,
+    /// Fullscreen app detection for `hide_in_fullscreen`
+    #[serde(default = "default_fullscreen_check_ms")]
+    pub fullscreen_check_ms: u64,
+}
+
+/// Shortest accepted interval, so a typo can't turn polling into a busy loop
+const MIN_INTERVAL_MS: u64 = 10;
+
+impl Intervals {
+    pub fn process_check(&self) -> Duration {
+        Duration::from_millis(self.process_check_ms.max(MIN_INTERVAL_MS))
+    }
+    
+    pub fn mouse_poll(&self) -> Duration {
+        Duration::from_millis(self.mouse_poll_ms.max(MIN_INTERVAL_MS))
+    }
+    
+    pub fn taskbar_check(&self) -> Duration {
+        Duration::from_millis(self.taskbar_check_ms.max(MIN_INTERVAL_MS))
+    }
+    
+    pub fn fullscreen_check(&self) -> Duration {
+        Duration::from_millis(self.fullscreen_check_ms.max(MIN_INTERVAL_MS))
+    }
+}
+
+/// What a click on an item does, for `[dock.modifier_clicks]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    /// Same as a plain click: focus the app's window or start it
+    Launch,
+    /// Start another copy even if the app already has a window
+    NewInstance,
+    /// Show the item's file in Explorer
+    OpenLocation,
+    /// Open the item editor (pinned items on an unlocked dock)
+    Edit,
+}
+
+/// `[dock.modifier_clicks]`: the click action for each modifier. With
+/// several held, Ctrl wins over Alt and Alt over Shift.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ModifierClicks {
+    #[serde(default = "default_shift_click")]
+    pub shift: ClickAction,
+    #[serde(default = "default_ctrl_click")]
+    pub ctrl: ClickAction,
+    #[serde(default = "default_alt_click")]
+    pub alt: ClickAction,
+}
+
+fn default_shift_click() -> ClickAction { ClickAction::NewInstance }
+fn default_ctrl_click() -> ClickAction { ClickAction::OpenLocation }
+fn default_alt_click() -> ClickAction { ClickAction::Edit }
+
+impl Default for ModifierClicks {
+    fn default() -> Self {
+        Self {
+            shift: default_shift_click(),
+            ctrl: default_ctrl_click(),
+            alt: default_alt_click(),
+        }
+    }
+}
+
+impl Default for Intervals {
+    fn default() -> Self {
+        Self {
+            process_check_ms: default_process_check_ms(),
+            mouse_poll_ms: default_mouse_poll_ms(),
+            taskbar_check_ms: default_taskbar_check_ms(),
+            fullscreen_check_ms: default_fullscreen_check_ms(),
+        }
+    }
+}
+
+impl DockSettings {
+    /// `trigger_distance_px` clamped to 0..=MAX_TRIGGER_DISTANCE
+    pub fn trigger_distance(&self) -> u32 {
+        self.trigger_distance_px.min(MAX_TRIGGER_DISTANCE)
+    }
+    
+    /// Separator slot width: `separator_width` if set (at most one icon wide),
+    /// otherwise a third of the icon size.
+    pub fn separator_slot(&self) -> u32 {
+        let icon_size = self.icon_size.pixels();
+        self.separator_width
+            .map(|w| w.clamp(1, icon_size))
+            .unwrap_or(icon_size / 3)
+    }
+    
+    /// How far the bottom of the shown dock sits below the screen edge:
+    /// `negative_vertical_offset`, or minus `edge_margin` when the dock floats.
+    pub fn edge_offset(&self) -> i32 {
+        if self.edge_margin > 0 {
+            -(self.edge_margin as i32)
+        } else {
+            self.negative_vertical_offset
+        }
+    }
+
+    /// Where a `dock_width` x `dock_height` dock goes on the screen: `dock_x`
+    /// across, its shown position from `edge_offset`, and a hidden position
+    /// fully below the screen so the cursor can't enter it directly (the
+    /// reveal zone is watched by polling the cursor instead).
+    pub fn layout(&self, screen_width: u32, screen_height: u32, dock_width: u32, dock_height: u32) -> Layout {
+        Layout {
+            x: self.dock_x(screen_width, dock_width),
+            // Positive offset = move down (bury into edge), negative = float above it
+            y_visible: screen_height as i32 - dock_height as i32 + self.edge_offset(),
+            y_hidden: screen_height as i32 + HIDDEN_MARGIN,
+        }
+    }
+
+    /// Left edge of a dock `dock_width` wide on a screen `screen_width` wide,
+    /// from `anchor` and `anchor_offset_px`. Kept on screen, so a dock that
+    /// changes width keeps its anchored edge where it was.
+    pub fn dock_x(&self, screen_width: u32, dock_width: u32) -> i32 {
+        let room = screen_width as i32 - dock_width as i32;
+        let x = match self.anchor {
+            Anchor::Center => room / 2 + self.anchor_offset_px,
+            Anchor::Left => self.anchor_offset_px,
+            Anchor::Right => room - self.anchor_offset_px,
+        };
+        x.clamp(0, room.max(0))
+    }
+
+    /// Side length icons are loaded and stored at: `icon_load_scale` times the
+    /// icon size, at least 384, capped by `max_icon_load_px`, and never below
+    /// the icon size itself.
+    pub fn icon_load_size(&self) -> u32 {
+        let icon_size = self.icon_size.pixels();
+        (icon_size * self.icon_load_scale.clamp(1, 8))
+            .max(384)
+            .min(self.max_icon_load_px)
+            .max(icon_size)
+    }
+    
+    /// Whether `exe` is listed in `hide_when_focused`, by file name with or
+    /// without the extension, ignoring case
+    pub fn hides_for(&self, exe: &Path) -> bool {
+        let names = [exe.file_name(), exe.file_stem()];
+        self.hide_when_focused.iter().any(|listed| {
+            names.iter().flatten().any(|name| name.to_string_lossy().eq_ignore_ascii_case(listed))
+        })
+    }
+    
+    /// How to treat the Windows taskbar: `taskbar_mode` if given, otherwise
+    /// derived from the older `hide_windows_taskbar` flag.
+    pub fn taskbar_mode(&self) -> TaskbarMode {
+        self.taskbar_mode.unwrap_or(if self.hide_windows_taskbar {
+            TaskbarMode::Hide
+        } else {
+            TaskbarMode::Leave
+        })
+    }
+    
+    /// Copy with a percentage `icon_size` turned into pixels for a monitor
+    /// of `width` x `height`.
+    pub fn for_screen(&self, width: u32, height: u32) -> Self {
+        let mut dock = self.clone();
+        dock.icon_size = IconSize::Pixels(self.icon_size.resolve(width.min(height)));
+        dock
+    }
+    
+    /// Copy with icon size, spacing, and padding multiplied by `factor`.
+    pub fn scaled(&self, factor: f32) -> Self {
+        let scale = |v: u32| (v as f32 * factor).round() as u32;
+        let mut dock = self.clone();
+        dock.icon_size = IconSize::Pixels(scale(self.icon_size.pixels()).max(16));
+        dock.spacing = ItemSpacing { x: scale(self.spacing.x), y: scale(self.spacing.y) };
+        dock.padding = Spacing {
+            top: scale(self.padding.top),
+            right: scale(self.padding.right),
+            bottom: scale(self.padding.bottom),
+            left: scale(self.padding.left),
+        };
+        dock.separator_width = self.separator_width.map(scale);
+        dock.icon_corner_radius = scale(self.icon_corner_radius);
+        dock.indicator_offset = scale(self.indicator_offset);
+        dock
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -167,10 +716,47 @@ pub struct DockItem {
     pub args: Vec<String>,
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub separator: bool,
+    /// On a separator: start a new region, drawn as a wider gap with the dock
+    /// background tinted differently from here to the next region
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub region: bool,
+    /// A transparent gap this many pixels wide, with no divider drawn;
+    /// otherwise it behaves like a separator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spacer: Option<u32>,
     /// Special system item type: "start_menu", "recycle_bin", "settings", "show_desktop", 
     /// "task_view", "action_center", "file_explorer", "control_panel", "run_dialog"
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub special: Option<String>,
+    /// Glob matched against running executable paths for the running indicator
+    /// (e.g. `C:\Apps\Foo\*\foo.exe`). Falls back to an exact `path` match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_pattern: Option<String>,
+    /// Ask this question (Yes/No) before launching, for destructive items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm: Option<String>,
+    /// Command line run through `cmd /c` instead of launching `path`;
+    /// prefix with `ps:` to run it in PowerShell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Hover text, when it should say more than `name`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    /// Short text (an unread count, say) drawn in a pill on the icon's
+    /// top-right corner; `rdock --badge` sets one while the dock runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub badge: Option<String>,
+    /// Kept in the config but left out of the dock until shown again
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Items of a group: clicking the item opens a flyout listing these
+    /// instead of launching anything. Groups inside groups aren't opened.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DockItem>,
+    /// The `include`d file this item was read from, or `None` for the main
+    /// config. Included items are shown but never saved back.
+    #[serde(skip)]
+    pub included_from: Option<PathBuf>,
 }
 
 fn is_default_path(p: &Path) -> bool {
@@ -185,29 +771,109 @@ impl DockItem {
             icon: None,
             args: Vec::new(),
             separator: true,
+            region: false,
+            spacer: None,
+            special: None,
+            match_pattern: None,
+            confirm: None,
+            command: None,
+            tooltip: None,
+            badge: None,
+            hidden: false,
+            children: Vec::new(),
+            included_from: None,
+        }
+    }
+    
+    /// Spacer of `DEFAULT_SPACER_WIDTH`, as added from the context menu
+    pub fn new_spacer() -> Self {
+        Self {
+            spacer: Some(DEFAULT_SPACER_WIDTH),
+            separator: false,
+            ..Self::new_separator()
+        }
+    }
+    
+    /// Item launching an executable, named after its file stem.
+    pub fn from_exe(path: PathBuf) -> Self {
+        let name = path.file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            name,
+            path,
+            icon: None,
+            args: Vec::new(),
+            separator: false,
+            region: false,
+            spacer: None,
             special: None,
+            match_pattern: None,
+            confirm: None,
+            command: None,
+            tooltip: None,
+            badge: None,
+            hidden: false,
+            children: Vec::new(),
+            included_from: None,
         }
     }
     
     pub fn is_separator(&self) -> bool {
-        self.separator || self.name == "---"
+        self.separator || self.spacer.is_some() || self.name == "---"
+    }
+    
+    /// Separator that starts a new background region
+    pub fn is_region(&self) -> bool {
+        self.region && self.is_separator()
+    }
+    
+    pub fn is_group(&self) -> bool {
+        !self.children.is_empty()
+    }
+    
+    /// Text shown on hover: `tooltip` unless it's blank, otherwise the name.
+    pub fn tooltip_text(&self) -> &str {
+        match self.tooltip.as_deref().map(str::trim) {
+            Some(tooltip) if !tooltip.is_empty() => tooltip,
+            _ => &self.name,
+        }
+    }
+    
+    /// Caption for a labeled separator: `separator = true` with a name other than "---".
+    pub fn separator_label(&self) -> Option<&str> {
+        let name = self.name.trim();
+        (self.separator && !name.is_empty() && name != "---").then_some(name)
     }
     
 }
 
-fn default_icon_size() -> u32 { 48 }
+fn default_icon_size() -> IconSize { IconSize::Pixels(48) }
 fn default_negative_vertical_offset() -> i32 { 8 }
 fn default_background_color() -> String { "#1a1928".to_string() }
 fn default_background_opacity() -> f32 { 1.0 }
 fn default_indicator_color() -> String { "#f38ba8".to_string() }
+fn default_indicator_offset() -> u32 { 12 }
 fn default_auto_hide() -> bool { true }
 fn default_auto_hide_delay() -> u64 { 250 }
 fn default_auto_show_delay() -> u64 { 250 }
+fn default_launch_cooldown() -> u64 { 1500 }
+fn default_drag_threshold() -> u32 { 5 }
+fn default_process_check_ms() -> u64 { 5000 }
+fn default_mouse_poll_ms() -> u64 { 50 }
+fn default_taskbar_check_ms() -> u64 { 1000 }
+fn default_fullscreen_check_ms() -> u64 { 500 }
+fn default_drag_to_remove() -> bool { true }
 fn default_corner_radius() -> u32 { 12 }
 fn default_magnification() -> f32 { 1.5 }
 fn default_locked() -> bool { true }
 fn default_hide_windows_taskbar() -> bool { true }
 fn default_hide_in_fullscreen() -> bool { true }
+fn default_always_on_top() -> bool { true }
+fn default_trigger_distance() -> u32 { 2 }
+fn default_compact_scale() -> f32 { 0.75 }
+fn default_icon_load_scale() -> u32 { 6 }
+fn default_max_icon_load_px() -> u32 { 1024 }
 
 impl Default for DockSettings {
     fn default() -> Self {
@@ -216,17 +882,62 @@ impl Default for DockSettings {
             spacing: ItemSpacing::default(),
             padding: Spacing::default(),
             negative_vertical_offset: default_negative_vertical_offset(),
+            edge_margin: 0,
+            anchor: Anchor::default(),
+            anchor_offset_px: 0,
+            all_monitors: false,
+            overflow: Overflow::default(),
             background_color: default_background_color(),
             background_opacity: default_background_opacity(),
+            flat_background: false,
+            intro_animation: false,
             indicator_color: default_indicator_color(),
+            indicator_offset: default_indicator_offset(),
             auto_hide: default_auto_hide(),
             auto_hide_delay_ms: default_auto_hide_delay(),
             auto_show_delay_ms: default_auto_show_delay(),
+            launch_cooldown_ms: default_launch_cooldown(),
+            drag_threshold_px: default_drag_threshold(),
+            drag_to_remove: default_drag_to_remove(),
             corner_radius: default_corner_radius(),
             magnification: default_magnification(),
+            magnification_mode: MagnificationMode::default(),
+            fixed_hit_area: false,
+            scroll_magnify: false,
             locked: default_locked(),
             hide_windows_taskbar: default_hide_windows_taskbar(),
+            taskbar_mode: None,
             hide_in_fullscreen: default_hide_in_fullscreen(),
+            hide_when_focused: Vec::new(),
+            stay_visible_when_app_active: false,
+            always_on_top: default_always_on_top(),
+            show_running: false,
+            tidy_separators: false,
+            notify_on_error: false,
+            show_not_responding: false,
+            trigger_distance_px: default_trigger_distance(),
+            hot_corner: None,
+            hide_style: HideStyle::default(),
+            reveal_feedback: RevealFeedback::default(),
+            reserve_space: false,
+            separator_style: SeparatorStyle::default(),
+            separator_width: None,
+            icon_corner_radius: 0,
+            icon_theme: None,
+            placeholder_style: PlaceholderStyle::default(),
+            effects: Effects::default(),
+            compact: false,
+            compact_scale: default_compact_scale(),
+            animated_icons: false,
+            gamma_correct_blending: false,
+            icon_load_scale: default_icon_load_scale(),
+            max_icon_load_px: default_max_icon_load_px(),
+            remember_compact: false,
+            presentation_hotkey: None,
+            button_bindings: BTreeMap::new(),
+            modifier_clicks: ModifierClicks::default(),
+            intervals: Intervals::default(),
+            include: Vec::new(),
         }
     }
 }
@@ -235,11 +946,29 @@ impl Config {
     pub fn load(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-        let config: Config = toml::from_str(&content)
+        let mut config: Config = toml::from_str(&content)
             .with_context(|| "Failed to parse config file")?;
+        // Included items follow the main file's, in `include` order, each
+        // file's own includes right after its items
+        let mut chain = vec![path.canonicalize().unwrap_or_else(|_| path.to_path_buf())];
+        for include in &config.dock.include {
+            load_included(&resolve_include(path, include), &mut chain, &mut config.items)?;
+        }
+        for problem in config.problems() {
+            log::warn!("{}: {}", path.display(), problem);
+        }
         Ok(config)
     }
     
+    /// Settings that parse but can't be used as written; the dock runs with
+    /// a fallback for each. `--doctor` lists them.
+    pub fn problems(&self) -> Vec<String> {
+        [("background_color", &self.dock.background_color), ("indicator_color", &self.dock.indicator_color)]
+            .into_iter()
+            .filter_map(|(key, value)| parse_color(value).err().map(|e| format!("dock.{}: {}", key, e)))
+            .collect()
+    }
+    
     pub fn save(&self, path: &Path) -> Result<()> {
         // Try to preserve comments/formatting in the [dock] section by only
         // regenerating the [[items]] portion of the file.
@@ -248,53 +977,92 @@ impl Config {
         } else {
             self.serialize_full()
         };
-        std::fs::write(path, &content)
-            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
-        Ok(())
+        write_config_file(path, &content)
+    }
+    
+    /// Like `save`, but also writes back [dock] settings that differ from the
+    /// file. Only changed keys are touched so comments and layout survive.
+    pub fn save_with_dock(&self, path: &Path) -> Result<()> {
+        let content = if let Ok(original) = std::fs::read_to_string(path) {
+            let header = self.merge_dock_settings(config_header(&original))?;
+            format!("{}\n\n{}", header.trim_end(), self.serialize_items())
+        } else {
+            self.serialize_full()
+        };
+        write_config_file(path, &content)
     }
     
     /// Preserve everything before the first [[items]] entry, regenerate items only.
     fn save_preserving_header(&self, original: &str) -> String {
-        if let Some(items_start) = original.find("[[items]]") {
-            let header = original[..items_start].trim_end();
-            format!("{}\n\n{}", header, self.serialize_items())
-        } else {
-            // No items section found — append items to existing content
-            let header = original.trim_end();
-            format!("{}\n\n{}", header, self.serialize_items())
+        format!("{}\n\n{}", config_header(original), self.serialize_items())
+    }
+    
+    /// Rewrite the [dock] table in `header` so it matches `self.dock`.
+    fn merge_dock_settings(&self, header: &str) -> Result<String> {
+        let mut doc: toml_edit::Document = header.parse()
+            .context("Failed to parse config file")?;
+        let on_disk = toml::from_str::<Config>(header)
+            .map(|c| c.dock)
+            .unwrap_or_default();
+        
+        let new_values = toml::Value::try_from(&self.dock)?;
+        let old_values = toml::Value::try_from(&on_disk)?;
+        let (Some(new_values), Some(old_values)) = (new_values.as_table(), old_values.as_table()) else {
+            return Ok(header.to_string());
+        };
+        
+        let Some(dock) = doc.entry("dock").or_insert(toml_edit::table()).as_table_mut() else {
+            anyhow::bail!("[dock] in config file is not a table");
+        };
+        
+        for (key, value) in new_values {
+            if SESSION_ONLY_KEYS.contains(&key.as_str()) || old_values.get(key) == Some(value) {
+                continue;
+            }
+            let literal = toml_value_literal(value)?;
+            
+            // Update the key under whichever spelling the file already uses
+            let existing = std::iter::once(key.as_str())
+                .chain(DOCK_KEY_ALIASES.iter().filter(|(k, _)| k == key).map(|(_, alias)| *alias))
+                .find(|k| dock.contains_key(k));
+            match existing.and_then(|k| dock.get_mut(k)).and_then(|item| item.as_value_mut()) {
+                Some(current) => {
+                    let decor = current.decor().clone();
+                    *current = literal;
+                    *current.decor_mut() = decor;
+                }
+                None => {
+                    dock.insert(key, toml_edit::value(literal));
+                }
+            }
         }
+        
+        Ok(doc.to_string())
     }
     
     /// Full serialization (no original file to preserve).
     fn serialize_full(&self) -> String {
-        toml::to_string_pretty(self).unwrap_or_default()
+        let mut own = self.clone();
+        own.items.retain(|item| item.included_from.is_none());
+        toml::to_string_pretty(&own).unwrap_or_default()
     }
     
     /// Serialize just the [[items]] array with clean formatting.
+    /// Items from included files stay in their own files.
     fn serialize_items(&self) -> String {
+        let items: Vec<&DockItem> = self.items.iter()
+            .filter(|item| item.included_from.is_none())
+            .collect();
         let mut s = String::new();
-        for (i, item) in self.items.iter().enumerate() {
+        for (i, item) in items.iter().enumerate() {
             s.push_str("[[items]]\n");
-            // Use repr() style quoting for name (double quotes)
-            s.push_str(&format!("name = {:?}\n", item.name));
-            if !item.path.as_os_str().is_empty() {
-                // Single quotes for paths to avoid backslash escaping on Windows
-                s.push_str(&format!("path = '{}'\n", item.path.display()));
+            serialize_item_fields(&mut s, item);
+            // Group members follow their parent as [[items.children]] tables
+            for child in &item.children {
+                s.push_str("\n[[items.children]]\n");
+                serialize_item_fields(&mut s, child);
             }
-            if let Some(icon) = &item.icon {
-                s.push_str(&format!("icon = '{}'\n", icon.display()));
-            }
-            if !item.args.is_empty() {
-                let args: Vec<String> = item.args.iter().map(|a| format!("{:?}", a)).collect();
-                s.push_str(&format!("args = [{}]\n", args.join(", ")));
-            }
-            if item.separator {
-                s.push_str("separator = true\n");
-            }
-            if let Some(special) = &item.special {
-                s.push_str(&format!("special = {:?}\n", special));
-            }
-            if i < self.items.len() - 1 {
+            if i < items.len() - 1 {
                 s.push('\n');
             }
         }
@@ -303,21 +1071,448 @@ impl Config {
 
 }
 
-/// Parse hex color string to ARGB u32
+/// Write one item's keys (everything but `children`) in the [[items]] style
+fn serialize_item_fields(s: &mut String, item: &DockItem) {
+    // Use repr() style quoting for name (double quotes)
+    s.push_str(&format!("name = {:?}\n", item.name));
+    if !item.path.as_os_str().is_empty() {
+        s.push_str(&format!("path = {}\n", toml_string(&item.path.to_string_lossy())));
+    }
+    if let Some(icon) = &item.icon {
+        s.push_str(&format!("icon = {}\n", toml_string(&icon.to_string_lossy())));
+    }
+    if !item.args.is_empty() {
+        let args: Vec<String> = item.args.iter().map(|a| format!("{:?}", a)).collect();
+        s.push_str(&format!("args = [{}]\n", args.join(", ")));
+    }
+    if item.separator {
+        s.push_str("separator = true\n");
+    }
+    if item.region {
+        s.push_str("region = true\n");
+    }
+    if let Some(width) = item.spacer {
+        s.push_str(&format!("spacer = {}\n", width));
+    }
+    if let Some(special) = &item.special {
+        s.push_str(&format!("special = {:?}\n", special));
+    }
+    if let Some(pattern) = &item.match_pattern {
+        s.push_str(&format!("match_pattern = {}\n", toml_string(pattern)));
+    }
+    if let Some(confirm) = &item.confirm {
+        s.push_str(&format!("confirm = {:?}\n", confirm));
+    }
+    if let Some(tooltip) = &item.tooltip {
+        s.push_str(&format!("tooltip = {:?}\n", tooltip));
+    }
+    // Only the config's own badge; ones from `--badge` never reach `items`
+    if let Some(badge) = &item.badge {
+        s.push_str(&format!("badge = {:?}\n", badge));
+    }
+    if let Some(command) = &item.command {
+        s.push_str(&format!("command = {}\n", toml_string(command)));
+    }
+    if item.hidden {
+        s.push_str("hidden = true\n");
+    }
+}
+
+/// `text` as a TOML string: single-quoted (no backslash escaping for
+/// Windows paths) unless it holds a quote or control characters
+fn toml_string(text: &str) -> String {
+    toml_edit::Value::from(text).to_string()
+}
+
+/// Replace the config file at `path` with `content` without ever leaving it
+/// half-written: the content goes to a temp file that is then renamed over
+/// the target. The previous contents are kept as `config.toml.bak`.
+pub fn write_config_file(path: &Path, content: &str) -> Result<()> {
+    let temp = path.with_extension("toml.tmp");
+    std::fs::write(&temp, content)
+        .with_context(|| format!("Failed to write config file: {}", temp.display()))?;
+    if path.exists() {
+        let backup = path.with_extension("toml.bak");
+        if let Err(e) = std::fs::copy(path, &backup) {
+            log::warn!("Failed to back up config to {}: {}", backup.display(), e);
+        }
+    }
+    std::fs::rename(&temp, path)
+        .with_context(|| format!("Failed to write config file: {}", path.display()))
+}
+
+/// [dock] keys that only change for the current session and are never saved.
+const SESSION_ONLY_KEYS: &[&str] = &["locked"];
+
+/// Alternate [dock] key spellings accepted on load: (canonical, alias)
+const DOCK_KEY_ALIASES: &[(&str, &str)] = &[
+    ("negative_vertical_offset", "vertical_offset"),
+    ("hide_windows_taskbar", "hide_taskbar"),
+];
+
+/// Everything before the first [[items]] entry, or the whole file if there are none.
+fn config_header(original: &str) -> &str {
+    match original.find("[[items]]") {
+        Some(items_start) => original[..items_start].trim_end(),
+        None => original.trim_end(),
+    }
+}
+
+/// Convert a serialized setting into an editable TOML value. Floats are rounded
+/// so `0.85` doesn't come back as `0.8500000238418579` after the f32 round trip.
+fn toml_value_literal(value: &toml::Value) -> Result<toml_edit::Value> {
+    let value = match value {
+        toml::Value::Float(f) => toml::Value::Float((f * 10000.0).round() / 10000.0),
+        other => other.clone(),
+    };
+    value.to_string().parse()
+        .map_err(|e| anyhow::anyhow!("Failed to format setting: {}", e))
+}
+
+/// Sort `items` with `compare` within each run between separators, so the
+/// separators stay put and every section keeps its own items.
+pub fn sort_sections(items: &mut [DockItem], mut compare: impl FnMut(&DockItem, &DockItem) -> std::cmp::Ordering) {
+    for section in items.split_mut(|item| item.is_separator()) {
+        section.sort_by(&mut compare);
+    }
+}
+
+/// Where `include` points when written in the config file at `from`:
+/// relative paths start from that file's directory
+pub fn resolve_include(from: &Path, include: &Path) -> PathBuf {
+    match from.parent() {
+        Some(dir) => dir.join(include),
+        None => include.to_path_buf(),
+    }
+}
+
+/// An included config file: only its [[items]] and `include` are read
+#[derive(Deserialize)]
+struct IncludedFile {
+    #[serde(default)]
+    dock: IncludeList,
+    #[serde(default)]
+    items: Vec<DockItem>,
+}
+
+#[derive(Default, Deserialize)]
+struct IncludeList {
+    #[serde(default)]
+    include: Vec<PathBuf>,
+}
+
+/// Append the items of the included file at `path` and then of the files it
+/// includes. `chain` holds the files being loaded, so a cycle is an error
+/// instead of endless recursion.
+fn load_included(path: &Path, chain: &mut Vec<PathBuf>, items: &mut Vec<DockItem>) -> Result<()> {
+    let canonical = path.canonicalize()
+        .with_context(|| format!("Failed to read included config file: {}", path.display()))?;
+    if chain.contains(&canonical) {
+        anyhow::bail!("Config file includes itself: {}", path.display());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read included config file: {}", path.display()))?;
+    let file: IncludedFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse included config file: {}", path.display()))?;
+    items.extend(file.items.into_iter().map(|mut item| {
+        item.included_from = Some(path.to_path_buf());
+        item
+    }));
+    chain.push(canonical);
+    for include in &file.dock.include {
+        load_included(&resolve_include(path, include), chain, items)?;
+    }
+    chain.pop();
+    Ok(())
+}
+
+/// Indices of the items in `items` that aren't hidden, in order
+pub fn shown_indices(items: &[DockItem]) -> Vec<usize> {
+    items.iter().enumerate()
+        .filter(|(_, item)| !item.hidden)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The indices in `shown` (into `items`) left once each run of adjacent
+/// separators is collapsed into one and the separators at either end are
+/// dropped, for `tidy_separators`. A run keeps its region separator if it has
+/// one, otherwise its first separator. Spacers are deliberate gaps and stay.
+pub fn tidy_separators(items: &[DockItem], shown: &[usize]) -> Vec<usize> {
+    let divides = |i: usize| items[i].is_separator() && items[i].spacer.is_none();
+    let mut tidied: Vec<usize> = Vec::with_capacity(shown.len());
+    for &i in shown {
+        if !divides(i) {
+            tidied.push(i);
+            continue;
+        }
+        match tidied.last_mut() {
+            // Leading separator
+            None => {}
+            Some(last) if divides(*last) && items[i].is_region() && !items[*last].is_region() => {
+                *last = i;
+            }
+            Some(last) if divides(*last) => {}
+            Some(_) => tidied.push(i),
+        }
+    }
+    if tidied.last().is_some_and(|&last| divides(last)) {
+        tidied.pop();
+    }
+    tidied
+}
+
+/// Parse a hex color as ARGB: `#rgb`, `#rrggbb` (both opaque), or
+/// `#aarrggbb`, with or without the `#`
+pub fn parse_color(text: &str) -> Result<u32> {
+    let hex = text.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("{:?} is not a hex color", text);
+    }
+    let value = u32::from_str_radix(hex, 16).unwrap_or(0);
+    match hex.len() {
+        3 => {
+            // Each digit doubles: #f80 is #ff8800
+            let expand = |shift: u32| ((value >> shift) & 0xF) * 0x11;
+            Ok(0xFF00_0000 | (expand(8) << 16) | (expand(4) << 8) | expand(0))
+        }
+        6 => Ok(0xFF00_0000 | value),
+        8 => Ok(value),
+        _ => anyhow::bail!("{:?} is not a color; use #rgb, #rrggbb, or #aarrggbb", text),
+    }
+}
+
+/// Parse hex color string to ARGB u32, scaling its alpha by `opacity`
 pub fn parse_hex_color(hex: &str, opacity: f32) -> u32 {
-    let hex = hex.trim_start_matches('#');
-    let rgb = u32::from_str_radix(hex, 16).unwrap_or(0x1e1e2e);
-    let alpha = (opacity * 255.0) as u32;
-    (alpha << 24) | rgb
+    let argb = parse_color(hex).unwrap_or(0xff1e1e2e);
+    let alpha = ((argb >> 24) as f32 * opacity) as u32;
+    (alpha << 24) | (argb & 0x00FF_FFFF)
 }
 
 /// Parse hex color to RGB tuple
 pub fn parse_hex_rgb(hex: &str) -> (u8, u8, u8) {
-    let hex = hex.trim_start_matches('#');
-    let val = u32::from_str_radix(hex, 16).unwrap_or(0xcba6f7);
+    let val = parse_color(hex).unwrap_or(0xffcba6f7);
     (
         ((val >> 16) & 0xFF) as u8,
         ((val >> 8) & 0xFF) as u8,
         (val & 0xFF) as u8,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Save `config` over a file that already has a [dock] table, the way
+    /// the dock saves, and load it back
+    fn round_trip(config: &Config, name: &str) -> Config {
+        let path = std::env::temp_dir().join(format!("rdock_test_{}_{}.toml", name, std::process::id()));
+        std::fs::write(&path, "[dock]\nicon_size = 48\n").unwrap();
+        config.save(&path).unwrap();
+        let loaded = Config::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("toml.bak"));
+        loaded
+    }
+
+    fn item(name: &str) -> DockItem {
+        DockItem::from_exe(PathBuf::from(format!(r"C:\Apps\{}.exe", name)))
+    }
+
+    #[test]
+    fn hidden_survives_save() {
+        let mut hidden = item("Hidden");
+        hidden.hidden = true;
+        let config = Config { dock: DockSettings::default(), items: vec![item("Shown"), hidden] };
+
+        let loaded = round_trip(&config, "hidden");
+        let flags: Vec<bool> = loaded.items.iter().map(|item| item.hidden).collect();
+        assert_eq!(flags, [false, true]);
+    }
+
+    #[test]
+    fn badge_survives_save() {
+        let mut badged = item("Mail");
+        badged.badge = Some("3".to_string());
+        let config = Config { dock: DockSettings::default(), items: vec![badged, item("Plain")] };
+
+        let loaded = round_trip(&config, "badge");
+        let badges: Vec<Option<&str>> = loaded.items.iter().map(|item| item.badge.as_deref()).collect();
+        assert_eq!(badges, [Some("3"), None]);
+    }
+
+    #[test]
+    fn write_config_file_keeps_a_backup() {
+        let path = std::env::temp_dir().join(format!("rdock_test_write_{}.toml", std::process::id()));
+        let backup = path.with_extension("toml.bak");
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&backup);
+
+        write_config_file(&path, "first").unwrap();
+        assert!(!backup.exists(), "nothing to back up yet");
+        write_config_file(&path, "second").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "first");
+        assert!(!path.with_extension("toml.tmp").exists());
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&backup);
+    }
+
+    #[test]
+    fn interrupted_write_leaves_the_config_intact() {
+        let path = std::env::temp_dir().join(format!("rdock_test_interrupted_{}.toml", std::process::id()));
+        let original = b"[dock]\nicon_size = 48\n";
+        std::fs::write(&path, original).unwrap();
+        // Something in the temp file's place makes the write fail part way
+        let temp = path.with_extension("toml.tmp");
+        std::fs::create_dir_all(&temp).unwrap();
+
+        assert!(write_config_file(&path, "[dock]\nicon_size = 64\n").is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), original);
+
+        let _ = std::fs::remove_dir(&temp);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("toml.bak"));
+    }
+
+    /// `tidy_separators` over every item in `items`
+    fn tidied(items: &[DockItem]) -> Vec<usize> {
+        tidy_separators(items, &(0..items.len()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn tidy_separators_collapses_runs_and_trims_ends() {
+        let sep = DockItem::new_separator;
+        let items = [sep(), item("a"), sep(), sep(), item("b"), sep()];
+        assert_eq!(tidied(&items), [1, 2, 4]);
+        assert!(tidied(&[sep(), sep()]).is_empty());
+    }
+
+    #[test]
+    fn tidy_separators_keeps_a_runs_region() {
+        let mut region = DockItem::new_separator();
+        region.region = true;
+        let items = [item("a"), DockItem::new_separator(), region, DockItem::new_separator(), item("b")];
+        assert_eq!(tidied(&items), [0, 2, 4]);
+    }
+
+    #[test]
+    fn tidy_separators_leaves_spacers_and_skips_hidden() {
+        let sep = DockItem::new_separator;
+        let items = [item("a"), DockItem::new_spacer(), DockItem::new_spacer(), sep(), item("b")];
+        assert_eq!(tidied(&items), [0, 1, 2, 3, 4]);
+        // A hidden item between two separators leaves them adjacent
+        let mut hidden = item("h");
+        hidden.hidden = true;
+        let items = [item("a"), sep(), hidden, sep(), item("b")];
+        assert_eq!(tidy_separators(&items, &shown_indices(&items)), [0, 1, 4]);
+    }
+
+    #[test]
+    fn tidy_separators_keeps_the_items_saved() {
+        let sep = DockItem::new_separator;
+        let mut config = Config { dock: DockSettings::default(), items: vec![sep(), item("a"), sep(), sep(), item("b")] };
+        config.dock.tidy_separators = true;
+        assert_eq!(round_trip(&config, "tidy").items.len(), 5);
+    }
+
+    /// Every accepted color form, and the malformed ones `problems` reports
+    #[test]
+    fn parse_color_forms() {
+        assert_eq!(parse_color("#f80").unwrap(), 0xFFFF_8800);
+        assert_eq!(parse_color("#1e1e2e").unwrap(), 0xFF1E_1E2E);
+        assert_eq!(parse_color("1E1E2E").unwrap(), 0xFF1E_1E2E);
+        assert_eq!(parse_color("#80ffffff").unwrap(), 0x80FF_FFFF);
+        assert_eq!(parse_color(" #FFF ").unwrap(), 0xFFFF_FFFF);
+        for bad in ["", "#", "#ff", "#fffff", "#12345g", "#+12345", "#1234567", "red"] {
+            assert!(parse_color(bad).is_err(), "{bad:?} should not parse");
+        }
+        // The color's own alpha combines with the opacity setting
+        assert_eq!(parse_hex_color("#80000000", 0.5) >> 24, 0x40);
+    }
+
+    /// The dock keeps its anchored edge as it changes width, and stays on screen
+    #[test]
+    fn dock_x_keeps_the_anchored_edge() {
+        let mut dock = DockSettings::default();
+        assert_eq!(dock.dock_x(1920, 600), 660);
+        dock.anchor_offset_px = -100;
+        assert_eq!(dock.dock_x(1920, 600), 560);
+        dock.anchor = Anchor::Left;
+        assert_eq!(dock.dock_x(1920, 600), 0, "negative offset pushed it off screen");
+        dock.anchor_offset_px = 40;
+        assert_eq!(dock.dock_x(1920, 600), 40);
+        assert_eq!(dock.dock_x(1920, 900), 40);
+        dock.anchor = Anchor::Right;
+        assert_eq!(dock.dock_x(1920, 600), 1280);
+        assert_eq!(dock.dock_x(1920, 900) + 900, 1880);
+        // Wider than the screen: pinned to the left edge
+        assert_eq!(dock.dock_x(800, 900), 0);
+    }
+
+    /// Startup, reloads and screen changes all place the dock with `layout`
+    #[test]
+    fn layout_places_the_dock() {
+        let mut dock = DockSettings::default();
+        let shown_buried = Layout { x: 660, y_visible: 1016, y_hidden: 1100 };
+        assert_eq!(dock.layout(1920, 1080, 600, 72), shown_buried);
+        dock.edge_margin = 10;
+        assert_eq!(dock.layout(1920, 1080, 600, 72).y_visible, 998);
+        dock.anchor = Anchor::Right;
+        assert_eq!(dock.layout(2560, 1440, 600, 72), Layout { x: 1960, y_visible: 1358, y_hidden: 1460 });
+    }
+
+    /// `include` paths resolve from the including file's directory, items merge
+    /// in order, cycles are refused, and saving leaves included items out
+    #[test]
+    fn includes_merge_in_order() {
+        let dir = std::env::temp_dir().join(format!("rdock_test_include_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).expect("create include fixture dir");
+        let item = |name: &str| format!("[[items]]\nname = \"{}\"\npath = 'C:\\{}.exe'\n", name, name);
+        let write = |path: &str, content: String| std::fs::write(dir.join(path), content).expect("write include fixture");
+        write("main.toml", format!("[dock]\ninclude = [\"sub/work.toml\", \"other.toml\"]\n\n{}", item("main")));
+        write("sub/work.toml", format!("[dock]\ninclude = [\"games.toml\"]\n\n{}", item("work")));
+        write("sub/games.toml", item("games"));
+        write("other.toml", item("other"));
+        write("loop.toml", format!("[dock]\ninclude = [\"sub/../loop.toml\"]\n\n{}", item("loop")));
+
+        let main = dir.join("main.toml");
+        let config = Config::load(&main).expect("load config with includes");
+        let names: Vec<&str> = config.items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["main", "work", "games", "other"]);
+        assert_eq!(config.items[0].included_from, None);
+        assert_eq!(config.items[2].included_from.as_deref(), Some(dir.join("sub").join("games.toml").as_path()));
+
+        assert!(Config::load(&dir.join("loop.toml")).is_err(), "include cycle");
+
+        config.save(&main).expect("save config with includes");
+        let saved = std::fs::read_to_string(&main).expect("read saved config");
+        assert!(saved.contains("name = \"main\"") && !saved.contains("name = \"work\""));
+        assert_eq!(Config::load(&main).expect("reload saved config").items.len(), 4);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn overflow_accepts_wrap() {
+        let dock: DockSettings = toml::from_str("overflow = \"wrap\"").unwrap();
+        assert_eq!(dock.overflow, Overflow::Visible);
+        let dock: DockSettings = toml::from_str("overflow = \"scroll\"").unwrap();
+        assert_eq!(dock.overflow, Overflow::Scroll);
+    }
+
+    #[test]
+    fn quotes_survive_save() {
+        let mut quoted = item("Quoted");
+        quoted.path = PathBuf::from(r"C:\Apps\Bob's Tools\tool.exe");
+        quoted.command = Some(r#"echo 'hi' "there" \ \t"#.to_string());
+        quoted.match_pattern = Some(r"c:\apps\bob's*\tool.exe".to_string());
+        let config = Config { dock: DockSettings::default(), items: vec![quoted.clone()] };
+
+        let loaded = round_trip(&config, "quotes");
+        assert_eq!(loaded.items[0].path, quoted.path);
+        assert_eq!(loaded.items[0].command, quoted.command);
+        assert_eq!(loaded.items[0].match_pattern, quoted.match_pattern);
+    }
+}
@@ -1,6 +1,6 @@
 //! Context menu and file dialog handling for dock item management
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::*;
@@ -18,19 +18,34 @@ pub enum ContextMenuAction {
     None,
     // Item-specific actions
     EditItem(usize),
+    DuplicateItem(usize),
+    HideItem(usize),
     RemoveItem(usize),
     QuitApp(usize),
+    PinItem(usize),
+    OpenLocation(usize),
+    ItemProperties(usize),
     EmptyRecycleBin,
-    // General actions
-    AddItem,
-    AddSeparator,
-    AddSpecial(String),  // special item type
+    // General actions; the index is where the new item goes
+    AddItem(usize),
+    AddSeparator(usize),
+    AddSpacer(usize),
+    AddFromClipboard(PathBuf, usize),
+    AddSpecial(String, usize),  // special item type
+    ShowHiddenItems,
+    SortByName,
+    SortByLaunches,
     ToggleLock,
     OpenConfig,
+    ReloadConfig,
+    PreviewConfig,
+    OpenSettings,
     SaveConfigAs,
     LoadConfig,
     ResetSettings,
     ResetAll,
+    Undo,
+    Restart,
     Quit,
 }
 
@@ -47,6 +62,21 @@ const ID_LOAD_CONFIG: u32 = 1011;
 const ID_RESET_SETTINGS: u32 = 1012;
 const ID_RESET_ALL: u32 = 1013;
 const ID_QUIT_APP: u32 = 1014;
+const ID_OPEN_SETTINGS: u32 = 1015;
+const ID_PIN_ITEM: u32 = 1016;
+const ID_UNDO: u32 = 1017;
+const ID_OPEN_LOCATION: u32 = 1018;
+const ID_SORT_BY_NAME: u32 = 1019;
+const ID_SORT_BY_LAUNCHES: u32 = 1020;
+const ID_RELOAD_CONFIG: u32 = 1021;
+const ID_DUPLICATE_ITEM: u32 = 1022;
+const ID_ADD_FROM_CLIPBOARD: u32 = 1023;
+const ID_HIDE_ITEM: u32 = 1024;
+const ID_SHOW_HIDDEN_ITEMS: u32 = 1025;
+const ID_PREVIEW_CONFIG: u32 = 1026;
+const ID_RESTART: u32 = 1027;
+const ID_ITEM_PROPERTIES: u32 = 1028;
+const ID_ADD_SPACER: u32 = 1029;
 
 // Special item IDs start at 2000
 const ID_SPECIAL_BASE: u32 = 2000;
@@ -57,6 +87,7 @@ pub const SPECIAL_ITEMS: &[(&str, &str)] = &[
     ("settings", "Settings"),
     ("recycle_bin", "Recycle Bin"),
     ("show_desktop", "Show Desktop"),
+    ("toggle_desktop", "Minimize / Restore All"),
     ("system_tray", "System Tray (Hidden Icons)"),
     ("quick_settings", "Quick Settings"),
     ("file_explorer", "File Explorer"),
@@ -69,10 +100,13 @@ pub const SPECIAL_ITEMS: &[(&str, &str)] = &[
     ("task_view", "Task View"),
     ("action_center", "Action Center"),
     ("run_dialog", "Run Dialog"),
+    ("recent_apps", "Recent Apps"),
+    ("app_launcher", "App Launcher"),
 ];
 
 /// Show unified context menu
-pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>, is_locked: bool, is_separator: bool, is_recycle_bin: bool, app_name: Option<&str>) -> ContextMenuAction {
+#[allow(clippy::too_many_arguments)]
+pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>, insert_index: usize, is_locked: bool, is_separator: bool, is_recycle_bin: bool, is_pinned: bool, included_from: Option<&Path>, has_location: bool, can_undo: bool, has_hidden: bool, clipboard_path: Option<&Path>, app_name: Option<&str>) -> ContextMenuAction {
     unsafe {
         let hmenu = CreatePopupMenu().unwrap_or_default();
         if hmenu.is_invalid() {
@@ -95,10 +129,39 @@ pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>,
                 let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
             }
             
-            if !is_locked {
+            // Apps launched from a file can reveal it in Explorer (even when locked)
+            if has_location {
+                let location_text: Vec<u16> = "Open File Location\0".encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_STRING, ID_OPEN_LOCATION as usize, PCWSTR(location_text.as_ptr()));
+                let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
+            }
+            
+            // Pinned items can be inspected without editing (even when locked)
+            if is_pinned && !is_separator {
+                let properties_text: Vec<u16> = "Properties...\0".encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_STRING, ID_ITEM_PROPERTIES as usize, PCWSTR(properties_text.as_ptr()));
+                let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
+            }
+            
+            // Running-only items can be pinned but not edited until they are
+            if !is_pinned {
+                let pin_text: Vec<u16> = "Pin to Dock\0".encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_STRING, ID_PIN_ITEM as usize, PCWSTR(pin_text.as_ptr()));
+                let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
+            } else if let Some(file) = included_from {
+                // Items from an `include`d file are edited in that file
+                let name = file.file_name().unwrap_or(file.as_os_str()).to_string_lossy();
+                let included_text: Vec<u16> = format!("From {}\0", name).encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_STRING | MF_GRAYED, 0, PCWSTR(included_text.as_ptr()));
+                let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
+            } else if !is_locked {
                 if !is_separator {
                     let edit_text: Vec<u16> = "Edit Item...\0".encode_utf16().collect();
                     let _ = AppendMenuW(hmenu, MF_STRING, ID_EDIT_ITEM as usize, PCWSTR(edit_text.as_ptr()));
+                    let duplicate_text: Vec<u16> = "Duplicate\0".encode_utf16().collect();
+                    let _ = AppendMenuW(hmenu, MF_STRING, ID_DUPLICATE_ITEM as usize, PCWSTR(duplicate_text.as_ptr()));
+                    let hide_text: Vec<u16> = "Hide\0".encode_utf16().collect();
+                    let _ = AppendMenuW(hmenu, MF_STRING, ID_HIDE_ITEM as usize, PCWSTR(hide_text.as_ptr()));
                 }
                 let remove_text: Vec<u16> = "Remove\0".encode_utf16().collect();
                 let _ = AppendMenuW(hmenu, MF_STRING, ID_REMOVE_ITEM as usize, PCWSTR(remove_text.as_ptr()));
@@ -106,12 +169,30 @@ pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>,
             }
         }
 
+        if can_undo {
+            let undo_text: Vec<u16> = "Undo\0".encode_utf16().collect();
+            let _ = AppendMenuW(hmenu, MF_STRING, ID_UNDO as usize, PCWSTR(undo_text.as_ptr()));
+            let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
+        }
+
         // General options (always shown)
         if !is_locked {
             let add_text: Vec<u16> = "Add Item...\0".encode_utf16().collect();
             let sep_text: Vec<u16> = "Add Separator\0".encode_utf16().collect();
             let _ = AppendMenuW(hmenu, MF_STRING, ID_ADD_ITEM as usize, PCWSTR(add_text.as_ptr()));
+            // Only offered while the clipboard holds a path that exists
+            if let Some(path) = clipboard_path {
+                let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy();
+                let clipboard_text: Vec<u16> = format!("Add \"{}\" from Clipboard\0", name).encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_STRING, ID_ADD_FROM_CLIPBOARD as usize, PCWSTR(clipboard_text.as_ptr()));
+            }
             let _ = AppendMenuW(hmenu, MF_STRING, ID_ADD_SEPARATOR as usize, PCWSTR(sep_text.as_ptr()));
+            let spacer_text: Vec<u16> = "Add Spacer\0".encode_utf16().collect();
+            let _ = AppendMenuW(hmenu, MF_STRING, ID_ADD_SPACER as usize, PCWSTR(spacer_text.as_ptr()));
+            if has_hidden {
+                let show_hidden_text: Vec<u16> = "Show Hidden Items\0".encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_STRING, ID_SHOW_HIDDEN_ITEMS as usize, PCWSTR(show_hidden_text.as_ptr()));
+            }
             
             // Create submenu for special items
             let hsubmenu = CreatePopupMenu().unwrap_or_default();
@@ -124,6 +205,17 @@ pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>,
                 let _ = AppendMenuW(hmenu, MF_POPUP, hsubmenu.0 as usize, PCWSTR(special_text.as_ptr()));
             }
             
+            // Sorting keeps separators in place and sorts each section
+            let hsortmenu = CreatePopupMenu().unwrap_or_default();
+            if !hsortmenu.is_invalid() {
+                let name_text: Vec<u16> = "By Name\0".encode_utf16().collect();
+                let launches_text: Vec<u16> = "By Launch Count\0".encode_utf16().collect();
+                let _ = AppendMenuW(hsortmenu, MF_STRING, ID_SORT_BY_NAME as usize, PCWSTR(name_text.as_ptr()));
+                let _ = AppendMenuW(hsortmenu, MF_STRING, ID_SORT_BY_LAUNCHES as usize, PCWSTR(launches_text.as_ptr()));
+                let sort_text: Vec<u16> = "Sort Items\0".encode_utf16().collect();
+                let _ = AppendMenuW(hmenu, MF_POPUP, hsortmenu.0 as usize, PCWSTR(sort_text.as_ptr()));
+            }
+            
             let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
         }
         
@@ -137,18 +229,26 @@ pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>,
         
         let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
         
+        let settings_text: Vec<u16> = "Dock Settings...\0".encode_utf16().collect();
         let config_text: Vec<u16> = "Edit Config...\0".encode_utf16().collect();
+        let reload_text: Vec<u16> = "Reload Config\0".encode_utf16().collect();
+        let preview_text: Vec<u16> = "Preview Config Changes\0".encode_utf16().collect();
         let save_text: Vec<u16> = "Save Config As...\0".encode_utf16().collect();
         let load_text: Vec<u16> = "Load Config...\0".encode_utf16().collect();
         let reset_settings_text: Vec<u16> = "Reset Settings\0".encode_utf16().collect();
         let reset_all_text: Vec<u16> = "Reset All\0".encode_utf16().collect();
+        let restart_text: Vec<u16> = "Restart rDock\0".encode_utf16().collect();
         let quit_text: Vec<u16> = "Quit\0".encode_utf16().collect();
+        let _ = AppendMenuW(hmenu, MF_STRING, ID_OPEN_SETTINGS as usize, PCWSTR(settings_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_STRING, ID_OPEN_CONFIG as usize, PCWSTR(config_text.as_ptr()));
+        let _ = AppendMenuW(hmenu, MF_STRING, ID_RELOAD_CONFIG as usize, PCWSTR(reload_text.as_ptr()));
+        let _ = AppendMenuW(hmenu, MF_STRING, ID_PREVIEW_CONFIG as usize, PCWSTR(preview_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_STRING, ID_SAVE_CONFIG_AS as usize, PCWSTR(save_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_STRING, ID_LOAD_CONFIG as usize, PCWSTR(load_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_STRING, ID_RESET_SETTINGS as usize, PCWSTR(reset_settings_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_STRING, ID_RESET_ALL as usize, PCWSTR(reset_all_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
+        let _ = AppendMenuW(hmenu, MF_STRING, ID_RESTART as usize, PCWSTR(restart_text.as_ptr()));
         let _ = AppendMenuW(hmenu, MF_STRING, ID_QUIT as usize, PCWSTR(quit_text.as_ptr()));
 
         // Required: set foreground window so menu dismisses properly on click outside
@@ -176,22 +276,40 @@ pub fn show_context_menu(hwnd: isize, x: i32, y: i32, item_index: Option<usize>,
         // Check if it's a special item
         if cmd_id >= ID_SPECIAL_BASE && cmd_id < ID_SPECIAL_BASE + SPECIAL_ITEMS.len() as u32 {
             let idx = (cmd_id - ID_SPECIAL_BASE) as usize;
-            return ContextMenuAction::AddSpecial(SPECIAL_ITEMS[idx].0.to_string());
+            return ContextMenuAction::AddSpecial(SPECIAL_ITEMS[idx].0.to_string(), insert_index);
         }
         
         match cmd_id {
             ID_EDIT_ITEM => ContextMenuAction::EditItem(item_index.unwrap_or(0)),
+            ID_DUPLICATE_ITEM => ContextMenuAction::DuplicateItem(item_index.unwrap_or(0)),
+            ID_HIDE_ITEM => ContextMenuAction::HideItem(item_index.unwrap_or(0)),
             ID_REMOVE_ITEM => ContextMenuAction::RemoveItem(item_index.unwrap_or(0)),
             ID_QUIT_APP => ContextMenuAction::QuitApp(item_index.unwrap_or(0)),
+            ID_PIN_ITEM => ContextMenuAction::PinItem(item_index.unwrap_or(0)),
+            ID_OPEN_LOCATION => ContextMenuAction::OpenLocation(item_index.unwrap_or(0)),
+            ID_ITEM_PROPERTIES => ContextMenuAction::ItemProperties(item_index.unwrap_or(0)),
             ID_EMPTY_RECYCLE_BIN => ContextMenuAction::EmptyRecycleBin,
-            ID_ADD_ITEM => ContextMenuAction::AddItem,
-            ID_ADD_SEPARATOR => ContextMenuAction::AddSeparator,
+            ID_ADD_ITEM => ContextMenuAction::AddItem(insert_index),
+            ID_ADD_SEPARATOR => ContextMenuAction::AddSeparator(insert_index),
+            ID_ADD_SPACER => ContextMenuAction::AddSpacer(insert_index),
+            ID_ADD_FROM_CLIPBOARD => match clipboard_path {
+                Some(path) => ContextMenuAction::AddFromClipboard(path.to_path_buf(), insert_index),
+                None => ContextMenuAction::None,
+            },
+            ID_SHOW_HIDDEN_ITEMS => ContextMenuAction::ShowHiddenItems,
+            ID_SORT_BY_NAME => ContextMenuAction::SortByName,
+            ID_SORT_BY_LAUNCHES => ContextMenuAction::SortByLaunches,
             ID_TOGGLE_LOCK => ContextMenuAction::ToggleLock,
             ID_OPEN_CONFIG => ContextMenuAction::OpenConfig,
+            ID_RELOAD_CONFIG => ContextMenuAction::ReloadConfig,
+            ID_PREVIEW_CONFIG => ContextMenuAction::PreviewConfig,
+            ID_OPEN_SETTINGS => ContextMenuAction::OpenSettings,
             ID_SAVE_CONFIG_AS => ContextMenuAction::SaveConfigAs,
             ID_LOAD_CONFIG => ContextMenuAction::LoadConfig,
             ID_RESET_SETTINGS => ContextMenuAction::ResetSettings,
             ID_RESET_ALL => ContextMenuAction::ResetAll,
+            ID_UNDO => ContextMenuAction::Undo,
+            ID_RESTART => ContextMenuAction::Restart,
             ID_QUIT => ContextMenuAction::Quit,
             _ => ContextMenuAction::None,
         }
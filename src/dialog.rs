@@ -0,0 +1,89 @@
+//! DPI scaling and fonts shared by rDock's Win32 dialogs
+//!
+//! winit makes the process per-monitor DPI aware, so dialogs are laid out in
+//! physical pixels. Layouts are written for 96 DPI and multiplied by
+//! `dpi_scale`. `DialogFont` gives the controls the system message font at
+//! the window's DPI instead of the bitmap font child windows start with.
+
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{CreateFontIndirectW, DeleteObject, HFONT};
+use windows::Win32::UI::HiDpi::{AdjustWindowRectExForDpi, GetDpiForWindow, SystemParametersInfoForDpi};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumChildWindows, GetWindowLongW, SendMessageW, GWL_EXSTYLE, GWL_STYLE, NONCLIENTMETRICSW,
+    SPI_GETNONCLIENTMETRICS, WINDOW_EX_STYLE, WINDOW_STYLE, WM_SETFONT,
+};
+
+const BASE_DPI: u32 = 96;
+
+fn window_dpi(hwnd: HWND) -> u32 {
+    match unsafe { GetDpiForWindow(hwnd) } {
+        0 => BASE_DPI,
+        dpi => dpi,
+    }
+}
+
+/// How much larger than at 96 DPI the window's monitor draws (1.5 at 150%)
+pub fn dpi_scale(hwnd: HWND) -> f32 {
+    window_dpi(hwnd) as f32 / BASE_DPI as f32
+}
+
+/// Outer size of `hwnd` with a client area of `width` x `height` at 96 DPI,
+/// scaled to the window's DPI
+pub fn outer_size(hwnd: HWND, width: i32, height: i32) -> (i32, i32) {
+    let dpi = window_dpi(hwnd);
+    let scale = dpi as f32 / BASE_DPI as f32;
+    let mut rect = RECT {
+        left: 0,
+        top: 0,
+        right: (width as f32 * scale).round() as i32,
+        bottom: (height as f32 * scale).round() as i32,
+    };
+    unsafe {
+        let style = WINDOW_STYLE(GetWindowLongW(hwnd, GWL_STYLE) as u32);
+        let ex_style = WINDOW_EX_STYLE(GetWindowLongW(hwnd, GWL_EXSTYLE) as u32);
+        let _ = AdjustWindowRectExForDpi(&mut rect, style, false, ex_style, dpi);
+    }
+    (rect.right - rect.left, rect.bottom - rect.top)
+}
+
+/// The system message font at a window's DPI, set on all of its controls.
+/// Deleted when dropped, so keep it until the dialog is gone.
+pub struct DialogFont(HFONT);
+
+impl DialogFont {
+    /// Create the font for `hwnd`'s current DPI and apply it to every child
+    pub fn apply(hwnd: HWND) -> Option<Self> {
+        unsafe {
+            let mut metrics = NONCLIENTMETRICSW {
+                cbSize: std::mem::size_of::<NONCLIENTMETRICSW>() as u32,
+                ..Default::default()
+            };
+            SystemParametersInfoForDpi(
+                SPI_GETNONCLIENTMETRICS.0,
+                metrics.cbSize,
+                Some(&mut metrics as *mut _ as *mut _),
+                0,
+                window_dpi(hwnd),
+            ).ok()?;
+            let font = CreateFontIndirectW(&metrics.lfMessageFont);
+            if font.is_invalid() {
+                return None;
+            }
+            let _ = EnumChildWindows(hwnd, Some(set_font), LPARAM(font.0 as isize));
+            Some(Self(font))
+        }
+    }
+}
+
+impl Drop for DialogFont {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = DeleteObject(self.0);
+        }
+    }
+}
+
+unsafe extern "system" fn set_font(child: HWND, font: LPARAM) -> BOOL {
+    SendMessageW(child, WM_SETFONT, WPARAM(font.0 as usize), LPARAM(1));
+    true.into()
+}
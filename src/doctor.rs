@@ -0,0 +1,181 @@
+//! `rdock --doctor`: print what the dock would see at startup, then exit
+//!
+//! Runs the same config loading, icon extraction, and monitor/taskbar/fullscreen
+//! checks as the dock itself, without opening any windows, so a broken setup
+//! can be diagnosed from a console.
+
+use crate::config::{Config, DockItem};
+use crate::renderer::Renderer;
+use crate::special::special_action;
+use crate::window_focus::apps_folder_id;
+use std::path::Path;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+};
+use windows::Win32::System::Console::{AttachConsole, GetStdHandle, ATTACH_PARENT_PROCESS, STD_OUTPUT_HANDLE};
+use windows::Win32::UI::Shell::{SHAppBarMessage, ABM_GETSTATE, ABS_AUTOHIDE, APPBARDATA};
+use windows::Win32::UI::WindowsAndMessaging::{FindWindowW, IsWindowVisible, MONITORINFOF_PRIMARY};
+
+struct MonitorReport {
+    name: String,
+    rect: RECT,
+    primary: bool,
+}
+
+/// Print the report for the config at `config_path` to stdout.
+pub fn run(config_path: &Path) {
+    attach_console();
+
+    println!("rDock {} doctor", env!("CARGO_PKG_VERSION"));
+    println!();
+
+    println!("Config: {}", config_path.display());
+    let config = if !config_path.exists() {
+        println!("  not found (a default config is written on first launch)");
+        None
+    } else {
+        match Config::load(config_path) {
+            Ok(config) => {
+                println!("  parses: ok ({} items)", config.items.len());
+                for problem in config.problems() {
+                    println!("  problem: {}", problem);
+                }
+                Some(config)
+            }
+            Err(e) => {
+                println!("  parses: FAILED: {:#}", e);
+                None
+            }
+        }
+    };
+
+    if let Some(config) = &config {
+        println!();
+        println!("Items:");
+        match Renderer::new(config, &config.items) {
+            Ok(renderer) => {
+                for (i, item) in config.items.iter().enumerate() {
+                    println!("  [{}] {}", i, describe_item(item, &renderer));
+                }
+            }
+            Err(e) => println!("  icons: FAILED to create renderer: {:#}", e),
+        }
+    }
+
+    println!();
+    println!("Monitors:");
+    let monitors = enumerate_monitors();
+    if monitors.is_empty() {
+        println!("  none found");
+    }
+    // Mirrors the dock: the primary monitor, else the first one
+    let selected = monitors.iter().position(|m| m.primary).unwrap_or(0);
+    for (i, monitor) in monitors.iter().enumerate() {
+        let rect = &monitor.rect;
+        println!(
+            "  {} {}x{} at ({}, {}){}{}",
+            monitor.name,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            rect.left,
+            rect.top,
+            if monitor.primary { " primary" } else { "" },
+            if i == selected { " <- dock" } else { "" },
+        );
+    }
+
+    println!();
+    println!("Taskbar: {}", taskbar_status());
+    println!("Fullscreen app active: {}", if crate::is_fullscreen_app_active() { "yes" } else { "no" });
+}
+
+/// Point stdout at the console we were started from. rDock is a GUI-subsystem
+/// app, so it has no console of its own; redirected output already works.
+pub fn attach_console() {
+    unsafe {
+        let redirected = GetStdHandle(STD_OUTPUT_HANDLE).is_ok_and(|h| !h.is_invalid() && !h.0.is_null());
+        if !redirected {
+            let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+        }
+    }
+}
+
+fn describe_item(item: &DockItem, renderer: &Renderer) -> String {
+    if item.is_separator() {
+        return "separator".to_string();
+    }
+    let target = if let Some(special) = &item.special {
+        match special_action(special) {
+            Ok(_) => format!("special \"{}\"", special),
+            Err(_) => format!("special \"{}\" (UNKNOWN)", special),
+        }
+    } else if let Some(command) = &item.command {
+        format!("command \"{}\"", command.lines().next().unwrap_or_default())
+    } else if item.path.as_os_str().is_empty() {
+        "no path".to_string()
+    } else if let Some(app_id) = apps_folder_id(&item.path) {
+        format!("packaged app \"{}\"", app_id)
+    } else if item.path.exists() {
+        format!("{} (exists)", item.path.display())
+    } else if item.path.is_absolute() {
+        format!("{} (MISSING)", item.path.display())
+    } else {
+        format!("{} (not checked, resolved via PATH)", item.path.display())
+    };
+    let icon = match renderer.icon_error(item) {
+        Some(error) => format!("FAILED ({})", error),
+        None if renderer.has_icon(item) => "loaded".to_string(),
+        None => "none".to_string(),
+    };
+    let hidden = if item.hidden { " (hidden)" } else { "" };
+    let included = match &item.included_from {
+        Some(file) => format!(" (from {})", file.display()),
+        None => String::new(),
+    };
+    format!("{}{}{}: {}, icon {}", item.name, hidden, included, target, icon)
+}
+
+fn enumerate_monitors() -> Vec<MonitorReport> {
+    unsafe extern "system" fn callback(monitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, data: LPARAM) -> BOOL {
+        let monitors = &mut *(data.0 as *mut Vec<MonitorReport>);
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+            let len = info.szDevice.iter().position(|&c| c == 0).unwrap_or(info.szDevice.len());
+            monitors.push(MonitorReport {
+                name: String::from_utf16_lossy(&info.szDevice[..len]),
+                rect: info.monitorInfo.rcMonitor,
+                primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            });
+        }
+        true.into()
+    }
+
+    let mut monitors: Vec<MonitorReport> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(None, None, Some(callback), LPARAM(&mut monitors as *mut _ as isize));
+    }
+    monitors
+}
+
+fn taskbar_status() -> String {
+    unsafe {
+        let class_name: Vec<u16> = "Shell_TrayWnd".encode_utf16().chain(std::iter::once(0)).collect();
+        let Ok(taskbar) = FindWindowW(PCWSTR(class_name.as_ptr()), PCWSTR::null()) else {
+            return "NOT FOUND (hide_windows_taskbar and taskbar_mode will have no effect)".to_string();
+        };
+        let mut data = APPBARDATA {
+            cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+            hWnd: taskbar,
+            ..Default::default()
+        };
+        let state = SHAppBarMessage(ABM_GETSTATE, &mut data) as u32;
+        format!(
+            "found, {}, auto-hide {}",
+            if IsWindowVisible(taskbar).as_bool() { "visible" } else { "hidden" },
+            if state & ABS_AUTOHIDE != 0 { "on" } else { "off" },
+        )
+    }
+}
@@ -0,0 +1,308 @@
+//! Built-in icons for special items, drawn instead of loaded
+//!
+//! Each special item type has a white symbol on a colored rounded tile, made
+//! of a few shapes laid out on a unit square and rasterized at whatever size
+//! the renderer loads icons at. `Renderer` falls back to these when a special
+//! item has no `icon` and Windows doesn't provide one, so specials never show
+//! the placeholder. Shapes are anti-aliased by their distance to the pixel's
+//! center, the same one-pixel ramp the dock background uses.
+
+use crate::renderer::alpha_blend;
+
+const SYMBOL_COLOR: u32 = 0xFFFF_FFFF;
+/// Tile corner radius, as a fraction of the icon size
+const TILE_RADIUS: f32 = 0.22;
+
+/// One part of a symbol, in unit coordinates (0..1, y down)
+#[derive(Debug, Clone, Copy)]
+enum Shape {
+    /// Filled box with rounded corners
+    Rect { x0: f32, y0: f32, x1: f32, y1: f32, radius: f32 },
+    Circle { cx: f32, cy: f32, r: f32 },
+    /// Circle outline `width` thick
+    Ring { cx: f32, cy: f32, r: f32, width: f32 },
+    /// Segment with round ends
+    Line { x0: f32, y0: f32, x1: f32, y1: f32, width: f32 },
+}
+
+/// A shape and whether it's added to the symbol or cut out of what's drawn so far
+type Stroke = (bool, Shape);
+
+const fn add(shape: Shape) -> Stroke {
+    (true, shape)
+}
+
+const fn cut(shape: Shape) -> Stroke {
+    (false, shape)
+}
+
+const fn rect(x0: f32, y0: f32, x1: f32, y1: f32, radius: f32) -> Shape {
+    Shape::Rect { x0, y0, x1, y1, radius }
+}
+
+const fn line(x0: f32, y0: f32, x1: f32, y1: f32, width: f32) -> Shape {
+    Shape::Line { x0, y0, x1, y1, width }
+}
+
+const fn circle(cx: f32, cy: f32, r: f32) -> Shape {
+    Shape::Circle { cx, cy, r }
+}
+
+const fn ring(cx: f32, cy: f32, r: f32, width: f32) -> Shape {
+    Shape::Ring { cx, cy, r, width }
+}
+
+const START_MENU: &[Stroke] = &[
+    add(rect(0.25, 0.25, 0.48, 0.48, 0.01)),
+    add(rect(0.52, 0.25, 0.75, 0.48, 0.01)),
+    add(rect(0.25, 0.52, 0.48, 0.75, 0.01)),
+    add(rect(0.52, 0.52, 0.75, 0.75, 0.01)),
+];
+
+/// A cog: four bars through the middle make eight teeth around a ring
+const SETTINGS: &[Stroke] = &[
+    add(line(0.5, 0.22, 0.5, 0.78, 0.12)),
+    add(line(0.22, 0.5, 0.78, 0.5, 0.12)),
+    add(line(0.3, 0.3, 0.7, 0.7, 0.12)),
+    add(line(0.7, 0.3, 0.3, 0.7, 0.12)),
+    add(circle(0.5, 0.5, 0.2)),
+    cut(circle(0.5, 0.5, 0.08)),
+];
+
+const RECYCLE_BIN: &[Stroke] = &[
+    add(line(0.26, 0.3, 0.74, 0.3, 0.06)),
+    add(line(0.42, 0.23, 0.58, 0.23, 0.06)),
+    add(rect(0.31, 0.36, 0.69, 0.78, 0.05)),
+    cut(line(0.42, 0.44, 0.42, 0.7, 0.045)),
+    cut(line(0.58, 0.44, 0.58, 0.7, 0.045)),
+];
+
+/// Screen with a stand
+const DESKTOP: &[Stroke] = &[
+    add(rect(0.2, 0.24, 0.8, 0.64, 0.05)),
+    cut(rect(0.26, 0.3, 0.74, 0.58, 0.01)),
+    add(line(0.5, 0.64, 0.5, 0.74, 0.06)),
+    add(line(0.36, 0.76, 0.64, 0.76, 0.06)),
+];
+
+/// Two overlapping windows
+const TASK_VIEW: &[Stroke] = &[
+    add(rect(0.2, 0.2, 0.62, 0.56, 0.04)),
+    cut(rect(0.25, 0.25, 0.57, 0.51, 0.01)),
+    cut(rect(0.34, 0.4, 0.84, 0.84, 0.04)),
+    add(rect(0.38, 0.44, 0.8, 0.8, 0.04)),
+    cut(rect(0.43, 0.49, 0.75, 0.75, 0.01)),
+];
+
+/// A speech bubble with lines of text
+const NOTIFICATIONS: &[Stroke] = &[
+    add(rect(0.2, 0.24, 0.8, 0.66, 0.08)),
+    add(line(0.32, 0.62, 0.28, 0.78, 0.08)),
+    cut(line(0.32, 0.38, 0.68, 0.38, 0.05)),
+    cut(line(0.32, 0.52, 0.56, 0.52, 0.05)),
+];
+
+/// Two switches, one on and one off
+const QUICK_SETTINGS: &[Stroke] = &[
+    add(line(0.3, 0.36, 0.7, 0.36, 0.2)),
+    cut(circle(0.7, 0.36, 0.07)),
+    add(line(0.3, 0.64, 0.7, 0.64, 0.2)),
+    cut(line(0.3, 0.64, 0.7, 0.64, 0.12)),
+    add(circle(0.3, 0.64, 0.07)),
+];
+
+const FOLDER: &[Stroke] = &[
+    add(rect(0.2, 0.26, 0.46, 0.4, 0.04)),
+    add(rect(0.2, 0.32, 0.8, 0.74, 0.05)),
+    cut(line(0.24, 0.4, 0.76, 0.4, 0.03)),
+];
+
+/// Three sliders
+const CONTROL_PANEL: &[Stroke] = &[
+    add(line(0.3, 0.24, 0.3, 0.76, 0.05)),
+    add(line(0.5, 0.24, 0.5, 0.76, 0.05)),
+    add(line(0.7, 0.24, 0.7, 0.76, 0.05)),
+    add(circle(0.3, 0.6, 0.08)),
+    add(circle(0.5, 0.36, 0.08)),
+    add(circle(0.7, 0.54, 0.08)),
+];
+
+/// A command prompt
+const RUN_DIALOG: &[Stroke] = &[
+    add(line(0.26, 0.3, 0.46, 0.5, 0.08)),
+    add(line(0.46, 0.5, 0.26, 0.7, 0.08)),
+    add(line(0.52, 0.7, 0.74, 0.7, 0.08)),
+];
+
+/// A document with a folded corner and lines of text
+const DOCUMENTS: &[Stroke] = &[
+    add(rect(0.28, 0.2, 0.72, 0.8, 0.04)),
+    cut(rect(0.58, 0.14, 0.78, 0.34, 0.0)),
+    add(line(0.58, 0.21, 0.58, 0.34, 0.04)),
+    add(line(0.58, 0.34, 0.71, 0.34, 0.04)),
+    cut(line(0.36, 0.46, 0.64, 0.46, 0.04)),
+    cut(line(0.36, 0.57, 0.64, 0.57, 0.04)),
+    cut(line(0.36, 0.68, 0.54, 0.68, 0.04)),
+];
+
+const DOWNLOADS: &[Stroke] = &[
+    add(line(0.5, 0.2, 0.5, 0.62, 0.08)),
+    add(line(0.32, 0.46, 0.5, 0.64, 0.08)),
+    add(line(0.68, 0.46, 0.5, 0.64, 0.08)),
+    add(line(0.26, 0.78, 0.74, 0.78, 0.08)),
+];
+
+/// Three linked nodes
+const NETWORK: &[Stroke] = &[
+    add(line(0.5, 0.32, 0.3, 0.7, 0.05)),
+    add(line(0.5, 0.32, 0.7, 0.7, 0.05)),
+    add(line(0.3, 0.7, 0.7, 0.7, 0.05)),
+    add(circle(0.5, 0.3, 0.1)),
+    add(circle(0.3, 0.7, 0.1)),
+    add(circle(0.7, 0.7, 0.1)),
+];
+
+/// Head and shoulders
+const USER_FOLDER: &[Stroke] = &[
+    add(circle(0.5, 0.36, 0.13)),
+    add(circle(0.5, 0.86, 0.28)),
+    cut(rect(0.0, 0.8, 1.0, 1.0, 0.0)),
+];
+
+/// A chevron pointing up, as on the taskbar's overflow button
+const SYSTEM_TRAY: &[Stroke] = &[
+    add(line(0.28, 0.6, 0.5, 0.38, 0.09)),
+    add(line(0.5, 0.38, 0.72, 0.6, 0.09)),
+];
+
+const RECENT_APPS: &[Stroke] = &[
+    add(ring(0.5, 0.5, 0.26, 0.07)),
+    add(line(0.5, 0.5, 0.5, 0.34, 0.06)),
+    add(line(0.5, 0.5, 0.62, 0.56, 0.06)),
+];
+
+/// A magnifying glass
+const APP_LAUNCHER: &[Stroke] = &[
+    add(ring(0.45, 0.45, 0.18, 0.07)),
+    add(line(0.58, 0.58, 0.74, 0.74, 0.09)),
+];
+
+/// Tile color and symbol for each special item type, matching the names
+/// `special` accepts
+const GLYPHS: &[(&[&str], u32, &[Stroke])] = &[
+    (&["start_menu"], 0xFF0078D4, START_MENU),
+    (&["settings"], 0xFF5C6370, SETTINGS),
+    (&["recycle_bin"], 0xFF6B7A8F, RECYCLE_BIN),
+    (&["show_desktop", "toggle_desktop"], 0xFF2E7D9A, DESKTOP),
+    (&["task_view"], 0xFF3F51B5, TASK_VIEW),
+    (&["action_center", "notification_center"], 0xFF7E57C2, NOTIFICATIONS),
+    (&["quick_settings"], 0xFF00897B, QUICK_SETTINGS),
+    (&["file_explorer"], 0xFFE0A526, FOLDER),
+    (&["control_panel"], 0xFF43A047, CONTROL_PANEL),
+    (&["run_dialog"], 0xFF37474F, RUN_DIALOG),
+    (&["this_pc", "my_computer"], 0xFF1E88E5, DESKTOP),
+    (&["documents"], 0xFF1565C0, DOCUMENTS),
+    (&["downloads"], 0xFF00A86B, DOWNLOADS),
+    (&["network"], 0xFF0097A7, NETWORK),
+    (&["user_folder", "home"], 0xFFEF6C00, USER_FOLDER),
+    (&["system_tray"], 0xFF546E7A, SYSTEM_TRAY),
+    (&["recent_apps"], 0xFFD81B60, RECENT_APPS),
+    (&["app_launcher"], 0xFF8E24AA, APP_LAUNCHER),
+];
+
+/// The built-in icon for special item `special` as a `size` x `size` ARGB
+/// buffer, or None for a name without one
+pub fn special_glyph(special: &str, size: u32) -> Option<Vec<u32>> {
+    let &(_, tile, strokes) = GLYPHS.iter().find(|(names, _, _)| names.contains(&special))?;
+    if size == 0 {
+        return Some(Vec::new());
+    }
+
+    let s = size as f32;
+    let tile_shape = rect(0.0, 0.0, 1.0, 1.0, TILE_RADIUS);
+    let mut pixels = vec![0u32; (size * size) as usize];
+    for y in 0..size {
+        for x in 0..size {
+            // Pixel center, in icon pixels
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let tile_coverage = coverage(&tile_shape, px, py, s);
+            if tile_coverage <= 0.0 {
+                continue;
+            }
+            let symbol = strokes.iter().fold(0.0f32, |covered, (adds, shape)| {
+                let c = coverage(shape, px, py, s);
+                if *adds { covered.max(c) } else { covered * (1.0 - c) }
+            });
+            let color = alpha_blend(tile, with_alpha(SYMBOL_COLOR, symbol));
+            pixels[(y * size + x) as usize] = with_alpha(color, tile_coverage);
+        }
+    }
+    Some(pixels)
+}
+
+/// `color` with its alpha scaled by `coverage`
+fn with_alpha(color: u32, coverage: f32) -> u32 {
+    let alpha = ((color >> 24) as f32 * coverage.clamp(0.0, 1.0)).round() as u32;
+    (alpha << 24) | (color & 0x00FF_FFFF)
+}
+
+/// How much of the pixel centered on (`px`, `py`) `shape` covers, for a
+/// shape scaled to `size` pixels
+fn coverage(shape: &Shape, px: f32, py: f32, size: f32) -> f32 {
+    (0.5 - distance(shape, px, py, size)).clamp(0.0, 1.0)
+}
+
+/// Signed distance in pixels from (`px`, `py`) to the edge of `shape`,
+/// negative inside
+fn distance(shape: &Shape, px: f32, py: f32, size: f32) -> f32 {
+    match *shape {
+        Shape::Rect { x0, y0, x1, y1, radius } => {
+            let r = radius * size;
+            let (cx, cy) = ((x0 + x1) * 0.5 * size, (y0 + y1) * 0.5 * size);
+            let (hw, hh) = ((x1 - x0) * 0.5 * size, (y1 - y0) * 0.5 * size);
+            let qx = (px - cx).abs() - hw + r;
+            let qy = (py - cy).abs() - hh + r;
+            let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+            outside + qx.max(qy).min(0.0) - r
+        }
+        Shape::Circle { cx, cy, r } => {
+            (px - cx * size).hypot(py - cy * size) - r * size
+        }
+        Shape::Ring { cx, cy, r, width } => {
+            ((px - cx * size).hypot(py - cy * size) - r * size).abs() - width * 0.5 * size
+        }
+        Shape::Line { x0, y0, x1, y1, width } => {
+            let (ax, ay, bx, by) = (x0 * size, y0 * size, x1 * size, y1 * size);
+            let (dx, dy) = (bx - ax, by - ay);
+            let len_sq = dx * dx + dy * dy;
+            let t = if len_sq > 0.0 {
+                (((px - ax) * dx + (py - ay) * dy) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (px - (ax + dx * t)).hypot(py - (ay + dy * t)) - width * 0.5 * size
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Built-in special icons are opaque tiles with rounded corners and a white
+    /// symbol; aliases share one and unknown names have none
+    #[test]
+    fn special_glyph_tiles() {
+        const SIZE: u32 = 48;
+        let glyph = special_glyph("start_menu", SIZE).expect("start_menu has a glyph");
+        assert_eq!(glyph.len(), (SIZE * SIZE) as usize);
+        assert_eq!(glyph[0] >> 24, 0, "corner outside the rounded tile");
+        assert!(glyph.contains(&0xFFFF_FFFF), "no symbol pixels");
+        assert!(glyph.contains(&0xFF00_78D4), "no tile pixels");
+        // Between the logo's four panes is bare tile
+        assert_eq!(glyph[(SIZE / 2 * SIZE + SIZE / 2) as usize], 0xFF00_78D4);
+
+        assert_eq!(special_glyph("home", SIZE), special_glyph("user_folder", SIZE));
+        assert!(special_glyph("no_such_special", SIZE).is_none());
+    }
+}
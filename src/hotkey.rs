@@ -0,0 +1,104 @@
+//! Global hotkeys registered on the dock window
+//!
+//! Hotkeys are written like `"Ctrl+Alt+P"`: any of Ctrl, Alt, Shift, and Win
+//! followed by a letter, digit, or F1-F24. Windows posts WM_HOTKEY to the
+//! registering window, which winit doesn't surface, so a window subclass
+//! records presses for the event loop to collect with `take_pressed`.
+
+use std::cell::RefCell;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT,
+    MOD_SHIFT, MOD_WIN,
+};
+use windows::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+use windows::Win32::UI::WindowsAndMessaging::WM_HOTKEY;
+
+const SUBCLASS_ID: usize = 0x484B; // "HK"
+
+thread_local! {
+    static PRESSED: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    modifiers: HOT_KEY_MODIFIERS,
+    vk: u32,
+}
+
+/// Parse a hotkey like "Ctrl+Alt+P" (case-insensitive). At least one
+/// modifier is required so a bare key never gets swallowed system-wide.
+pub fn parse(spec: &str) -> Option<Hotkey> {
+    let mut modifiers = HOT_KEY_MODIFIERS(0);
+    let mut vk = None;
+    for part in spec.split('+').map(str::trim) {
+        let part = part.to_ascii_lowercase();
+        match part.as_str() {
+            "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "shift" => modifiers |= MOD_SHIFT,
+            "win" | "windows" | "super" => modifiers |= MOD_WIN,
+            key if vk.is_none() => vk = Some(key_code(key)?),
+            _ => return None,
+        }
+    }
+    if modifiers.0 == 0 {
+        return None;
+    }
+    Some(Hotkey { modifiers, vk: vk? })
+}
+
+/// Virtual-key code for a letter, digit, or function key name
+fn key_code(key: &str) -> Option<u32> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Some(c.to_ascii_uppercase() as u32);
+        }
+    }
+    let n: u32 = key.strip_prefix('f')?.parse().ok()?;
+    (1..=24).contains(&n).then(|| 0x70 + n - 1) // VK_F1..VK_F24
+}
+
+/// Register `spec` as hotkey `id` on `hwnd`. Returns false (and logs why) if
+/// it doesn't parse or another program already owns it.
+pub fn register(hwnd: HWND, id: i32, spec: &str) -> bool {
+    let Some(hotkey) = parse(spec) else {
+        log::warn!("Ignoring invalid hotkey {:?}", spec);
+        return false;
+    };
+    unsafe {
+        let _ = SetWindowSubclass(hwnd, Some(hotkey_subclass_proc), SUBCLASS_ID, 0);
+        if let Err(e) = RegisterHotKey(hwnd, id, hotkey.modifiers | MOD_NOREPEAT, hotkey.vk) {
+            log::warn!("Couldn't register hotkey {:?}: {}", spec, e);
+            return false;
+        }
+    }
+    true
+}
+
+pub fn unregister(hwnd: HWND, id: i32) {
+    unsafe {
+        let _ = UnregisterHotKey(hwnd, id);
+    }
+}
+
+/// Ids of the hotkeys pressed since the last call
+pub fn take_pressed() -> Vec<i32> {
+    PRESSED.with(|p| std::mem::take(&mut *p.borrow_mut()))
+}
+
+unsafe extern "system" fn hotkey_subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    _data: usize,
+) -> LRESULT {
+    if msg == WM_HOTKEY {
+        PRESSED.with(|p| p.borrow_mut().push(wparam.0 as i32));
+        return LRESULT(0);
+    }
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}
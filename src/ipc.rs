@@ -0,0 +1,155 @@
+//! Talking to the running dock over a named pipe
+//!
+//! The dock listens on `\\.\pipe\rdock` for one-line commands and answers each
+//! with a single reply before closing the connection. `rdock --status` is the
+//! client side: it sends `status` and prints the JSON the dock sends back.
+//! `rdock --badge <item> [text]` sends `badge` with shell-quoted arguments.
+//! Requests are answered on the dock's own thread, the next time its event
+//! loop wakes (within a mouse poll interval).
+
+use crate::config::DockItem;
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::fs::File;
+use std::os::windows::io::{AsRawHandle, FromRawHandle};
+use std::sync::mpsc;
+use std::time::Duration;
+use windows::core::PCWSTR;
+use windows::Win32::Storage::FileSystem::{FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX};
+use windows::Win32::System::Pipes::{ConnectNamedPipe, CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_TYPE_BYTE, PIPE_WAIT};
+
+const PIPE_NAME: &str = r"\\.\pipe\rdock";
+
+/// How long a client waits for the dock to answer
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A command received from a client, answered by sending on `reply`
+pub struct Request {
+    pub command: String,
+    pub reply: mpsc::Sender<String>,
+}
+
+/// The dock's end of the pipe
+pub struct IpcServer {
+    requests: mpsc::Receiver<Request>,
+}
+
+impl IpcServer {
+    /// Start listening on a background thread. Fails if another instance
+    /// already owns the pipe.
+    pub fn start() -> Option<Self> {
+        let first = create_pipe(true)?;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut pipe = Some(first);
+            loop {
+                let Some(file) = pipe.take().or_else(|| create_pipe(false)) else { return };
+                // Waits for a client; one that connected first is reported as an error
+                unsafe {
+                    let _ = ConnectNamedPipe(windows::Win32::Foundation::HANDLE(file.as_raw_handle()), None);
+                }
+                match serve(file, &tx) {
+                    Ok(true) => {}
+                    // The dock has exited
+                    Ok(false) => return,
+                    Err(e) => log::debug!("IPC client dropped: {}", e),
+                }
+            }
+        });
+        Some(Self { requests: rx })
+    }
+
+    /// Requests received since the last call
+    pub fn take_requests(&self) -> Vec<Request> {
+        self.requests.try_iter().collect()
+    }
+}
+
+/// A new instance of the pipe, as a File that closes it when dropped
+fn create_pipe(first: bool) -> Option<File> {
+    let name: Vec<u16> = PIPE_NAME.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut mode = PIPE_ACCESS_DUPLEX;
+    if first {
+        mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+    }
+    let handle = unsafe {
+        CreateNamedPipeW(PCWSTR(name.as_ptr()), mode, PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT, 1, 4096, 4096, 0, None)
+    };
+    if handle.is_invalid() {
+        log::warn!("Could not create {}: {}", PIPE_NAME, std::io::Error::last_os_error());
+        return None;
+    }
+    // SAFETY: the handle is a freshly created pipe that nothing else owns
+    Some(unsafe { File::from_raw_handle(handle.0) })
+}
+
+/// Read one command from a connected client and write back the dock's reply.
+/// Returns false once the dock has stopped taking requests.
+fn serve(file: File, requests: &mpsc::Sender<Request>) -> std::io::Result<bool> {
+    let mut reader = BufReader::new(file);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let (reply_tx, reply_rx) = mpsc::channel();
+    let request = Request { command: line.trim().to_string(), reply: reply_tx };
+    if requests.send(request).is_err() {
+        return Ok(false);
+    }
+    let reply = reply_rx.recv_timeout(REPLY_TIMEOUT)
+        .unwrap_or_else(|_| "error: the dock did not answer".to_string());
+
+    let mut file = reader.into_inner();
+    file.write_all(reply.as_bytes())?;
+    // Make sure the client has read it all before the pipe closes
+    file.sync_all()?;
+    Ok(true)
+}
+
+/// Send `command` to the running dock and return its reply
+pub fn send(command: &str) -> Result<String> {
+    let mut pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(PIPE_NAME)
+        .context("rDock isn't running")?;
+    pipe.write_all(format!("{}\n", command).as_bytes())?;
+    let mut reply = String::new();
+    pipe.read_to_string(&mut reply).context("Failed to read rDock's reply")?;
+    Ok(reply)
+}
+
+/// JSON array of each item's name and whether it's running, for `status`.
+/// `running` is indexed like `items`; separators are left out.
+pub fn status_json(items: &[DockItem], running: &[bool]) -> String {
+    let entries: Vec<String> = items.iter().enumerate()
+        .filter(|(_, item)| !item.is_separator())
+        .map(|(i, item)| format!(
+            "  {{\"name\": {}, \"running\": {}}}",
+            json_string(&item.name),
+            running.get(i).copied().unwrap_or(false)
+        ))
+        .collect();
+    if entries.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", entries.join(",\n"))
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -3,13 +3,15 @@
 use std::path::PathBuf;
 use std::cell::RefCell;
 use windows::core::PCWSTR;
-use windows::Win32::Foundation::{HWND, WPARAM, LPARAM, LRESULT};
+use windows::Win32::Foundation::{HWND, WPARAM, LPARAM, LRESULT, RECT};
 use windows::Win32::UI::WindowsAndMessaging::*;
 use windows::Win32::Graphics::Gdi::HBRUSH;
+use windows::Win32::UI::Controls::{CheckDlgButton, IsDlgButtonChecked, BST_CHECKED};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 
 use crate::config::DockItem;
 use crate::context_menu::{pick_executable_with_path, pick_icon_with_path, SPECIAL_ITEMS};
+use crate::dialog::{self, DialogFont};
 
 // Control IDs
 const ID_NAME_EDIT: i32 = 101;
@@ -22,12 +24,25 @@ const ID_SPECIAL_COMBO: i32 = 107;
 const ID_OK: i32 = 1;
 const ID_CANCEL: i32 = 2;
 const ID_REMOVE: i32 = 108;
+const ID_CONFIRM_EDIT: i32 = 109;
+const ID_COMMAND_EDIT: i32 = 110;
+const ID_TOOLTIP_EDIT: i32 = 111;
+const ID_HIDDEN_CHECK: i32 = 112;
+/// Labels take consecutive IDs from here, one per entry of `ROWS`
+const ID_LABEL_BASE: i32 = 120;
+
+/// Client area at 96 DPI; also the smallest size the dialog can be dragged to
+const CLIENT_WIDTH: i32 = 564;
+const CLIENT_HEIGHT: i32 = 468;
 
 // Style constants
 const SS_RIGHT: u32 = 0x0002;
+const ES_MULTILINE: u32 = 0x0004;
+const ES_AUTOVSCROLL: u32 = 0x0040;
 const ES_AUTOHSCROLL: u32 = 0x0080;
 const CBS_DROPDOWNLIST: u32 = 0x0003;
 const CBS_HASSTRINGS: u32 = 0x0200;
+const BS_AUTOCHECKBOX: u32 = 0x0003;
 const COLOR_BTNFACE: u32 = 15;
 
 // Dialog result stored in thread-local for the dialog proc
@@ -35,11 +50,13 @@ thread_local! {
     static DIALOG_RESULT: RefCell<Option<DialogResult>> = const { RefCell::new(None) };
     static DIALOG_ITEM: RefCell<Option<DockItem>> = const { RefCell::new(None) };
     static DIALOG_IS_NEW: RefCell<bool> = const { RefCell::new(true) };
+    static DIALOG_READ_ONLY: RefCell<bool> = const { RefCell::new(false) };
+    static DIALOG_FONT: RefCell<Option<DialogFont>> = const { RefCell::new(None) };
 }
 
 #[derive(Debug, Clone)]
 pub enum DialogResult {
-    Ok(DockItem),
+    Ok(Box<DockItem>),
     Remove,
     Cancel,
 }
@@ -47,6 +64,16 @@ pub enum DialogResult {
 /// Show the item editor dialog
 /// Returns DialogResult with the edited item, remove request, or cancel
 pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
+    show_dialog(item, is_new, false)
+}
+
+/// Show an item's settings in the editor with every field disabled and
+/// only a Close button, so nothing can be changed
+pub fn show_item_properties(item: &DockItem) {
+    show_dialog(Some(item), false, true);
+}
+
+fn show_dialog(item: Option<&DockItem>, is_new: bool, read_only: bool) -> DialogResult {
     // Initialize dialog item
     let initial_item = item.cloned().unwrap_or_else(|| DockItem {
         name: String::new(),
@@ -54,7 +81,17 @@ pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
         icon: None,
         args: Vec::new(),
         separator: false,
+        region: false,
+        spacer: None,
         special: None,
+        match_pattern: None,
+        confirm: None,
+        command: None,
+        tooltip: None,
+        badge: None,
+        hidden: false,
+        children: Vec::new(),
+        included_from: None,
     });
     
     DIALOG_ITEM.with(|cell| {
@@ -66,6 +103,9 @@ pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
     DIALOG_IS_NEW.with(|cell| {
         *cell.borrow_mut() = is_new;
     });
+    DIALOG_READ_ONLY.with(|cell| {
+        *cell.borrow_mut() = read_only;
+    });
     
     unsafe {
         let hinstance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default().0 as *mut _;
@@ -90,15 +130,9 @@ pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
         
         RegisterClassExW(&wc);
         
-        // Calculate window size and position
-        let width = 580;
-        let height = if is_new { 330 } else { 380 };
-        let screen_w = GetSystemMetrics(SM_CXSCREEN);
-        let screen_h = GetSystemMetrics(SM_CYSCREEN);
-        let x = (screen_w - width) / 2;
-        let y = (screen_h - height) / 2;
-        
-        let title: Vec<u16> = if is_new {
+        let title: Vec<u16> = if read_only {
+            "Item Properties\0".encode_utf16().collect()
+        } else if is_new {
             "Add Item\0".encode_utf16().collect()
         } else {
             "Edit Item\0".encode_utf16().collect()
@@ -108,8 +142,8 @@ pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
             WS_EX_DLGMODALFRAME | WS_EX_TOPMOST,
             PCWSTR(class_name.as_ptr()),
             PCWSTR(title.as_ptr()),
-            WS_POPUP | WS_CAPTION | WS_SYSMENU,
-            x, y, width, height,
+            WS_POPUP | WS_CAPTION | WS_SYSMENU | WS_THICKFRAME,
+            0, 0, 0, 0,
             HWND::default(),
             HMENU::default(),
             hinstance,
@@ -120,6 +154,12 @@ pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
             return DialogResult::Cancel;
         }
         
+        // Size for the DPI of the monitor it opened on, centered
+        let (width, height) = dialog::outer_size(hwnd, CLIENT_WIDTH, CLIENT_HEIGHT);
+        let screen_w = GetSystemMetrics(SM_CXSCREEN);
+        let screen_h = GetSystemMetrics(SM_CYSCREEN);
+        let _ = SetWindowPos(hwnd, None, (screen_w - width) / 2, (screen_h - height) / 2, width, height, SWP_NOZORDER);
+        
         let _ = ShowWindow(hwnd, SW_SHOW);
         let _ = windows::Win32::Graphics::Gdi::UpdateWindow(hwnd);
         
@@ -137,6 +177,8 @@ pub fn show_item_editor(item: Option<&DockItem>, is_new: bool) -> DialogResult {
             }
         }
         
+        DIALOG_FONT.with(|cell| cell.borrow_mut().take());
+        
         // Get result
         DIALOG_RESULT.with(|cell| {
             cell.borrow_mut().take().unwrap_or(DialogResult::Cancel)
@@ -148,8 +190,33 @@ unsafe extern "system" fn dialog_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
     match msg {
         WM_CREATE => {
             let is_new = DIALOG_IS_NEW.with(|cell| *cell.borrow());
-            create_controls(hwnd, is_new);
+            let read_only = DIALOG_READ_ONLY.with(|cell| *cell.borrow());
+            create_controls(hwnd, is_new, read_only);
             populate_controls(hwnd);
+            DIALOG_FONT.with(|cell| *cell.borrow_mut() = DialogFont::apply(hwnd));
+            LRESULT(0)
+        }
+        WM_SIZE => {
+            layout_controls(hwnd);
+            LRESULT(0)
+        }
+        WM_GETMINMAXINFO => {
+            let (width, height) = dialog::outer_size(hwnd, CLIENT_WIDTH, CLIENT_HEIGHT);
+            let info = &mut *(lparam.0 as *mut MINMAXINFO);
+            info.ptMinTrackSize.x = width;
+            info.ptMinTrackSize.y = height;
+            LRESULT(0)
+        }
+        WM_DPICHANGED => {
+            // Moved to a monitor with another scale: new font, then the
+            // suggested size, whose WM_SIZE lays the controls out again
+            DIALOG_FONT.with(|cell| *cell.borrow_mut() = DialogFont::apply(hwnd));
+            let suggested = &*(lparam.0 as *const RECT);
+            let _ = SetWindowPos(
+                hwnd, None, suggested.left, suggested.top,
+                suggested.right - suggested.left, suggested.bottom - suggested.top,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            );
             LRESULT(0)
         }
         WM_COMMAND => {
@@ -172,140 +239,124 @@ unsafe extern "system" fn dialog_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lpar
     }
 }
 
-unsafe fn create_controls(hwnd: HWND, is_new: bool) {
+/// Each labeled row: label, field, its Browse button, field height at 96 DPI
+const ROWS: &[(&str, i32, Option<i32>, i32)] = &[
+    ("Name:", ID_NAME_EDIT, None, 24),
+    // Hover text (empty = show the name)
+    ("Tooltip:", ID_TOOLTIP_EDIT, None, 24),
+    ("Path:", ID_PATH_EDIT, Some(ID_PATH_BROWSE), 24),
+    ("Icon:", ID_ICON_EDIT, Some(ID_ICON_BROWSE), 24),
+    ("Arguments:", ID_ARGS_EDIT, None, 24),
+    // Command line (replaces Path/Arguments when set)
+    ("Command:", ID_COMMAND_EDIT, None, 48),
+    // Confirmation prompt (empty = launch without asking)
+    ("Confirm:", ID_CONFIRM_EDIT, None, 24),
+    ("Special:", ID_SPECIAL_COMBO, None, 24),
+    ("", ID_HIDDEN_CHECK, None, 24),
+];
+
+unsafe fn create_controls(hwnd: HWND, is_new: bool, read_only: bool) {
     let hinstance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default().0 as *mut _;
     let hinstance = windows::Win32::Foundation::HINSTANCE(hinstance);
     
-    let mut y = 20;
-    let label_w = 90;
-    let edit_x = 110;
-    let edit_w = 340;
-    let btn_w = 90;
-    let btn_x = 460;
-    let row_h = 35;
-    
-    let static_class: Vec<u16> = "STATIC\0".encode_utf16().collect();
-    let edit_class: Vec<u16> = "EDIT\0".encode_utf16().collect();
-    let button_class: Vec<u16> = "BUTTON\0".encode_utf16().collect();
-    let combo_class: Vec<u16> = "COMBOBOX\0".encode_utf16().collect();
-    
-    // Name
-    let name_label: Vec<u16> = "Name:\0".encode_utf16().collect();
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(name_label.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
-        10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WS_EX_CLIENTEDGE, PCWSTR(edit_class.as_ptr()), PCWSTR::null(),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL),
-        edit_x, y, edit_w + btn_w + 10, 24, hwnd, HMENU(ID_NAME_EDIT as *mut _), hinstance, None
-    );
-    y += row_h + 5;
+    // Controls start empty-sized; layout_controls places them on WM_SIZE
+    let create = |class: &str, text: &str, ex_style: WINDOW_EX_STYLE, style: WINDOW_STYLE, id: i32| {
+        let class: Vec<u16> = class.encode_utf16().chain(std::iter::once(0)).collect();
+        let text: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        CreateWindowExW(
+            ex_style, PCWSTR(class.as_ptr()), PCWSTR(text.as_ptr()),
+            WS_CHILD | WS_VISIBLE | style,
+            0, 0, 0, 0, hwnd, HMENU(id as *mut _), hinstance, None
+        ).unwrap_or_default()
+    };
     
-    // Path
-    let path_label: Vec<u16> = "Path:\0".encode_utf16().collect();
-    let browse_text: Vec<u16> = "Browse...\0".encode_utf16().collect();
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(path_label.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
-        10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WS_EX_CLIENTEDGE, PCWSTR(edit_class.as_ptr()), PCWSTR::null(),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL),
-        edit_x, y, edit_w, 24, hwnd, HMENU(ID_PATH_EDIT as *mut _), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(browse_text.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP,
-        btn_x, y, btn_w, 24, hwnd, HMENU(ID_PATH_BROWSE as *mut _), hinstance, None
-    );
-    y += row_h + 5;
+    // Read-only fields and their Browse buttons are created disabled
+    let disabled = if read_only { WS_DISABLED } else { WINDOW_STYLE(0) };
     
-    // Icon
-    let icon_label: Vec<u16> = "Icon:\0".encode_utf16().collect();
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(icon_label.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
-        10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WS_EX_CLIENTEDGE, PCWSTR(edit_class.as_ptr()), PCWSTR::null(),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL),
-        edit_x, y, edit_w, 24, hwnd, HMENU(ID_ICON_EDIT as *mut _), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(browse_text.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP,
-        btn_x, y, btn_w, 24, hwnd, HMENU(ID_ICON_BROWSE as *mut _), hinstance, None
-    );
-    y += row_h + 5;
-    
-    // Args
-    let args_label: Vec<u16> = "Arguments:\0".encode_utf16().collect();
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(args_label.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
-        10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WS_EX_CLIENTEDGE, PCWSTR(edit_class.as_ptr()), PCWSTR::null(),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL),
-        edit_x, y, edit_w + btn_w + 10, 24, hwnd, HMENU(ID_ARGS_EDIT as *mut _), hinstance, None
-    );
-    y += row_h + 5;
-    
-    // Special type dropdown
-    let special_label: Vec<u16> = "Special:\0".encode_utf16().collect();
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(special_label.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
-        10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
-    );
-    let combo = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(combo_class.as_ptr()), PCWSTR::null(),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WS_VSCROLL | WINDOW_STYLE(CBS_DROPDOWNLIST | CBS_HASSTRINGS),
-        edit_x, y, edit_w + btn_w + 10, 200, hwnd, HMENU(ID_SPECIAL_COMBO as *mut _), hinstance, None
-    ).unwrap_or_default();
-    
-    // Populate combo box
-    let none_text: Vec<u16> = "(None - Regular Item)\0".encode_utf16().collect();
-    SendMessageW(combo, CB_ADDSTRING, WPARAM(0), LPARAM(none_text.as_ptr() as isize));
-    
-    for (_, display_name) in SPECIAL_ITEMS {
-        let text: Vec<u16> = format!("{}\0", display_name).encode_utf16().collect();
-        SendMessageW(combo, CB_ADDSTRING, WPARAM(0), LPARAM(text.as_ptr() as isize));
+    for (i, &(label, field, browse, _)) in ROWS.iter().enumerate() {
+        create("STATIC", label, WINDOW_EX_STYLE(0), WINDOW_STYLE(SS_RIGHT), ID_LABEL_BASE + i as i32);
+        match field {
+            ID_COMMAND_EDIT => {
+                create("EDIT", "", WS_EX_CLIENTEDGE, disabled | WS_TABSTOP | WS_VSCROLL | WINDOW_STYLE(ES_MULTILINE | ES_AUTOVSCROLL), field);
+            }
+            ID_SPECIAL_COMBO => {
+                let combo = create("COMBOBOX", "", WINDOW_EX_STYLE(0), disabled | WS_TABSTOP | WS_VSCROLL | WINDOW_STYLE(CBS_DROPDOWNLIST | CBS_HASSTRINGS), field);
+                
+                // Populate combo box
+                let none_text: Vec<u16> = "(None - Regular Item)\0".encode_utf16().collect();
+                SendMessageW(combo, CB_ADDSTRING, WPARAM(0), LPARAM(none_text.as_ptr() as isize));
+                
+                for (_, display_name) in SPECIAL_ITEMS {
+                    let text: Vec<u16> = format!("{}\0", display_name).encode_utf16().collect();
+                    SendMessageW(combo, CB_ADDSTRING, WPARAM(0), LPARAM(text.as_ptr() as isize));
+                }
+                
+                SendMessageW(combo, CB_SETCURSEL, WPARAM(0), LPARAM(0));
+            }
+            ID_HIDDEN_CHECK => {
+                create("BUTTON", "Hidden (kept in the config, not shown in the dock)", WINDOW_EX_STYLE(0), disabled | WS_TABSTOP | WINDOW_STYLE(BS_AUTOCHECKBOX), field);
+            }
+            _ => {
+                create("EDIT", "", WS_EX_CLIENTEDGE, disabled | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL), field);
+            }
+        }
+        if let Some(id) = browse {
+            create("BUTTON", "Browse...", WINDOW_EX_STYLE(0), disabled | WS_TABSTOP, id);
+        }
     }
     
-    SendMessageW(combo, CB_SETCURSEL, WPARAM(0), LPARAM(0));
-    y += row_h + 15;
-    
     // Buttons
-    let ok_text: Vec<u16> = "OK\0".encode_utf16().collect();
-    let cancel_text: Vec<u16> = "Cancel\0".encode_utf16().collect();
-    let remove_text: Vec<u16> = "Remove\0".encode_utf16().collect();
+    if read_only {
+        // Takes the place of Cancel, which layout_controls puts on the right
+        create("BUTTON", "Close", WINDOW_EX_STYLE(0), WS_TABSTOP | WINDOW_STYLE(0x0001), ID_CANCEL); // BS_DEFPUSHBUTTON
+        return;
+    }
+    if !is_new {
+        create("BUTTON", "Remove", WINDOW_EX_STYLE(0), WS_TABSTOP, ID_REMOVE);
+    }
+    create("BUTTON", "OK", WINDOW_EX_STYLE(0), WS_TABSTOP | WINDOW_STYLE(0x0001), ID_OK); // BS_DEFPUSHBUTTON
+    create("BUTTON", "Cancel", WINDOW_EX_STYLE(0), WS_TABSTOP, ID_CANCEL);
+}
+
+/// Position every control for the current client size and DPI. Fields
+/// stretch with the width and the Command box takes any extra height.
+unsafe fn layout_controls(hwnd: HWND) {
+    let scale = dialog::dpi_scale(hwnd);
+    let px = |v: i32| (v as f32 * scale).round() as i32;
+    let mut client = RECT::default();
+    let _ = GetClientRect(hwnd, &mut client);
     
-    let btn_y = y + 10;
+    let place = |id: i32, x: i32, y: i32, w: i32, h: i32| {
+        if let Ok(ctrl) = GetDlgItem(hwnd, id) {
+            let _ = MoveWindow(ctrl, x, y, w.max(0), h.max(0), true);
+        }
+    };
+    let right = client.right - px(15);
+    let extra = (client.bottom - px(CLIENT_HEIGHT)).max(0);
+    let edit_x = px(110);
+    let btn_w = px(90);
     
-    if !is_new {
-        let _ = CreateWindowExW(
-            WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(remove_text.as_ptr()),
-            WS_CHILD | WS_VISIBLE | WS_TABSTOP,
-            15, btn_y, 80, 28, hwnd, HMENU(ID_REMOVE as *mut _), hinstance, None
-        );
+    let mut y = px(20);
+    for (i, &(_, field, browse, height)) in ROWS.iter().enumerate() {
+        let height = if field == ID_COMMAND_EDIT { px(height) + extra } else { px(height) };
+        place(ID_LABEL_BASE + i as i32, px(10), y + px(3), px(90), px(20));
+        let field_w = match browse {
+            Some(id) => {
+                place(id, right - btn_w, y, btn_w, px(24));
+                right - btn_w - px(10) - edit_x
+            }
+            None => right - edit_x,
+        };
+        // A combo box's height includes its drop-down list
+        let field_h = if field == ID_SPECIAL_COMBO { px(200) } else { height };
+        place(field, edit_x, y, field_w, field_h);
+        y += height + px(16);
     }
     
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(ok_text.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(0x0001), // BS_DEFPUSHBUTTON
-        370, btn_y, 90, 30, hwnd, HMENU(ID_OK as *mut _), hinstance, None
-    );
-    let _ = CreateWindowExW(
-        WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(cancel_text.as_ptr()),
-        WS_CHILD | WS_VISIBLE | WS_TABSTOP,
-        470, btn_y, 90, 30, hwnd, HMENU(ID_CANCEL as *mut _), hinstance, None
-    );
+    let btn_y = y + px(14);
+    place(ID_REMOVE, px(15), btn_y, px(80), px(28));
+    place(ID_OK, right - 2 * btn_w - px(10), btn_y, btn_w, px(30));
+    place(ID_CANCEL, right - btn_w, btn_y, btn_w, px(30));
 }
 
 unsafe fn populate_controls(hwnd: HWND) {
@@ -315,6 +366,13 @@ unsafe fn populate_controls(hwnd: HWND) {
             set_edit_text(hwnd, ID_PATH_EDIT, &item.path.to_string_lossy());
             set_edit_text(hwnd, ID_ICON_EDIT, &item.icon.as_ref().map(|p| p.to_string_lossy().to_string()).unwrap_or_default());
             set_edit_text(hwnd, ID_ARGS_EDIT, &item.args.join(" "));
+            set_edit_text(hwnd, ID_TOOLTIP_EDIT, item.tooltip.as_deref().unwrap_or(""));
+            set_edit_text(hwnd, ID_CONFIRM_EDIT, item.confirm.as_deref().unwrap_or(""));
+            // Multiline edit controls need CRLF line breaks
+            set_edit_text(hwnd, ID_COMMAND_EDIT, &item.command.as_deref().unwrap_or("").replace('\n', "\r\n"));
+            if item.hidden {
+                let _ = CheckDlgButton(hwnd, ID_HIDDEN_CHECK, BST_CHECKED);
+            }
             
             // Set special combo
             if let Ok(combo) = GetDlgItem(hwnd, ID_SPECIAL_COMBO) {
@@ -349,6 +407,8 @@ unsafe fn get_edit_text(hwnd: HWND, id: i32) -> String {
 }
 
 unsafe fn handle_command(hwnd: HWND, id: i32) {
+    // Enter in the read-only dialog closes it, the same as Close
+    let id = if id == ID_OK && DIALOG_READ_ONLY.with(|cell| *cell.borrow()) { ID_CANCEL } else { id };
     match id {
         ID_PATH_BROWSE => {
             let current = get_edit_text(hwnd, ID_PATH_EDIT);
@@ -375,6 +435,9 @@ unsafe fn handle_command(hwnd: HWND, id: i32) {
             let path_str = get_edit_text(hwnd, ID_PATH_EDIT);
             let icon_str = get_edit_text(hwnd, ID_ICON_EDIT);
             let args_str = get_edit_text(hwnd, ID_ARGS_EDIT);
+            let tooltip_str = get_edit_text(hwnd, ID_TOOLTIP_EDIT);
+            let confirm_str = get_edit_text(hwnd, ID_CONFIRM_EDIT);
+            let command_str = get_edit_text(hwnd, ID_COMMAND_EDIT).replace("\r\n", "\n");
             
             let sel = if let Ok(combo) = GetDlgItem(hwnd, ID_SPECIAL_COMBO) {
                 SendMessageW(combo, CB_GETCURSEL, WPARAM(0), LPARAM(0)).0 as i32
@@ -394,11 +457,27 @@ unsafe fn handle_command(hwnd: HWND, id: i32) {
                 icon: if icon_str.is_empty() { None } else { Some(PathBuf::from(icon_str)) },
                 args: if args_str.is_empty() { Vec::new() } else { shell_words::split(&args_str).unwrap_or_else(|_| vec![args_str]) },
                 separator: false,
+                region: false,
+                spacer: None,
                 special,
+                match_pattern: DIALOG_ITEM.with(|cell| {
+                    cell.borrow().as_ref().and_then(|item| item.match_pattern.clone())
+                }),
+                confirm: if confirm_str.trim().is_empty() { None } else { Some(confirm_str) },
+                command: if command_str.trim().is_empty() { None } else { Some(command_str) },
+                tooltip: if tooltip_str.trim().is_empty() { None } else { Some(tooltip_str) },
+                badge: DIALOG_ITEM.with(|cell| {
+                    cell.borrow().as_ref().and_then(|item| item.badge.clone())
+                }),
+                hidden: IsDlgButtonChecked(hwnd, ID_HIDDEN_CHECK) == BST_CHECKED.0,
+                children: DIALOG_ITEM.with(|cell| {
+                    cell.borrow().as_ref().map(|item| item.children.clone()).unwrap_or_default()
+                }),
+                included_from: None,
             };
             
             DIALOG_RESULT.with(|cell| {
-                *cell.borrow_mut() = Some(DialogResult::Ok(item));
+                *cell.borrow_mut() = Some(DialogResult::Ok(Box::new(item)));
             });
             let _ = DestroyWindow(hwnd);
         }
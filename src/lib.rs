@@ -0,0 +1,30 @@
+//! rDock's config model and software renderer, usable without the dock app
+//!
+//! The `rdock` binary is built on these modules. `Renderer` loads each item's
+//! icon once and composites the whole dock (background, magnified icons,
+//! reflections, running indicators) into a caller-provided ARGB buffer, so
+//! other tools can draw a dock the same way without winit or softbuffer.
+//! Build with `default-features = false` to leave out the dock app's own
+//! dependencies (winit, softbuffer, tray-icon, notify, env_logger).
+//! Icon extraction from executables uses the Windows shell; elsewhere only
+//! image files load, special items get their built-in icons and other items
+//! get placeholder tiles.
+//!
+//! ```
+//! use rdock::config::{Config, DockItem, DockSettings};
+//! use rdock::renderer::Renderer;
+//!
+//! let items = vec![DockItem::from_exe("C:\\Windows\\notepad.exe".into())];
+//! let config = Config { dock: DockSettings::default(), items: Vec::new() };
+//! let renderer = Renderer::new(&config, &items)?;
+//!
+//! let mut buffer = vec![0u32; (renderer.width * renderer.height) as usize];
+//! renderer.render(&mut buffer, &items, &[false], &[], None, &[1.0], None);
+//! assert!(buffer.iter().any(|&pixel| pixel != 0));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod config;
+pub mod glyphs;
+pub mod renderer;
+pub mod text;
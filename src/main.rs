@@ -1,43 +1,81 @@
 #![windows_subsystem = "windows"]
 
+mod app_launcher;
 mod app_monitor;
-mod config;
+mod appbar;
+mod bundle;
 mod context_menu;
+mod dialog;
+mod doctor;
+mod hotkey;
+mod ipc;
 mod item_editor;
-mod renderer;
+mod recent_apps;
+mod session;
+mod settings_dialog;
+mod shortcut;
+mod sound;
+mod special;
 mod tooltip;
 mod tray_popup;
 mod window_focus;
 
 use anyhow::Result;
-use config::{Config, DockItem, DockSettings};
+use rdock::{config, renderer, text};
+use config::{Config, DockItem, DockSettings, HideStyle, HotCorner, MagnificationMode, Overflow, RevealFeedback, TaskbarMode};
 use notify::{Watcher, RecursiveMode, Event, EventKind};
 use renderer::Renderer;
 use tooltip::Tooltip;
 use softbuffer::Surface;
+use std::collections::HashMap;
 use std::num::NonZeroU32;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::rc::Rc;
 use std::sync::mpsc;
 use std::time::{Duration, Instant, SystemTime};
 use tray_icon::{
-    menu::{Menu, MenuEvent, MenuItem},
+    menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem},
     TrayIconBuilder, TrayIconEvent,
 };
 use winit::application::ApplicationHandler;
 use winit::dpi::{PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::keyboard::ModifiersState;
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::monitor::MonitorHandle;
+use winit::keyboard::{Key, NamedKey};
 use winit::platform::windows::WindowAttributesExtWindows;
 use winit::window::{Window, WindowId, WindowLevel};
 
-const PROCESS_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+// Fallback frame interval when the monitor doesn't report a refresh rate
 const ANIMATION_FRAME_TIME: Duration = Duration::from_millis(16);
-const TASKBAR_CHECK_INTERVAL: Duration = Duration::from_secs(1);
-const MOUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
-const FULLSCREEN_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+// Shortest frame interval we'll schedule (caps animation at 240fps)
+const MIN_FRAME_TIME: Duration = Duration::from_micros(4_166);
+// Exponential easing rates (per second) for the dock slide and icon scaling.
+// Equivalent to covering 15% / 30% of the remaining distance per 60fps frame.
+const DOCK_EASE_RATE: f32 = 9.75;
+const ICON_EASE_RATE: f32 = 21.4;
 const ZORDER_REASSERT_INTERVAL: Duration = Duration::from_millis(1000);
+const PRESENTATION_HOTKEY_ID: i32 = 1;
+// Hide requests are ignored this long after the dock starts showing, so the
+// cursor leaving the trigger edge mid-animation can't bounce it back down
+const SHOW_GRACE_PERIOD: Duration = Duration::from_millis(200);
+// External config edits are reloaded once the file has been quiet this long,
+// so editors that write in several steps trigger one reload of the final file
+const CONFIG_RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
+// With dock.intro_animation the dock rises this far into place at startup
+// while fading in from fully transparent
+const INTRO_DURATION: Duration = Duration::from_millis(300);
+const INTRO_RISE_PX: f32 = 24.0;
+// With reveal_feedback = "pulse" the background brightens and fades back
+// over this long when the dock comes out of hiding
+const REVEAL_PULSE_DURATION: Duration = Duration::from_millis(150);
+// "Preview Config Changes" goes back to the previous config after this long
+// unless the dock is clicked
+const CONFIG_PREVIEW_DURATION: Duration = Duration::from_secs(10);
+// A restarted rDock waits at most this long for the old one to exit
+const RESTART_WAIT: Duration = Duration::from_secs(5);
 
 
 /// Check if a fullscreen application is currently running
@@ -128,40 +166,71 @@ fn is_fullscreen_app_active() -> bool {
     }
 }
 
-/// Hide or show the Windows taskbar
+/// Switch on the taskbar's own auto-hide setting. Returns the previous
+/// ABM_GETSTATE flags so they can be restored on exit.
 #[cfg(windows)]
-fn set_taskbar_visibility(visible: bool) {
+fn enable_taskbar_autohide() -> Option<u32> {
+    use windows::Win32::UI::Shell::{SHAppBarMessage, APPBARDATA, ABM_GETSTATE, ABM_SETSTATE, ABS_AUTOHIDE};
+    use windows::Win32::UI::WindowsAndMessaging::FindWindowW;
+    use windows::Win32::Foundation::LPARAM;
+    use windows::core::PCWSTR;
+    
+    unsafe {
+        let class_name: Vec<u16> = "Shell_TrayWnd".encode_utf16().chain(std::iter::once(0)).collect();
+        let taskbar = FindWindowW(PCWSTR(class_name.as_ptr()), PCWSTR::null()).ok()?;
+        let mut data = APPBARDATA {
+            cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+            hWnd: taskbar,
+            ..Default::default()
+        };
+        let previous = SHAppBarMessage(ABM_GETSTATE, &mut data) as u32;
+        if previous & ABS_AUTOHIDE == 0 {
+            data.lParam = LPARAM((previous | ABS_AUTOHIDE) as isize);
+            SHAppBarMessage(ABM_SETSTATE, &mut data);
+        }
+        Some(previous)
+    }
+}
+
+/// Put the taskbar auto-hide flags back to what `enable_taskbar_autohide` saw
+#[cfg(windows)]
+fn restore_taskbar_state(state: u32) {
+    use windows::Win32::UI::Shell::{SHAppBarMessage, APPBARDATA, ABM_SETSTATE};
+    use windows::Win32::UI::WindowsAndMessaging::FindWindowW;
+    use windows::Win32::Foundation::LPARAM;
+    use windows::core::PCWSTR;
+    
+    unsafe {
+        let class_name: Vec<u16> = "Shell_TrayWnd".encode_utf16().chain(std::iter::once(0)).collect();
+        if let Ok(taskbar) = FindWindowW(PCWSTR(class_name.as_ptr()), PCWSTR::null()) {
+            let mut data = APPBARDATA {
+                cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+                hWnd: taskbar,
+                lParam: LPARAM(state as isize),
+                ..Default::default()
+            };
+            SHAppBarMessage(ABM_SETSTATE, &mut data);
+        }
+    }
+}
+
+/// The primary taskbar and every secondary (multi-monitor) one
+#[cfg(windows)]
+fn find_taskbars() -> Vec<windows::Win32::Foundation::HWND> {
     use windows::Win32::UI::WindowsAndMessaging::*;
     use windows::core::PCWSTR;
     use windows::Win32::Foundation::HWND;
     
+    let mut taskbars = Vec::new();
     unsafe {
-        let cmd = if visible { SW_SHOW } else { SW_HIDE };
-        
-        // Primary taskbar
         let class_name: Vec<u16> = "Shell_TrayWnd".encode_utf16().chain(std::iter::once(0)).collect();
         if let Ok(taskbar) = FindWindowW(PCWSTR(class_name.as_ptr()), PCWSTR::null()) {
             if !taskbar.0.is_null() {
-                let is_visible = IsWindowVisible(taskbar).as_bool();
-                if is_visible != visible {
-                    let _ = ShowWindow(taskbar, cmd);
-                }
-                if !visible {
-                    // More aggressive hiding - move it off screen
-                    let _ = SetWindowPos(
-                        taskbar,
-                        HWND::default(),
-                        -10000, -10000, 0, 0,
-                        SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE
-                    );
-                }
+                taskbars.push(taskbar);
             }
         }
         
-        // Secondary taskbars (multi-monitor)
-        // Use EnumWindows to find all secondary taskbars
         let class_name2: Vec<u16> = "Shell_SecondaryTrayWnd".encode_utf16().chain(std::iter::once(0)).collect();
-        let mut taskbars = Vec::new();
         let mut hwnd = FindWindowExW(HWND::default(), HWND::default(), PCWSTR(class_name2.as_ptr()), PCWSTR::null());
         while let Ok(taskbar2) = hwnd {
             if taskbar2.0.is_null() {
@@ -171,38 +240,71 @@ fn set_taskbar_visibility(visible: bool) {
             // Find next secondary taskbar
             hwnd = FindWindowExW(HWND::default(), taskbar2, PCWSTR(class_name2.as_ptr()), PCWSTR::null());
         }
+    }
+    taskbars
+}
+
+/// Hide the taskbar on the dock's monitor, leaving the other monitors'
+/// taskbars alone. Taskbars hidden earlier are remembered in `hidden` (as raw
+/// handles) and hidden again, since once moved off screen they no longer
+/// report the dock's monitor.
+#[cfg(windows)]
+fn hide_taskbars(dock: windows::Win32::Foundation::HWND, hidden: &mut Vec<isize>) {
+    use windows::Win32::UI::WindowsAndMessaging::*;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Graphics::Gdi::{MonitorFromWindow, MONITOR_DEFAULTTONULL, MONITOR_DEFAULTTOPRIMARY};
+    
+    unsafe {
+        // Explorer restarts replace its taskbars
+        hidden.retain(|&taskbar| IsWindow(HWND(taskbar as *mut _)).as_bool());
         
-        for taskbar2 in taskbars {
-            let is_visible = IsWindowVisible(taskbar2).as_bool();
-            if is_visible != visible {
-                let _ = ShowWindow(taskbar2, cmd);
+        let dock_monitor = MonitorFromWindow(dock, MONITOR_DEFAULTTOPRIMARY);
+        for taskbar in find_taskbars() {
+            let known = hidden.contains(&(taskbar.0 as isize));
+            if !known && MonitorFromWindow(taskbar, MONITOR_DEFAULTTONULL) != dock_monitor {
+                continue;
             }
-            if !visible {
-                // More aggressive hiding
-                let _ = SetWindowPos(
-                    taskbar2,
-                    HWND::default(),
-                    -10000, -10000, 0, 0,
-                    SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE
-                );
+            if IsWindowVisible(taskbar).as_bool() {
+                let _ = ShowWindow(taskbar, SW_HIDE);
+            }
+            // More aggressive hiding - move it off screen
+            let _ = SetWindowPos(
+                taskbar,
+                HWND::default(),
+                -10000, -10000, 0, 0,
+                SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE
+            );
+            if !known {
+                hidden.push(taskbar.0 as isize);
+            }
+        }
+    }
+}
+
+/// Show the taskbars `hide_taskbars` hid, and forget them
+#[cfg(windows)]
+fn show_taskbars(hidden: &mut Vec<isize>) {
+    use windows::Win32::UI::WindowsAndMessaging::*;
+    use windows::Win32::Foundation::HWND;
+    
+    for taskbar in hidden.drain(..) {
+        let taskbar = HWND(taskbar as *mut _);
+        unsafe {
+            if IsWindow(taskbar).as_bool() && !IsWindowVisible(taskbar).as_bool() {
+                let _ = ShowWindow(taskbar, SW_SHOW);
             }
         }
     }
 }
 
-/// Create a tray icon with a dock-like design (3 rounded squares)
-fn create_tray_icon(color_hex: &str) -> Result<tray_icon::Icon, tray_icon::BadIcon> {
+/// Tray icon: three squares in the indicator color, dimmed while the dock is
+/// hidden, with a dot underneath when auto-hide is on.
+fn create_tray_icon(color_hex: &str, hidden: bool, auto_hide: bool) -> Result<tray_icon::Icon, tray_icon::BadIcon> {
     const SIZE: usize = 32;
     let mut rgba = vec![0u8; SIZE * SIZE * 4];
     
     // Parse color
-    let hex = color_hex.trim_start_matches('#');
-    let (r, g, b) = if hex.len() >= 6 {
-        let val = u32::from_str_radix(hex, 16).unwrap_or(0xCBA6F7);
-        (((val >> 16) & 0xFF) as u8, ((val >> 8) & 0xFF) as u8, (val & 0xFF) as u8)
-    } else {
-        (203, 166, 247) // Default purple
-    };
+    let (r, g, b) = config::parse_hex_rgb(color_hex);
     
     // Draw 3 rounded squares representing dock icons
     let square_size = 7;
@@ -210,6 +312,7 @@ fn create_tray_icon(color_hex: &str) -> Result<tray_icon::Icon, tray_icon::BadIc
     let total_width = square_size * 3 + gap * 2;
     let start_x = (SIZE - total_width) / 2;
     let start_y = (SIZE - square_size) / 2;
+    let square_alpha = if hidden { 110 } else { 255 };
     
     for i in 0..3 {
         let sx = start_x + i * (square_size + gap);
@@ -225,24 +328,54 @@ fn create_tray_icon(color_hex: &str) -> Result<tray_icon::Icon, tray_icon::BadIc
                         rgba[idx] = r;
                         rgba[idx + 1] = g;
                         rgba[idx + 2] = b;
-                        rgba[idx + 3] = 255;
+                        rgba[idx + 3] = square_alpha;
                     }
                 }
             }
         }
     }
     
+    if auto_hide {
+        // 3x3 dot centered under the middle square
+        let dot_x = SIZE / 2 - 1;
+        let dot_y = start_y + square_size + 3;
+        for y in dot_y..dot_y + 3 {
+            for x in dot_x..dot_x + 3 {
+                let idx = (y * SIZE + x) * 4;
+                rgba[idx] = r;
+                rgba[idx + 1] = g;
+                rgba[idx + 2] = b;
+                rgba[idx + 3] = 255;
+            }
+        }
+    }
+    
     tray_icon::Icon::from_rgba(rgba, SIZE as u32, SIZE as u32)
 }
 
-struct DockApp {
+/// The config file applied on trial by "Preview Config Changes"
+struct ConfigPreview {
+    /// What the dock showed before, restored when the preview runs out
+    previous: Config,
+    until: Instant,
+}
+
+/// One dock window and the state tied to its monitor. The dock in
+/// `DockApp::screen` is the one being handled; with `all_monitors` the others
+/// wait in `other_screens` and are swapped in while their window is.
+struct ScreenDock {
+    /// The dock that follows the primary monitor. It alone holds the hotkey,
+    /// the AppBar reservation and the setting-change hook.
+    main: bool,
+    /// The monitor a secondary dock sits on; the main dock looks up the
+    /// primary monitor each time instead
+    monitor: Option<MonitorHandle>,
     window: Option<Rc<Window>>,
     surface: Option<Surface<Rc<Window>, Rc<Window>>>,
-    config: Config,
     renderer: Option<Renderer>,
     hovered_item: Option<usize>,
-    running_states: Vec<bool>,
-    last_process_check: Instant,
+    // The tooltip includes launch stats (Shift was held when it was shown)
+    tooltip_stats: bool,
     cursor_in_window: bool,
     
     // Animation state
@@ -252,6 +385,11 @@ struct DockApp {
     dock_y_visible: f32,
     hide_timer: Option<Instant>,
     show_timer: Option<Instant>,
+    // When the startup intro began; None once it has finished
+    intro_start: Option<Instant>,
+    // When the reveal pulse began; None when it isn't playing
+    pulse_start: Option<Instant>,
+    last_shown: Option<Instant>,
     icon_scales: Vec<f32>,
     
     // Cursor position for smooth wave effect
@@ -262,28 +400,22 @@ struct DockApp {
     dragging: bool,
     drag_start_idx: Option<usize>,
     drag_start_x: f32,
+    // Wheel movement not yet worth a whole item of scrolling
+    wheel_lines: f32,
     
-    // Screen info
-    screen_width: u32,
-    screen_height: u32,
-    
-    // Tray
-    _tray: Option<tray_icon::TrayIcon>,
-    quit_id: Option<tray_icon::menu::MenuId>,
-    
-    // Hot reload
-    config_path: PathBuf,
-    config_rx: Option<mpsc::Receiver<Result<Event, notify::Error>>>,
-    _watcher: Option<notify::RecommendedWatcher>,
-    needs_reload: bool,
-    last_config_modified: Option<SystemTime>,
-    last_config_poll: Instant,
+    // Monitor bounds in desktop coordinates
+    left: i32,
+    top: i32,
+    width: u32,
+    height: u32,
     
     // Tooltip
     tooltip: Option<Tooltip>,
     
     // Taskbar state
     taskbar_hidden: bool,
+    /// Raw handles of the taskbars `hide_windows_taskbar` hid, to show again
+    hidden_taskbars: Vec<isize>,
     last_taskbar_check: Instant,
     
     // Mouse polling
@@ -291,36 +423,32 @@ struct DockApp {
     
     // Fullscreen detection
     fullscreen_active: bool,
+    // A `hide_when_focused` app is in the foreground
+    focus_hidden: bool,
+    // One of the dock's items is in the foreground (`stay_visible_when_app_active`)
+    app_active: bool,
     last_fullscreen_check: Instant,
     
     // Z-order maintenance
     last_zorder_reassert: Instant,
     
-    // Frame timing for delta-time animation
-    last_frame_time: Instant,
+    // Frame timing for delta-time animation; None while idle so the first
+    // frame after a pause doesn't count the whole pause as elapsed time
+    last_frame_time: Option<Instant>,
+    /// Redraw interval while animating, matched to the monitor refresh rate
+    frame_time: Duration,
 }
 
-impl DockApp {
-    fn new(config: Config, config_path: PathBuf) -> Self {
-        let n = config.items.len();
-        
-        // Canonicalize path for reliable file watching
-        let config_path = config_path.canonicalize().unwrap_or(config_path);
-        
-        // Set up file watcher for hot reload
-        let (tx, rx) = mpsc::channel();
-        let watcher = notify::recommended_watcher(move |res| {
-            let _ = tx.send(res);
-        }).ok();
-        
+impl ScreenDock {
+    fn new(main: bool, monitor: Option<MonitorHandle>, item_count: usize) -> Self {
         Self {
+            main,
+            monitor,
             window: None,
             surface: None,
-            config,
             renderer: None,
             hovered_item: None,
-            running_states: Vec::new(),
-            last_process_check: Instant::now() - PROCESS_CHECK_INTERVAL,
+            tooltip_stats: false,
             cursor_in_window: false,
             dock_y_current: 0.0,
             dock_y_target: 0.0,
@@ -328,37 +456,229 @@ impl DockApp {
             dock_y_visible: 0.0,
             hide_timer: None,
             show_timer: None,
-            icon_scales: vec![1.0; n],
+            intro_start: None,
+            pulse_start: None,
+            last_shown: None,
+            icon_scales: vec![1.0; item_count],
             cursor_x: -1000.0,
             cursor_y: -1000.0,
             dragging: false,
             drag_start_idx: None,
             drag_start_x: 0.0,
-            screen_width: 1920,
-            screen_height: 1080,
-            _tray: None,
-            quit_id: None,
-            config_path,
-            config_rx: Some(rx),
-            _watcher: watcher,
-            needs_reload: false,
-            last_config_modified: None,
-            last_config_poll: Instant::now(),
+            wheel_lines: 0.0,
+            left: 0,
+            top: 0,
+            width: 1920,
+            height: 1080,
             tooltip: None,
             taskbar_hidden: false,
+            hidden_taskbars: Vec::new(),
             last_taskbar_check: Instant::now(),
             last_mouse_poll: Instant::now(),
             fullscreen_active: false,
+            focus_hidden: false,
+            app_active: false,
             last_fullscreen_check: Instant::now(),
             last_zorder_reassert: Instant::now(),
-            last_frame_time: Instant::now(),
+            last_frame_time: None,
+            frame_time: ANIMATION_FRAME_TIME,
+        }
+    }
+    
+    /// Which dock this is in `session.toml`: the main one, or its monitor
+    fn session_key(&self) -> String {
+        match (&self.monitor, self.main) {
+            (Some(monitor), false) => monitor.name().unwrap_or_default(),
+            _ => "main".to_string(),
+        }
+    }
+    
+    /// Whether the desktop point (`x`, `y`) is on this dock's monitor
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left && x < self.left + self.width as i32
+            && y >= self.top && y < self.top + self.height as i32
+    }
+}
+
+struct DockApp {
+    /// The dock being handled: the main one, or a secondary one swapped in
+    screen: ScreenDock,
+    /// With `all_monitors`, the docks on the other monitors
+    other_screens: Vec<ScreenDock>,
+    config: Config,
+    running_states: Vec<bool>,
+    /// Per dock item: running but not responding (with `show_not_responding`)
+    hung_states: Vec<bool>,
+    last_process_check: Instant,
+    
+    // Items shown in the dock: pinned config items, then running-only apps
+    dock_items: Vec<DockItem>,
+    // Index in `config.items` of each pinned item in `dock_items`
+    // (hidden items are left out, so the two don't line up)
+    pinned_indices: Vec<usize>,
+    running_items: Vec<DockItem>,
+    
+    // Config before the last edit made through the dock, for one-step undo
+    undo_config: Option<Config>,
+    
+    // Launch history for the recent_apps special item
+    recent_apps: recent_apps::RecentApps,
+    recent_apps_path: PathBuf,
+    
+    // Where scrolled docks were left, restored at startup
+    session: session::Session,
+    session_path: PathBuf,
+    
+    // Last window focused through the dock, per executable path
+    focused_windows: HashMap<PathBuf, isize>,
+    
+    // Windows the toggle_desktop special minimized, restored on its next click
+    desktop_minimized: Vec<isize>,
+    
+    // Tray
+    tray: Option<tray_icon::TrayIcon>,
+    /// (hidden, auto_hide) the tray icon was last drawn for
+    tray_state: Option<(bool, bool)>,
+    quit_id: Option<tray_icon::menu::MenuId>,
+    toggle_dock_id: Option<tray_icon::menu::MenuId>,
+    reload_id: Option<tray_icon::menu::MenuId>,
+    preview_config_id: Option<tray_icon::menu::MenuId>,
+    restart_id: Option<tray_icon::menu::MenuId>,
+    edit_config_id: Option<tray_icon::menu::MenuId>,
+    compact_item: Option<CheckMenuItem>,
+    auto_hide_item: Option<CheckMenuItem>,
+    lock_item: Option<CheckMenuItem>,
+    presentation_item: Option<CheckMenuItem>,
+    always_on_top_item: Option<CheckMenuItem>,
+    
+    // Compact mode: runtime size multiplier, not written to the items config
+    compact: bool,
+    /// Presentation mode: dock forced hidden and taskbar left alone until toggled off
+    presentation_mode: bool,
+    /// `presentation_hotkey` as currently registered
+    registered_hotkey: Option<String>,
+    // Named pipe for `--status` and other commands from outside
+    ipc: Option<ipc::IpcServer>,
+    /// Badges set with `--badge`, by lowercased item name; they replace the
+    /// config's `badge` until cleared and aren't saved
+    badges: HashMap<String, String>,
+    
+    // Hot reload
+    config_path: PathBuf,
+    config_rx: Option<mpsc::Receiver<Result<Event, notify::Error>>>,
+    _watcher: Option<notify::RecommendedWatcher>,
+    needs_reload: bool,
+    // Last time an external change to the config file was seen (debounced)
+    pending_reload_since: Option<Instant>,
+    last_config_modified: Option<SystemTime>,
+    // The config file didn't parse at startup: running on defaults, and
+    // saves are held back so they don't replace the user's file
+    config_unreadable: bool,
+    last_config_poll: Instant,
+    config_preview: Option<ConfigPreview>,
+    // Shift/Ctrl/Alt as last reported to the window, for `modifier_clicks`
+    modifiers: ModifiersState,
+    
+    /// Work-area reservation while `reserve_space` is on and auto-hide is
+    /// off; only the main dock reserves space
+    appbar: Option<appbar::AppBar>,
+    
+    /// Taskbar ABM_GETSTATE flags from before auto-hide mode changed them
+    taskbar_restore_state: Option<u32>,
+    /// `all_monitors` changed, so docks need opening or closing
+    screens_changed: bool,
+    
+    /// Windows' "Animation effects" setting; when off, the dock snaps instead of easing
+    system_animations: bool,
+    /// When each item last started a new process, for `launch_cooldown_ms`,
+    /// keyed by `recent_apps::launch_key` so it follows the item around
+    launch_cooldowns: HashMap<String, Instant>,
+    /// Executable names the running-state check looks for, from `dock_items`
+    running_targets: app_monitor::TargetNames,
+}
+
+impl DockApp {
+    fn new(config: Config, config_path: PathBuf) -> Self {
+        let pinned_indices = pinned_indices(&config);
+        let dock_items: Vec<DockItem> = pinned_indices.iter().map(|&i| config.items[i].clone()).collect();
+        let n = dock_items.len();
+        let running_targets = app_monitor::target_names(&dock_items);
+        let config_compact = config.dock.compact;
+        
+        // Canonicalize path for reliable file watching
+        let config_path = config_path.canonicalize().unwrap_or(config_path);
+        let recent_apps_path = config_path.with_file_name(recent_apps::RECENT_FILE_NAME);
+        let session_path = config_path.with_file_name(session::SESSION_FILE_NAME);
+        
+        // Set up file watcher for hot reload
+        let (tx, rx) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        }).ok();
+        let last_process_check = time_ago(config.dock.intervals.process_check());
+        
+        Self {
+            screen: ScreenDock::new(true, None, n),
+            other_screens: Vec::new(),
+            config,
+            running_states: Vec::new(),
+            hung_states: Vec::new(),
+            last_process_check,
+            dock_items,
+            pinned_indices,
+            running_items: Vec::new(),
+            undo_config: None,
+            recent_apps: recent_apps::RecentApps::load(&recent_apps_path),
+            recent_apps_path,
+            session: session::Session::load(&session_path),
+            session_path,
+            focused_windows: HashMap::new(),
+            desktop_minimized: Vec::new(),
+            tray: None,
+            tray_state: None,
+            quit_id: None,
+            toggle_dock_id: None,
+            reload_id: None,
+            preview_config_id: None,
+            restart_id: None,
+            edit_config_id: None,
+            compact_item: None,
+            auto_hide_item: None,
+            lock_item: None,
+            presentation_item: None,
+            always_on_top_item: None,
+            compact: config_compact,
+            presentation_mode: false,
+            registered_hotkey: None,
+            ipc: ipc::IpcServer::start(),
+            badges: HashMap::new(),
+            config_path,
+            config_rx: Some(rx),
+            _watcher: watcher,
+            needs_reload: false,
+            pending_reload_since: None,
+            last_config_modified: None,
+            config_unreadable: false,
+            last_config_poll: Instant::now(),
+            config_preview: None,
+            modifiers: ModifiersState::empty(),
+            appbar: None,
+            taskbar_restore_state: None,
+            screens_changed: false,
+            system_animations: true,
+            launch_cooldowns: HashMap::new(),
+            running_targets,
         }
     }
     
     fn start_watching(&mut self) {
+        // Watch the directory rather than the file itself: editors and
+        // `git checkout` often replace the file (delete + create or rename),
+        // which would silently end a watch on the old file.
+        let dir = self.config_path.parent().unwrap_or(&self.config_path);
         if let Some(watcher) = &mut self._watcher {
-            if let Err(e) = watcher.watch(&self.config_path, RecursiveMode::NonRecursive) {
-                eprintln!("Failed to watch config: {}", e);
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                log::error!("Failed to watch config: {}", e);
             }
         }
     }
@@ -367,26 +687,38 @@ impl DockApp {
         // Check notify watcher events
         if let Some(rx) = &self.config_rx {
             while let Ok(event) = rx.try_recv() {
-                if let Ok(Event { kind: EventKind::Modify(_), .. }) = event {
-                    self.needs_reload = true;
+                if let Ok(event) = event {
+                    if is_config_change(&event, &self.config_path) {
+                        self.pending_reload_since = Some(Instant::now());
+                    }
                 }
             }
         }
         
-        // Fallback: poll file modification time every 500ms
+        // Fallback: poll file modification time every 500ms. While the file
+        // is missing nothing changes; the last good config stays loaded.
         if self.last_config_poll.elapsed() >= Duration::from_millis(500) {
             self.last_config_poll = Instant::now();
             if let Ok(meta) = std::fs::metadata(&self.config_path) {
                 if let Ok(modified) = meta.modified() {
                     if let Some(last) = self.last_config_modified {
-                        if modified > last {
-                            self.needs_reload = true;
+                        // A replacement file can carry an older timestamp
+                        if modified != last {
+                            self.pending_reload_since = Some(Instant::now());
                         }
                     }
                     self.last_config_modified = Some(modified);
                 }
             }
         }
+        
+        // Reload once writes have settled
+        if let Some(since) = self.pending_reload_since {
+            if since.elapsed() >= CONFIG_RELOAD_DEBOUNCE {
+                self.pending_reload_since = None;
+                self.needs_reload = true;
+            }
+        }
     }
     
     fn reload_config(&mut self) {
@@ -395,242 +727,1009 @@ impl DockApp {
         }
         self.needs_reload = false;
         
-        // Small delay to let file finish writing
-        std::thread::sleep(Duration::from_millis(50));
-        
-        if let Ok(mut new_config) = Config::load(&self.config_path) {
-            let previous_locked = self.config.dock.locked;
-            let n = new_config.items.len();
-            new_config.dock.locked = previous_locked;
-            self.config = new_config;
-            
-            // Rebuild renderer with new config
-            if let Ok(renderer) = Renderer::new(&self.config, &self.config.items) {
-                // Resize window if needed
-                if let Some(window) = &self.window {
-                    let _ = window.request_inner_size(PhysicalSize::new(renderer.width, renderer.height));
-                    
-                    // Reposition with vertical offset
-                    let x = (self.screen_width - renderer.width) / 2;
-                    let offset = self.config.dock.negative_vertical_offset;
-                    let y_vis = (self.screen_height as i32 - renderer.height as i32 + offset) as u32;
-                    self.dock_y_visible = y_vis as f32;
-                    self.dock_y_hidden = (self.screen_height + 20) as f32;
-                    self.dock_y_target = y_vis as f32;
-                    self.dock_y_current = y_vis as f32;
-                    window.set_outer_position(PhysicalPosition::new(x as i32, y_vis as i32));
-                    
-                    // Request redraw to ensure window updates
-                    window.request_redraw();
-                }
+        // Parse fully before touching the live config; on error keep running
+        // with the current one (a later save will trigger another reload)
+        let new_config = match Config::load(&self.config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Failed to reload config: {:#}", e);
+                return;
+            }
+        };
+        // The file changing again (or the dock saving it) keeps a preview
+        self.config_preview = None;
+        self.apply_config(new_config);
+    }
+    
+    /// Load the config file on trial: it shows until `CONFIG_PREVIEW_DURATION`
+    /// runs out, then the dock goes back to the config it had and writes that
+    /// back to the file, so later saves don't mix the two. Clicking the dock
+    /// keeps it.
+    fn preview_config(&mut self) {
+        let new_config = match Config::load(&self.config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Failed to preview config: {:#}", e);
+                return;
+            }
+        };
+        // Previewing again restarts the clock but still reverts to the original
+        let previous = match self.config_preview.take() {
+            Some(preview) => preview.previous,
+            None => self.config.clone(),
+        };
+        self.apply_config(new_config);
+        self.config_preview = Some(ConfigPreview {
+            previous,
+            until: Instant::now() + CONFIG_PREVIEW_DURATION,
+        });
+    }
+    
+    /// Put back the config from before a preview once its time is up, in
+    /// the dock and in the file; the edited file is left as `config.toml.bak`
+    fn check_config_preview(&mut self) {
+        if self.config_preview.as_ref().is_some_and(|preview| Instant::now() >= preview.until) {
+            if let Some(preview) = self.config_preview.take() {
+                self.apply_config(preview.previous);
+                self.save_config_with_settings();
+            }
+        }
+    }
+    
+    /// Make `new_config` the live config and rebuild everything it affects
+    fn apply_config(&mut self, mut new_config: Config) {
+        let previous_locked = self.config.dock.locked;
+        new_config.dock.locked = previous_locked;
+        self.config = new_config;
+        self.config_unreadable = false;
+        if !self.config.dock.show_running {
+            self.running_items.clear();
+        }
+        self.refresh_dock_items();
+        let n = self.dock_items.len();
+        
+        self.rebuild_renderer();
+        
+        self.running_states = vec![false; n];
+        self.last_process_check = time_ago(self.config.dock.intervals.process_check());
+        // `all_monitors` may have changed
+        self.screens_changed = true;
+        
+        // Show dock after reload and prevent immediate hiding
+        // Give user time to see the changes (2 seconds grace period)
+        self.for_each_screen(|app| {
+            if !app.presentation_mode {
+                app.screen.dock_y_target = app.screen.dock_y_visible;
+                app.screen.hide_timer = None;
+                app.set_click_through(false);
+            }
+            app.apply_window_level();
+        });
+        self.update_hotkey();
+        self.tray_state = None; // indicator color may have changed
+        self.update_tray_icon();
+        self.sync_tray_checks();
+    }
+
+    /// Rebuild every dock's renderer from the current config and resize and
+    /// reposition the windows and surfaces to match.
+    fn rebuild_renderer(&mut self) {
+        self.for_each_screen(Self::rebuild_screen);
+    }
+    
+    /// `rebuild_renderer` for the dock in `self.screen`
+    fn rebuild_screen(&mut self) {
+        self.screen.icon_scales = vec![1.0; self.dock_items.len()];
+        if let Ok(renderer) = self.create_renderer() {
+            // Resize window if needed
+            if let Some(window) = &self.screen.window {
+                let _ = window.request_inner_size(PhysicalSize::new(renderer.width, renderer.height));
                 
-                // Resize surface
-                if let Some(surface) = &mut self.surface {
-                    let _ = surface.resize(
-                        NonZeroU32::new(renderer.width).unwrap(),
-                        NonZeroU32::new(renderer.height).unwrap(),
-                    );
+                // Reposition with vertical offset
+                let layout = self.compute_layout(&renderer);
+                // Keep a hidden dock hidden (e.g. when running apps change)
+                let showing = (self.screen.dock_y_target - self.screen.dock_y_visible).abs() < 0.5;
+                self.screen.dock_y_visible = layout.y_visible as f32;
+                self.screen.dock_y_hidden = layout.y_hidden as f32;
+                if showing {
+                    self.screen.dock_y_target = self.screen.dock_y_visible;
+                    self.screen.dock_y_current = self.screen.dock_y_visible;
                 }
+                self.apply_dock_y(layout.x);
                 
-                self.renderer = Some(renderer);
+                // Request redraw to ensure window updates
+                window.request_redraw();
             }
             
-            self.running_states = vec![false; n];
-            self.icon_scales = vec![1.0; n];
-            self.last_process_check = Instant::now() - PROCESS_CHECK_INTERVAL;
+            // Resize surface
+            if let Some(surface) = &mut self.screen.surface {
+                let _ = surface.resize(
+                    NonZeroU32::new(renderer.width).unwrap(),
+                    NonZeroU32::new(renderer.height).unwrap(),
+                );
+            }
             
-            // Show dock after reload and prevent immediate hiding
-            // Give user time to see the changes (2 seconds grace period)
-            self.dock_y_target = self.dock_y_visible;
-            self.hide_timer = None;
+            self.screen.renderer = Some(renderer);
+            self.update_appbar();
         }
     }
-
-    fn redraw(&mut self) {
-        // Prepare drag state for rendering (before borrowing surface)
-        let drag_state = if self.dragging {
-            self.drag_start_idx.map(|idx| (idx, self.get_drop_index(), self.cursor_x))
+    
+    /// Follow the monitors after a resolution change, hotplug or reload. The
+    /// main dock stays on the primary monitor, sized and placed for it like
+    /// on a reload: when the old primary was unplugged Windows promotes
+    /// another monitor, which the dock moves to, and with no monitor left it
+    /// stays put until one returns. With `all_monitors` every other monitor
+    /// gets a dock of its own, closed again when its monitor goes away.
+    /// Only called with the main dock in `self.screen`.
+    fn update_screens(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(primary) = event_loop.primary_monitor()
+            .or_else(|| event_loop.available_monitors().next())
+        else {
+            log::warn!("No monitor connected; keeping the dock where it is");
+            return;
+        };
+        self.fit_to_monitor(&primary);
+        
+        let others: Vec<MonitorHandle> = if self.config.dock.all_monitors {
+            event_loop.available_monitors().filter(|monitor| monitor.name() != primary.name()).collect()
         } else {
-            None
+            Vec::new()
         };
+        let mut slot = 0;
+        while slot < self.other_screens.len() {
+            let monitor = self.other_screens[slot].monitor.clone();
+            let still_there = monitor.and_then(|m| others.iter().find(|other| other.name() == m.name()).cloned());
+            self.swap_screen(slot);
+            if let Some(monitor) = still_there {
+                self.fit_to_monitor(&monitor);
+                self.screen.monitor = Some(monitor);
+                self.swap_screen(slot);
+                slot += 1;
+            } else {
+                self.release_taskbar();
+                self.swap_screen(slot);
+                self.other_screens.remove(slot);
+            }
+        }
+        
+        for monitor in others {
+            let has_dock = self.other_screens.iter()
+                .any(|screen| screen.monitor.as_ref().is_some_and(|m| m.name() == monitor.name()));
+            if has_dock {
+                continue;
+            }
+            self.other_screens.push(ScreenDock::new(false, Some(monitor.clone()), self.dock_items.len()));
+            let slot = self.other_screens.len() - 1;
+            self.swap_screen(slot);
+            let opened = self.open_window(event_loop, &monitor);
+            self.swap_screen(slot);
+            if let Err(e) = opened {
+                log::error!("Failed to open the dock on {}: {:#}", monitor.name().unwrap_or_default(), e);
+                self.other_screens.pop();
+            }
+        }
+    }
+    
+    /// Size and place the dock in `self.screen` for `monitor`, if it moved
+    /// or changed resolution
+    fn fit_to_monitor(&mut self, monitor: &MonitorHandle) {
+        let size = monitor.size();
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.screen.frame_time = frame_time_for_refresh(monitor.refresh_rate_millihertz());
+        let position = monitor.position();
+        let bounds = (position.x, position.y, size.width, size.height);
+        if bounds == (self.screen.left, self.screen.top, self.screen.width, self.screen.height) {
+            return;
+        }
+        log::info!("Screen changed to {}x{} at ({}, {})", size.width, size.height, position.x, position.y);
+        (self.screen.left, self.screen.top, self.screen.width, self.screen.height) = bounds;
         
-        let Some(surface) = &mut self.surface else { return };
-        let Some(renderer) = &self.renderer else { return };
+        self.rebuild_screen();
+    }
+    
+    /// Create the window for the dock in `self.screen` on `monitor`, with
+    /// its tooltip, and hide that monitor's taskbar if configured
+    fn open_window(&mut self, event_loop: &ActiveEventLoop, monitor: &MonitorHandle) -> Result<()> {
+        let screen = monitor.size();
+        let position = monitor.position();
+        self.screen.left = position.x;
+        self.screen.top = position.y;
+        self.screen.width = screen.width;
+        self.screen.height = screen.height;
+        self.screen.frame_time = frame_time_for_refresh(monitor.refresh_rate_millihertz());
 
-        let mut buffer = surface.buffer_mut().unwrap();
+        let renderer = self.create_renderer()?;
         
-        renderer.render(
-            &mut buffer,
-            &self.config.items,
-            &self.running_states,
-            self.hovered_item,
-            &self.icon_scales,
-            drag_state,
-        );
+        let dock_w = renderer.width;
+        let dock_h = renderer.height;
 
-        let _ = buffer.present();
-    }
+        let config::Layout { x, y_visible: y_vis, y_hidden } = self.compute_layout(&renderer);
+        self.screen.dock_y_visible = y_vis as f32;
+        self.screen.dock_y_hidden = y_hidden as f32;
+        self.screen.dock_y_current = y_vis as f32;
+        self.screen.dock_y_target = y_vis as f32;
 
-    fn update_running_states(&mut self) {
-        if self.last_process_check.elapsed() < PROCESS_CHECK_INTERVAL {
-            return;
-        }
-        self.last_process_check = Instant::now();
+        let attrs = Window::default_attributes()
+            .with_title("rDock")
+            .with_inner_size(PhysicalSize::new(dock_w, dock_h))
+            .with_position(PhysicalPosition::new(x, y_vis))
+            .with_decorations(false)
+            .with_transparent(true)
+            .with_resizable(false)
+            .with_window_level(self.window_level())
+            .with_skip_taskbar(true);
 
-        let running = app_monitor::get_running_executables();
-        self.running_states = self.config.items
-            .iter()
-            .map(|item| app_monitor::is_running(&item.path, &running))
-            .collect();
-    }
+        let window = Rc::new(event_loop.create_window(attrs)?);
+        
+        // Set position again after creation - with_position doesn't always work
+        window.set_outer_position(PhysicalPosition::new(x, y_vis));
+        
+        let ctx = softbuffer::Context::new(window.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to create drawing context: {}", e))?;
+        let mut surface = Surface::new(&ctx, window.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to create drawing surface: {}", e))?;
+        let (Some(surface_w), Some(surface_h)) = (NonZeroU32::new(dock_w), NonZeroU32::new(dock_h)) else {
+            anyhow::bail!("Dock has no size ({}x{})", dock_w, dock_h);
+        };
+        surface.resize(surface_w, surface_h)
+            .map_err(|e| anyhow::anyhow!("Failed to size drawing surface: {}", e))?;
 
-    fn launch_item(&self, index: usize) {
-        if let Some(item) = self.config.items.get(index) {
-            // Handle special system items
-            if let Some(special) = &item.special {
-                self.launch_special(special);
-                return;
-            }
-            
-            // Regular app launch
-            if item.path.as_os_str().is_empty() {
-                return;
-            }
-            
-            // Try to focus existing window first
-            if window_focus::focus_existing_window(&item.path) {
-                return;
-            }
-            
-            // No existing window found, launch new instance
-            let mut cmd = Command::new(&item.path);
-            if !item.args.is_empty() {
-                cmd.args(&item.args);
-            }
-            #[cfg(windows)]
-            {
-                use std::os::windows::process::CommandExt;
-                cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        self.screen.window = Some(window);
+        self.screen.surface = Some(surface);
+        self.screen.renderer = Some(renderer);
+        self.screen.icon_scales = vec![1.0; self.dock_items.len()];
+        
+        // Initialize tooltip with config background color
+        if let Some(window) = &self.screen.window {
+            use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+            if let Ok(RawWindowHandle::Win32(h)) = window.window_handle().map(|h| h.as_raw()) {
+                let hwnd = windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _);
+                self.screen.tooltip = Tooltip::new_with_color(hwnd, &self.config.dock.background_color);
+                if self.screen.main {
+                    watch_setting_changes(hwnd);
+                }
             }
-            let _ = cmd.spawn();
         }
-    }
-    
-    #[cfg(windows)]
-    fn launch_special(&self, special: &str) {
-        use std::os::windows::process::CommandExt;
         
-        match special {
-            "start_menu" => {
-                // Ctrl+Esc opens Start Menu
-                let script = r#"Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('^{ESC}')"#;
-                let _ = Command::new("powershell")
-                    .args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", script])
-                    .creation_flags(0x08000000)
-                    .spawn();
+        // A monitor plugged in during presentation mode gets a dock that stays out of sight
+        if self.presentation_mode {
+            self.apply_presentation_mode();
+            return Ok(());
+        }
+        
+        // Hide Windows taskbar if configured
+        self.apply_taskbar_mode();
+        
+        // Either way the dock starts below its position and animates into place,
+        // which also works around winit/Windows ignoring the initial position
+        if self.config.dock.intro_animation && self.system_animations {
+            self.screen.intro_start = Some(Instant::now());
+            self.screen.dock_y_current = y_vis as f32 + INTRO_RISE_PX;
+            if let Some(window) = &self.screen.window {
+                set_window_fade(window, Some(0));
             }
-            "recycle_bin" => {
-                let _ = Command::new("explorer")
-                    .arg("shell:RecycleBinFolder")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        } else {
+            self.screen.dock_y_current = y_vis as f32 + 10.0;
+        }
+        if let Some(window) = &self.screen.window {
+            window.request_redraw();
+        }
+        Ok(())
+    }
+    
+    /// Run `f` once for each monitor's dock, with that dock in `self.screen`
+    fn for_each_screen(&mut self, mut f: impl FnMut(&mut Self)) {
+        f(self);
+        for slot in 0..self.other_screens.len() {
+            self.swap_screen(slot);
+            f(self);
+            self.swap_screen(slot);
+        }
+    }
+    
+    /// Trade the dock in `self.screen` for `other_screens[slot]`, or back
+    fn swap_screen(&mut self, slot: usize) {
+        std::mem::swap(&mut self.screen, &mut self.other_screens[slot]);
+    }
+    
+    /// Every dock, starting with the one in `self.screen`
+    fn screens(&self) -> impl Iterator<Item = &ScreenDock> {
+        std::iter::once(&self.screen).chain(&self.other_screens)
+    }
+    
+    fn request_redraws(&self) {
+        for screen in self.screens() {
+            if let Some(window) = &screen.window {
+                window.request_redraw();
             }
-            "settings" => {
-                let _ = Command::new("cmd")
-                    .args(["/c", "start", "ms-settings:"])
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+    }
+    
+    /// Register, resize, or drop the AppBar reservation to match the config
+    fn update_appbar(&mut self) {
+        if !self.screen.main {
+            return;
+        }
+        let wanted = self.config.dock.reserve_space && !self.config.dock.auto_hide;
+        let Some(renderer) = &self.screen.renderer else { return };
+        // Only the part of the dock above the screen edge needs reserving,
+        // plus the gap under a floating dock
+        let height = (renderer.height as i32 - self.config.dock.edge_offset()).max(0) as u32;
+        
+        if !wanted {
+            if let Some(appbar) = self.appbar.take() {
+                appbar.unregister();
             }
-            "show_desktop" => {
-                // Use Shell.Application COM object
-                let _ = Command::new("powershell")
-                    .args(["-Command", "(New-Object -ComObject Shell.Application).ToggleDesktop()"])
-                    .creation_flags(0x08000000)
-                    .spawn();
+            return;
+        }
+        if let Some(appbar) = &mut self.appbar {
+            appbar.set_height(height);
+            return;
+        }
+        
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        let Some(window) = &self.screen.window else { return };
+        if let Ok(RawWindowHandle::Win32(h)) = window.window_handle().map(|h| h.as_raw()) {
+            let hwnd = windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _);
+            self.appbar = appbar::AppBar::register(hwnd, height);
+        }
+    }
+    
+    /// Dock settings as rendered: the config sized for the monitor, shrunk
+    /// when in compact mode.
+    fn effective_dock(&self) -> DockSettings {
+        let dock = self.config.dock.for_screen(self.screen.width, self.screen.height);
+        if self.compact {
+            dock.scaled(self.config.dock.compact_scale.clamp(0.25, 1.0))
+        } else {
+            dock
+        }
+    }
+    
+    fn create_renderer(&self) -> Result<Renderer> {
+        // Renderer only reads [dock] from the config; items are passed separately
+        let config = Config { dock: self.effective_dock(), items: Vec::new() };
+        let mut renderer = Renderer::new(&config, &self.dock_items)?;
+        if self.config.dock.overflow == Overflow::Scroll {
+            renderer.fit_width(self.screen.width, &self.dock_items);
+            // Rebuilds keep the scroll position; a new window takes the saved one
+            renderer.scroll = match &self.screen.renderer {
+                Some(old) => old.scroll,
+                None => self.session.scroll(&self.screen.session_key()),
+            };
+        }
+        Ok(renderer)
+    }
+    
+    /// Move the cursor position the magnification follows without the
+    /// pointer (`scroll_magnify`), hovering whatever it lands on so a click
+    /// opens that item. Touchpads report pixels, followed one to one; wheels
+    /// report lines, one item each.
+    fn scrub_magnification(&mut self, delta: MouseScrollDelta) {
+        let Some(renderer) = &self.screen.renderer else { return };
+        // Down or right moves toward later items
+        let shift = match delta {
+            MouseScrollDelta::PixelDelta(pos) => if pos.x != 0.0 { pos.x as f32 } else { -pos.y as f32 },
+            MouseScrollDelta::LineDelta(x, y) => {
+                let lines = if y != 0.0 { -y } else { x };
+                lines * (renderer.icon_size + renderer.spacing.x) as f32
             }
-            "task_view" => {
-                // Use explorer shell command for task view
-                let _ = Command::new("explorer")
-                    .arg("shell:::{3080F90E-D7AD-11D9-BD98-0000947B0257}")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        };
+        self.screen.cursor_x = (self.screen.cursor_x + shift).clamp(0.0, renderer.width as f32 - 1.0);
+        self.screen.hovered_item = renderer.hit_test(
+            self.screen.cursor_x as i32,
+            self.screen.cursor_y as i32,
+            &self.dock_items,
+            &self.screen.icon_scales,
+        );
+        self.update_tooltip();
+        if let Some(window) = &self.screen.window {
+            window.request_redraw();
+        }
+    }
+    
+    /// Scroll a dock narrowed by `overflow = "scroll"` by `steps` items
+    fn scroll_items(&mut self, steps: i32) {
+        let len = self.dock_items.len();
+        let key = self.screen.session_key();
+        let Some(renderer) = &mut self.screen.renderer else { return };
+        if !renderer.scroll_by(steps, len) {
+            return;
+        }
+        if self.session.set_scroll(&key, renderer.scroll) {
+            if let Err(e) = self.session.save(&self.session_path) {
+                log::warn!("Failed to save session: {:#}", e);
             }
-            "action_center" | "notification_center" => {
-                // Open notification center / action center
-                let _ = Command::new("explorer")
-                    .arg("ms-actioncenter:")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+        // Other items are now under the cursor
+        self.screen.hovered_item = renderer.hit_test(
+            self.screen.cursor_x as i32,
+            self.screen.cursor_y as i32,
+            &self.dock_items,
+            &self.screen.icon_scales,
+        );
+        self.update_tooltip();
+        if let Some(window) = &self.screen.window {
+            window.request_redraw();
+        }
+    }
+    
+    fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+        if let Some(item) = &self.compact_item {
+            item.set_checked(self.compact);
+        }
+        
+        self.rebuild_renderer();
+        self.for_each_screen(Self::show_dock);
+        
+        if self.config.dock.remember_compact {
+            self.config.dock.compact = self.compact;
+            self.save_config_with_settings();
+        }
+    }
+    
+    /// Apply draft dock settings for live preview without touching the config file.
+    fn preview_settings(&mut self, settings: &DockSettings) {
+        let size_changed = settings.icon_size != self.config.dock.icon_size;
+        self.config.dock = settings.clone();
+        
+        if size_changed {
+            self.rebuild_renderer();
+        } else {
+            self.for_each_screen(|app| {
+                if let Some(renderer) = &mut app.screen.renderer {
+                    renderer.apply_style(&app.config.dock);
+                }
+            });
+        }
+        
+        self.for_each_screen(Self::redraw);
+    }
+
+    fn redraw(&mut self) {
+        // Prepare drag state for rendering (before borrowing surface)
+        let drag_state = if self.screen.dragging {
+            self.screen.drag_start_idx.map(|idx| (idx, self.get_drop_index(), self.screen.cursor_x))
+        } else {
+            None
+        };
+        
+        let Some(surface) = &mut self.screen.surface else { return };
+        let Some(renderer) = &self.screen.renderer else { return };
+
+        let mut buffer = surface.buffer_mut().unwrap();
+        
+        renderer.render(
+            &mut buffer,
+            &self.dock_items,
+            &self.running_states,
+            &self.hung_states,
+            self.screen.hovered_item,
+            &self.screen.icon_scales,
+            drag_state,
+        );
+        if self.config_preview.is_some() {
+            let label = "Previewing - click to keep";
+            let (w, _) = text::measure_text(label, 1);
+            let x = (renderer.width as i32 - w as i32) / 2;
+            text::draw_text(&mut buffer, renderer.width as usize, x, 3, label, 0xE0FF_FFFF, 1);
+        }
+
+        let _ = buffer.present();
+    }
+
+    fn update_running_states(&mut self) {
+        if self.last_process_check.elapsed() < self.config.dock.intervals.process_check() {
+            return;
+        }
+        self.last_process_check = Instant::now();
+
+        if self.update_running_items() {
+            self.refresh_dock_items();
+            self.rebuild_renderer();
+        }
+
+        let processes = app_monitor::get_running_executables(&self.running_targets);
+        let running: app_monitor::RunningSet = processes.values().cloned().collect();
+        let states = |set: &app_monitor::RunningSet| -> Vec<bool> {
+            self.dock_items
+                .iter()
+                .map(|item| match &item.match_pattern {
+                    _ if item.command.is_some() || item.is_group() => false,
+                    Some(pattern) => app_monitor::is_running_pattern(pattern, set),
+                    None => app_monitor::is_running(&item.path, set),
+                })
+                .collect()
+        };
+        self.running_states = states(&running);
+        self.hung_states = if self.config.dock.show_not_responding {
+            states(&app_monitor::hung_executables(&processes))
+        } else {
+            Vec::new()
+        };
+    }
+    
+    /// Sync running-only items with apps that currently have windows: closed
+    /// apps drop out, newly opened unpinned apps are appended.
+    /// Returns true if the list changed.
+    fn update_running_items(&mut self) -> bool {
+        if !self.config.dock.show_running {
+            return false;
+        }
+        
+        let windowed = window_focus::windowed_executables();
+        let own_exe = std::env::current_exe().ok();
+        let is_listed = |items: &[DockItem], path: &Path| items.iter().any(|item| match &item.match_pattern {
+            Some(pattern) => app_monitor::matches_pattern(pattern, path),
+            None => same_exe(&item.path, path),
+        });
+        
+        let mut running_items: Vec<DockItem> = self.running_items.iter()
+            .filter(|item| windowed.iter().any(|path| same_exe(path, &item.path)))
+            .cloned()
+            .collect();
+        for path in windowed {
+            let is_own = own_exe.as_deref().map(|own| same_exe(own, &path)).unwrap_or(false);
+            if is_own || is_listed(&self.config.items, &path) || is_listed(&running_items, &path) {
+                continue;
             }
-            "system_tray" => {
-                // Show custom tray popup at cursor
-                tray_popup::show_tray_popup_at_cursor();
+            running_items.push(DockItem::from_exe(path));
+        }
+        
+        let changed = running_items.len() != self.running_items.len()
+            || running_items.iter().zip(&self.running_items).any(|(a, b)| a.path != b.path);
+        self.running_items = running_items;
+        changed
+    }
+    
+    /// Rebuild the list of displayed items from pinned and running-only items.
+    /// Hidden pinned items are skipped, and with `tidy_separators` so are
+    /// surplus separators; `pinned_indices` maps what's left back to
+    /// `config.items`.
+    fn refresh_dock_items(&mut self) {
+        self.pinned_indices = pinned_indices(&self.config);
+        self.dock_items = self.pinned_indices.iter()
+            .map(|&i| &self.config.items[i])
+            .chain(&self.running_items)
+            .cloned()
+            .collect();
+        for item in &mut self.dock_items {
+            if let Some(badge) = self.badges.get(&item.name.to_lowercase()) {
+                item.badge = Some(badge.clone());
             }
-            "quick_settings" => {
-                // Open Windows 11 Quick Settings with Win+A
-                let script = r#"$sig = '[DllImport("user32.dll")] public static extern void keybd_event(byte bVk, byte bScan, uint dwFlags, int dwExtraInfo);'; $kb = Add-Type -MemberDefinition $sig -Name KB -PassThru; $kb::keybd_event(0x5B,0,0,0); $kb::keybd_event(0x41,0,0,0); $kb::keybd_event(0x41,0,2,0); $kb::keybd_event(0x5B,0,2,0)"#;
-                let _ = Command::new("powershell")
-                    .args(["-NoProfile", "-WindowStyle", "Hidden", "-Command", script])
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+        self.running_targets = app_monitor::target_names(&self.dock_items);
+    }
+
+    /// `config.items` index of the pinned item at `dock_index`, unless it
+    /// comes from an included file (those are edited in their own file)
+    fn config_index(&self, dock_index: usize) -> Option<usize> {
+        self.pinned_indices.get(dock_index).copied()
+            .filter(|&i| self.config.items[i].included_from.is_none())
+    }
+    
+    /// `config.items` index that puts a new item in the dock's gap number
+    /// `gap`, counting only shown pinned items
+    fn insert_position(&self, gap: usize) -> usize {
+        self.pinned_indices.get(gap).copied().unwrap_or(self.config.items.len())
+    }
+    
+    /// Launch the item at `index`; with `new_instance` a running app is
+    /// started again instead of focused
+    fn launch_item(&mut self, index: usize, new_instance: bool) {
+        let Some(item) = self.dock_items.get(index).cloned() else { return };
+        if item.special.as_deref() == Some("recent_apps") {
+            self.show_recent_apps();
+            return;
+        }
+        if item.is_group() {
+            self.show_group(&item);
+            return;
+        }
+        
+        // Swallow repeat clicks while a freshly started app is still starting
+        let cooldown = Duration::from_millis(self.config.dock.launch_cooldown_ms);
+        let key = recent_apps::launch_key(&item);
+        if let Some(launched) = key.as_ref().and_then(|key| self.launch_cooldowns.get(key)) {
+            if launched.elapsed() < cooldown {
+                return;
             }
-            "file_explorer" => {
-                let _ = Command::new("explorer")
-                    .arg(",")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+        if self.launch(&item, new_instance) && item.special.is_none() {
+            if let Some(key) = key {
+                self.launch_cooldowns.retain(|_, launched| launched.elapsed() < cooldown);
+                self.launch_cooldowns.insert(key, Instant::now());
             }
-            "control_panel" => {
-                let _ = Command::new("control")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+    }
+    
+    /// A left click on the item at `index`, routed through `modifier_clicks`
+    /// while Ctrl, Alt or Shift is held
+    fn click_item(&mut self, index: usize, event_loop: &ActiveEventLoop) {
+        use config::ClickAction;
+        use context_menu::ContextMenuAction;
+        
+        let clicks = &self.config.dock.modifier_clicks;
+        let action = if self.modifiers.control_key() {
+            clicks.ctrl
+        } else if self.modifiers.alt_key() {
+            clicks.alt
+        } else if self.modifiers.shift_key() {
+            clicks.shift
+        } else {
+            ClickAction::Launch
+        };
+        
+        // Items the action doesn't apply to just launch, like the context
+        // menu leaving those entries out
+        let Some(item) = self.dock_items.get(index) else { return };
+        let has_location = item.special.is_none() && item.command.is_none() && !item.path.as_os_str().is_empty();
+        let editable = !self.config.dock.locked && self.config_index(index).is_some();
+        match action {
+            ClickAction::NewInstance => self.launch_item(index, true),
+            ClickAction::OpenLocation if has_location => {
+                self.handle_menu_action(ContextMenuAction::OpenLocation(index), event_loop);
             }
-            "run_dialog" => {
-                // Open Run dialog
-                let _ = Command::new("rundll32")
-                    .args(["shell32.dll,#61"])
-                    .creation_flags(0x08000000)
-                    .spawn();
+            ClickAction::Edit if editable => {
+                self.handle_menu_action(ContextMenuAction::EditItem(index), event_loop);
             }
-            "this_pc" | "my_computer" => {
-                let _ = Command::new("explorer")
-                    .arg("shell:MyComputerFolder")
-                    .creation_flags(0x08000000)
-                    .spawn();
+            _ => self.launch_item(index, false),
+        }
+    }
+    
+    /// Launch (or focus, unless `new_instance`) `item`. Returns true if a new
+    /// process was started.
+    fn launch(&mut self, item: &DockItem, new_instance: bool) -> bool {
+        if let Some(prompt) = &item.confirm {
+            if !self.ask_yes_no(&item.name, prompt) {
+                return false;
             }
-            "documents" => {
-                let _ = Command::new("explorer")
-                    .arg("shell:Personal")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+        
+        // Handle special system items
+        if let Some(special) = &item.special {
+            if let Err(e) = self.launch_special(special) {
+                self.report_launch_error(special, &e);
+                return false;
             }
-            "downloads" => {
-                let _ = Command::new("explorer")
-                    .arg("shell:Downloads")
-                    .creation_flags(0x08000000)
-                    .spawn();
+            self.record_launch(item);
+            return true;
+        }
+        
+        // Command line items
+        if let Some(command) = &item.command {
+            if let Err(e) = command_line_process(command).spawn() {
+                self.report_launch_error(command, &e);
+                return false;
             }
-            "network" => {
-                let _ = Command::new("explorer")
-                    .arg("shell:NetworkPlacesFolder")
-                    .creation_flags(0x08000000)
-                    .spawn();
+            self.record_launch(item);
+            return true;
+        }
+        
+        // Regular app launch
+        if item.path.as_os_str().is_empty() {
+            return false;
+        }
+        
+        // Catch broken paths up front with a clearer message than spawn's.
+        // Relative names like "notepad.exe" are left to the PATH search.
+        if item.path.is_absolute() && !item.path.exists() {
+            let e = std::io::Error::new(std::io::ErrorKind::NotFound, "The file does not exist");
+            self.report_launch_error(&item.path.display().to_string(), &e);
+            return false;
+        }
+        
+        // Try to focus existing window first
+        let last_focused = self.focused_windows.get(&item.path).copied();
+        if !new_instance {
+            if let Some(hwnd) = window_focus::focus_existing_window(&item.path, last_focused) {
+                self.focused_windows.insert(item.path.clone(), hwnd);
+                self.record_launch(item);
+                return false;
             }
-            "user_folder" | "home" => {
-                let _ = Command::new("explorer")
-                    .arg("shell:UsersFilesFolder")
-                    .creation_flags(0x08000000)
-                    .spawn();
+        }
+        
+        // No existing window found, launch new instance; packaged apps are
+        // started by the shell from their apps-folder path
+        let mut cmd = if window_focus::apps_folder_id(&item.path).is_some() {
+            let mut cmd = Command::new("explorer.exe");
+            cmd.arg(&item.path);
+            cmd
+        } else {
+            Command::new(&item.path)
+        };
+        if !item.args.is_empty() {
+            cmd.args(&item.args);
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        }
+        match cmd.spawn() {
+            Ok(_) => {
+                self.record_launch(item);
+                true
+            }
+            Err(e) => {
+                self.report_launch_error(&item.path.display().to_string(), &e);
+                false
+            }
+        }
+    }
+    
+    /// Ask Yes/No before launching or removing an item. Owned by the dock and
+    /// forced topmost so the always-on-top dock can't cover it.
+    fn ask_yes_no(&self, title: &str, prompt: &str) -> bool {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            MessageBoxW, SetForegroundWindow, IDYES, MB_ICONQUESTION, MB_SETFOREGROUND, MB_TOPMOST, MB_YESNO,
+        };
+        
+        let owner = match self.screen.window.as_ref().map(|w| w.window_handle().map(|h| h.as_raw())) {
+            Some(Ok(RawWindowHandle::Win32(h))) => HWND(h.hwnd.get() as *mut _),
+            _ => HWND::default(),
+        };
+        let text: Vec<u16> = prompt.encode_utf16().chain(std::iter::once(0)).collect();
+        let title: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+        unsafe {
+            let _ = SetForegroundWindow(owner);
+            MessageBoxW(
+                owner,
+                PCWSTR(text.as_ptr()),
+                PCWSTR(title.as_ptr()),
+                MB_YESNO | MB_ICONQUESTION | MB_TOPMOST | MB_SETFOREGROUND,
+            ) == IDYES
+        }
+    }
+    
+    /// Add `item` to the recent apps and its launch count, and save both
+    fn record_launch(&mut self, item: &DockItem) {
+        let recorded = self.recent_apps.record(item);
+        if self.recent_apps.count_launch(item) || recorded {
+            if let Err(e) = self.recent_apps.save(&self.recent_apps_path) {
+                log::error!("Failed to save recent apps: {}", e);
+            }
+        }
+    }
+    
+    /// Show the hovered item's tooltip above the cursor, or hide it. Holding
+    /// Shift adds how often and how recently the item was launched. Items whose
+    /// icon failed to load say why.
+    fn update_tooltip(&mut self) {
+        let stats = shift_held();
+        self.screen.tooltip_stats = stats;
+        let Some(tooltip) = &mut self.screen.tooltip else { return };
+        let item = self.screen.hovered_item.and_then(|idx| self.dock_items.get(idx));
+        let Some(item) = item.filter(|item| !item.is_separator() && !item.tooltip_text().is_empty()) else {
+            tooltip.hide();
+            return;
+        };
+        let Some(window) = &self.screen.window else { return };
+        
+        let mut text = if stats && !item.is_group() {
+            format!("{} \u{00b7} {}", item.tooltip_text(), self.recent_apps.launch_summary(item))
+        } else {
+            item.tooltip_text().to_string()
+        };
+        if let Some(error) = self.screen.renderer.as_ref().and_then(|renderer| renderer.icon_error(item)) {
+            text.push_str(&format!(" (icon failed to load: {})", error));
+        }
+        // Get screen position for tooltip
+        let win_pos = window.outer_position().unwrap_or_default();
+        tooltip.show(&text, win_pos.x + self.screen.cursor_x as i32, win_pos.y);
+    }
+    
+    /// Whether the last cursor position is inside the dock window
+    fn cursor_over_dock(&self) -> bool {
+        let Some(renderer) = &self.screen.renderer else { return false };
+        let (x, y) = (self.screen.cursor_x, self.screen.cursor_y);
+        x >= 0.0 && y >= 0.0 && x < renderer.width as f32 && y < renderer.height as f32
+    }
+    
+    /// The cursor left the dock window: drop hover and any press or drag,
+    /// and start hiding
+    fn cursor_left(&mut self) {
+        self.screen.cursor_in_window = false;
+        self.screen.cursor_x = -1000.0;
+        self.screen.cursor_y = -1000.0;
+        self.screen.hovered_item = None;
+        // Cancel any drag in progress
+        self.screen.dragging = false;
+        self.screen.drag_start_idx = None;
+        // Only start hide timer if dock is visible (prevents race conditions)
+        if (self.screen.dock_y_current - self.screen.dock_y_visible).abs() < 5.0 {
+            self.start_hide();
+        }
+        // Hide tooltip
+        if let Some(tooltip) = &mut self.screen.tooltip {
+            tooltip.hide();
+        }
+    }
+    
+    /// Whether the cursor is more than an icon's height above the dock, where
+    /// `drag_to_remove` drops remove the item
+    fn is_dropped_off_dock(&self) -> bool {
+        let icon_size = self.screen.renderer.as_ref().map(|r| r.icon_size).unwrap_or(48);
+        self.config.dock.drag_to_remove && self.screen.cursor_y < -(icon_size as f32)
+    }
+    
+    /// Remove a pinned item dropped off the dock, once the user confirms
+    fn remove_dragged_item(&mut self, index: usize) {
+        let name = self.config.items[index].name.clone();
+        let prompt = if self.config.items[index].is_separator() {
+            "Remove this separator from the dock?".to_string()
+        } else {
+            format!("Remove {} from the dock?", name)
+        };
+        if !self.ask_yes_no(&name, &prompt) {
+            return;
+        }
+        let before = self.config.clone();
+        self.config.items.remove(index);
+        self.undo_config = Some(before);
+        self.save_config();
+        self.needs_reload = true;
+    }
+    
+    /// Reply to commands sent over the pipe by `rdock --status`
+    fn answer_ipc_requests(&mut self) {
+        let Some(ipc) = &self.ipc else { return };
+        for request in ipc.take_requests() {
+            let reply = match request.command.as_str() {
+                "status" => ipc::status_json(&self.dock_items, &self.running_states),
+                command if command.starts_with("badge ") => self.set_badge(&command["badge ".len()..]),
+                other => format!("error: unknown command {:?}\n", other),
+            };
+            let _ = request.reply.send(reply);
+        }
+    }
+    
+    /// `badge <item> [text]` from `--badge`: show `text` on the item named
+    /// `item`, or go back to its config badge when `text` is empty or missing
+    fn set_badge(&mut self, args: &str) -> String {
+        let (name, text) = match shell_words::split(args).as_deref() {
+            Ok([name]) => (name.clone(), String::new()),
+            Ok([name, text]) => (name.clone(), text.clone()),
+            _ => return "error: usage: badge <item> [text]\n".to_string(),
+        };
+        let key = name.to_lowercase();
+        if !self.dock_items.iter().any(|item| item.name.to_lowercase() == key) {
+            return format!("error: no item named {:?}\n", name);
+        }
+        if text.is_empty() {
+            self.badges.remove(&key);
+        } else {
+            self.badges.insert(key, text);
+        }
+        self.refresh_dock_items();
+        self.request_redraws();
+        "ok\n".to_string()
+    }
+    
+    /// Carry out a `button_bindings` action; the names are listed in the README
+    fn run_button_action(&mut self, action: &str, event_loop: &ActiveEventLoop) {
+        match action {
+            "toggle_dock" => self.toggle_dock_visibility(),
+            "toggle_lock" => self.handle_menu_action(context_menu::ContextMenuAction::ToggleLock, event_loop),
+            "toggle_auto_hide" => self.toggle_auto_hide(),
+            "toggle_compact" => self.toggle_compact(),
+            "presentation_mode" => self.toggle_presentation_mode(),
+            "reload_config" => self.needs_reload = true,
+            "open_settings" => self.handle_menu_action(context_menu::ContextMenuAction::OpenSettings, event_loop),
+            "recent_apps" => self.show_recent_apps(),
+            _ => log::warn!("Unknown button_bindings action {:?}", action),
+        }
+    }
+    
+    /// Pop up a group's flyout at the cursor and launch the chosen member
+    fn show_group(&mut self, group: &DockItem) {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        
+        let Some(window) = &self.screen.window else { return };
+        let hwnd = match window.window_handle().map(|h| h.as_raw()) {
+            Ok(RawWindowHandle::Win32(h)) => h.hwnd.get(),
+            _ => return,
+        };
+        
+        let mut point = windows::Win32::Foundation::POINT::default();
+        unsafe {
+            let _ = windows::Win32::UI::WindowsAndMessaging::GetCursorPos(&mut point);
+        }
+        
+        // Nested groups would need a flyout of their own, so they're left out
+        let members: Vec<&DockItem> = group.children.iter()
+            .filter(|child| !child.is_separator() && !child.is_group())
+            .collect();
+        let entries: Vec<(&str, &Path)> = members.iter()
+            .map(|child| (child.name.as_str(), child.icon.as_deref().unwrap_or(&child.path)))
+            .collect();
+        if let Some(index) = recent_apps::show_app_popup(hwnd, point.x, point.y, &entries, "Empty group") {
+            let _ = self.launch(members[index], false);
+        }
+    }
+    
+    /// Pop up the launch history at the cursor and launch the chosen app
+    fn show_recent_apps(&mut self) {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        
+        let Some(window) = &self.screen.window else { return };
+        let hwnd = match window.window_handle().map(|h| h.as_raw()) {
+            Ok(RawWindowHandle::Win32(h)) => h.hwnd.get(),
+            _ => return,
+        };
+        
+        let mut point = windows::Win32::Foundation::POINT::default();
+        unsafe {
+            let _ = windows::Win32::UI::WindowsAndMessaging::GetCursorPos(&mut point);
+        }
+        
+        let entries = self.recent_apps.entries();
+        if let Some(index) = recent_apps::show_recent_popup(hwnd, point.x, point.y, entries) {
+            let item = entries[index].to_item();
+            let _ = self.launch(&item, false);
+        }
+    }
+    
+    /// Log a failed launch and, if enabled, tell the user about it.
+    fn report_launch_error(&self, target: &str, err: &std::io::Error) {
+        log::error!("Failed to launch {}: {}", target, err);
+        if self.config.dock.notify_on_error {
+            show_error_message(&format!("Couldn't launch:\n{}\n\n{}", target, err));
+        }
+    }
+    
+    /// Launch a special system item. Errors carry enough context to show the user.
+    #[cfg(windows)]
+    fn launch_special(&mut self, special: &str) -> std::io::Result<()> {
+        use special::SpecialAction;
+        use std::os::windows::process::CommandExt;
+        
+        match special::special_action(special)? {
+            SpecialAction::Run { program, args } => {
+                Command::new(program)
+                    .args(args)
+                    .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                    .spawn()
+                    .map(|_| ())
+            }
+            SpecialAction::TrayPopup => {
+                tray_popup::show_tray_popup_at_cursor();
+                Ok(())
+            }
+            SpecialAction::ToggleDesktop => {
+                // Restore what the last click minimized, unless the user
+                // already brought all of it back
+                let minimized = std::mem::take(&mut self.desktop_minimized);
+                if window_focus::restore_windows(&minimized) == 0 {
+                    self.desktop_minimized = window_focus::minimize_all_windows();
+                }
+                Ok(())
+            }
+            SpecialAction::RecentApps => {
+                self.show_recent_apps();
+                Ok(())
             }
-            _ => {
-                eprintln!("Unknown special item: {}", special);
+            SpecialAction::AppLauncher => {
+                if let Some(path) = app_launcher::show_app_launcher() {
+                    self.launch(&shortcut::item_for_file(&path), false);
+                }
+                Ok(())
             }
         }
     }
     
     #[cfg(not(windows))]
-    fn launch_special(&self, special: &str) {
-        eprintln!("Special items not supported on this platform: {}", special);
+    fn launch_special(&mut self, special: &str) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!("Special items not supported on this platform: {}", special),
+        ))
     }
     
     /// Empty the Windows recycle bin
@@ -648,89 +1747,124 @@ impl DockApp {
     
     #[cfg(not(windows))]
     fn empty_recycle_bin(&self) {
-        eprintln!("Empty recycle bin not supported on this platform");
+        log::error!("Empty recycle bin not supported on this platform");
     }
 
     fn update_animations(&mut self) -> bool {
         // Compute frame delta time for frame-rate-independent animation.
         // Clamp to 100ms so a stall doesn't cause a huge jump.
         let now = Instant::now();
-        let delta = now.duration_since(self.last_frame_time).as_secs_f32().min(0.1);
-        self.last_frame_time = now;
+        let delta = match self.screen.last_frame_time {
+            Some(last) => now.duration_since(last).as_secs_f32().min(0.1),
+            None => self.screen.frame_time.as_secs_f32(),
+        };
+        self.screen.last_frame_time = Some(now);
         
-        // Delta-time-compensated lerp alpha:
-        //   base_alpha is tuned for 60 fps (16.67ms); scale it to actual delta so
-        //   animation speed is identical regardless of frame rate.
-        const TARGET_DT: f32 = 1.0 / 60.0;
-        let dock_alpha  = 1.0 - (1.0_f32 - 0.15).powf(delta / TARGET_DT);
-        let icon_alpha  = 1.0 - (1.0_f32 - 0.30).powf(delta / TARGET_DT);
+        let (dock_alpha, icon_alpha) = if self.system_animations {
+            (ease_factor(DOCK_EASE_RATE, delta), ease_factor(ICON_EASE_RATE, delta))
+        } else {
+            (1.0, 1.0)
+        };
         
         let mut animating = false;
         
+        if self.screen.intro_start.is_some() {
+            self.advance_intro();
+            animating = true;
+        }
+        
         // Smooth dock Y position
-        let dy = self.dock_y_target - self.dock_y_current;
-        if dy.abs() > 0.5 {
-            self.dock_y_current += dy * dock_alpha;
-            if let Some(window) = &self.window {
-                let x = ((self.screen_width as f32 - self.renderer.as_ref().unwrap().width as f32) / 2.0) as i32;
-                window.set_outer_position(PhysicalPosition::new(x, self.dock_y_current as i32));
+        let dy = self.screen.dock_y_target - self.screen.dock_y_current;
+        if self.screen.intro_start.is_none() && dy.abs() > 0.5 {
+            self.screen.dock_y_current += dy * dock_alpha;
+            self.apply_dock_y(self.dock_x());
+            animating = true;
+        }
+
+        if self.icons_playing() {
+            if let Some(renderer) = &mut self.screen.renderer {
+                animating |= renderer.advance_animations(Duration::from_secs_f32(delta));
+            }
+        }
+        
+        // Reveal pulse: up and back down along half a sine wave
+        if let Some(start) = self.screen.pulse_start {
+            let t = start.elapsed().as_secs_f32() / REVEAL_PULSE_DURATION.as_secs_f32();
+            let pulse = if t < 1.0 { (t * std::f32::consts::PI).sin() } else { 0.0 };
+            if t >= 1.0 {
+                self.screen.pulse_start = None;
+            }
+            if let Some(renderer) = &mut self.screen.renderer {
+                renderer.pulse = pulse;
             }
             animating = true;
         }
 
         // Smooth wave magnification based on cursor distance (like macOS Dock)
-        if let Some(renderer) = &self.renderer {
+        if let Some(renderer) = &self.screen.renderer {
             let icon_size = renderer.icon_size as f32;
-            let spacing_x = renderer.spacing.x as f32;
-            let padding_left = renderer.padding.left as f32;
+            let centers = renderer.rest_centers(&self.dock_items);
             
             // Wider range for wave effect - affects more neighbors
             let mag_range = icon_size * 3.5; 
             let max_scale = self.config.dock.magnification;
+            let mode = self.config.dock.magnification_mode;
+            let hovering = self.screen.cursor_in_window && self.screen.cursor_x >= 0.0 && !self.screen.dragging;
             
-            for i in 0..self.icon_scales.len() {
-                // Calculate icon center X position
-                let icon_center_x = padding_left + (i as f32 * (icon_size + spacing_x)) + icon_size / 2.0;
+            for i in 0..self.screen.icon_scales.len() {
+                // Separators and scrolled-past items have no center and never grow
+                let center = centers.get(i).copied().flatten();
                 
-                let target = if self.cursor_in_window && self.cursor_x >= 0.0 && !self.dragging {
-                    // Distance from cursor to icon center
-                    let dist = (self.cursor_x - icon_center_x).abs();
-                    
-                    if dist < mag_range {
-                        // Smoother wave using cosine function for natural falloff
-                        let t = dist / mag_range;
-                        // Cosine curve gives a nice smooth wave effect
-                        let falloff = (1.0 + (t * std::f32::consts::PI).cos()) / 2.0;
-                        1.0 + (max_scale - 1.0) * falloff
+                let target = if !hovering || mode == MagnificationMode::None {
+                    1.0
+                } else if let Some(icon_center_x) = center {
+                    if mode == MagnificationMode::Single {
+                        if self.screen.hovered_item == Some(i) { max_scale } else { 1.0 }
                     } else {
-                        1.0
+                        // Distance from cursor to icon center
+                        let dist = (self.screen.cursor_x - icon_center_x).abs();
+                        
+                        if dist < mag_range {
+                            // Smoother wave using cosine function for natural falloff
+                            let t = dist / mag_range;
+                            // Cosine curve gives a nice smooth wave effect
+                            let falloff = (1.0 + (t * std::f32::consts::PI).cos()) / 2.0;
+                            1.0 + (max_scale - 1.0) * falloff
+                        } else {
+                            1.0
+                        }
                     }
                 } else {
                     1.0
                 };
                 
-                let d = target - self.icon_scales[i];
+                let d = target - self.screen.icon_scales[i];
                 if d.abs() > 0.001 {
-                    self.icon_scales[i] += d * icon_alpha;
+                    self.screen.icon_scales[i] += d * icon_alpha;
                     animating = true;
                 } else {
-                    self.icon_scales[i] = target;
+                    self.screen.icon_scales[i] = target;
                 }
             }
         }
         
+        if !animating {
+            self.screen.last_frame_time = None;
+        }
         animating
     }
 
     fn check_hide(&mut self) {
-        if !self.config.dock.auto_hide {
+        if !self.config.dock.auto_hide || self.presentation_mode {
             return;
         }
         let hide_delay = Duration::from_millis(self.config.dock.auto_hide_delay_ms);
-        if let Some(t) = self.hide_timer {
+        if let Some(t) = self.screen.hide_timer {
             if t.elapsed() >= hide_delay {
-                self.dock_y_target = self.dock_y_hidden;
-                self.hide_timer = None;
+                self.screen.dock_y_target = self.screen.dock_y_hidden;
+                self.screen.hide_timer = None;
+                self.set_click_through(true);
+                self.update_tray_icon();
             }
         }
     }
@@ -740,7 +1874,7 @@ impl DockApp {
             return;
         }
         let show_delay = Duration::from_millis(self.config.dock.auto_show_delay_ms);
-        if let Some(t) = self.show_timer {
+        if let Some(t) = self.screen.show_timer {
             if t.elapsed() >= show_delay {
                 self.show_dock();
             }
@@ -748,32 +1882,100 @@ impl DockApp {
     }
     
     fn check_taskbar_visibility(&mut self) {
-        // Only check if we're configured to hide taskbar
-        if !self.config.dock.hide_windows_taskbar {
+        // Only check if we're configured to hide taskbar (auto-hide needs no upkeep)
+        if self.config.dock.taskbar_mode() != TaskbarMode::Hide || self.presentation_mode {
             return;
         }
         
         // Check periodically and re-hide if needed
-        if self.last_taskbar_check.elapsed() < TASKBAR_CHECK_INTERVAL {
+        if self.screen.last_taskbar_check.elapsed() < self.config.dock.intervals.taskbar_check() {
             return;
         }
-        self.last_taskbar_check = Instant::now();
+        self.screen.last_taskbar_check = Instant::now();
         
         // Aggressively re-hide taskbar in case Windows restored it
-        if self.taskbar_hidden {
-            set_taskbar_visibility(false);
+        if self.screen.taskbar_hidden {
+            hide_taskbars(self.hwnd(), &mut self.screen.hidden_taskbars);
+        }
+    }
+    
+    /// Where `renderer`'s dock goes on the current screen; startup, reloads
+    /// and screen changes all place the window with this
+    fn compute_layout(&self, renderer: &Renderer) -> config::Layout {
+        let layout = self.config.dock.layout(self.screen.width, self.screen.height, renderer.width, renderer.height);
+        // `layout` works from the monitor's top-left corner
+        config::Layout {
+            x: self.screen.left + layout.x,
+            y_visible: self.screen.top + layout.y_visible,
+            y_hidden: self.screen.top + layout.y_hidden,
         }
     }
     
+    /// Left edge of the dock window for the current screen and renderer width
+    fn dock_x(&self) -> i32 {
+        self.screen.renderer.as_ref().map_or(0, |renderer| self.compute_layout(renderer).x)
+    }
+    
+    /// Step the startup intro: ease up from `INTRO_RISE_PX` below the visible
+    /// position while the window alpha goes from 0 to opaque. Ends early if the
+    /// dock is sent somewhere else meanwhile, leaving the usual easing to
+    /// take over from where the intro got to.
+    fn advance_intro(&mut self) {
+        let Some(start) = self.screen.intro_start else { return };
+        let x = self.dock_x();
+        
+        let t = (start.elapsed().as_secs_f32() / INTRO_DURATION.as_secs_f32()).min(1.0);
+        if t >= 1.0 || self.screen.dock_y_target != self.screen.dock_y_visible {
+            self.screen.intro_start = None;
+            if t >= 1.0 {
+                self.screen.dock_y_current = self.screen.dock_y_visible;
+            }
+            self.apply_dock_y(x);
+            return;
+        }
+        // Ease out (cubic) so it settles gently
+        let eased = 1.0 - (1.0 - t).powi(3);
+        self.screen.dock_y_current = self.screen.dock_y_visible + INTRO_RISE_PX * (1.0 - eased);
+        let Some(window) = &self.screen.window else { return };
+        window.set_outer_position(PhysicalPosition::new(x, self.screen.dock_y_current as i32));
+        set_window_fade(window, Some((eased * 255.0).round() as u8));
+    }
+    
     #[cfg(windows)]
+    /// Move the window to reflect `dock_y_current`. With the fade hide style the
+    /// window stays at its visible position and the same hide progress drives
+    /// the window alpha instead; a fully faded dock lets clicks through.
+    fn apply_dock_y(&self, x: i32) {
+        let Some(window) = &self.screen.window else { return };
+        
+        match self.config.dock.hide_style {
+            HideStyle::Slide => {
+                window.set_outer_position(PhysicalPosition::new(x, self.screen.dock_y_current as i32));
+                set_window_fade(window, None);
+            }
+            HideStyle::Fade => {
+                window.set_outer_position(PhysicalPosition::new(x, self.screen.dock_y_visible as i32));
+                let range = self.screen.dock_y_hidden - self.screen.dock_y_visible;
+                let progress = if range > 0.0 {
+                    ((self.screen.dock_y_current - self.screen.dock_y_visible) / range).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                set_window_fade(window, Some(((1.0 - progress) * 255.0).round() as u8));
+            }
+        }
+    }
+    
     fn ensure_topmost(&mut self) {
-        if self.last_zorder_reassert.elapsed() < ZORDER_REASSERT_INTERVAL {
+        if !self.config.dock.always_on_top
+            || self.screen.last_zorder_reassert.elapsed() < ZORDER_REASSERT_INTERVAL
+        {
             return;
         }
-        self.last_zorder_reassert = Instant::now();
+        self.screen.last_zorder_reassert = Instant::now();
         
         // Only reassert when dock is fully settled at its visible position (not animating)
-        if (self.dock_y_current - self.dock_y_visible).abs() > 5.0 {
+        if (self.screen.dock_y_current - self.screen.dock_y_visible).abs() > 5.0 {
             return;
         }
         
@@ -782,7 +1984,7 @@ impl DockApp {
             SetWindowPos, HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE, SWP_NOACTIVATE, SWP_NOSENDCHANGING,
         };
         
-        let Some(window) = &self.window else { return };
+        let Some(window) = &self.screen.window else { return };
         let hwnd = match window.window_handle().map(|h| h.as_raw()) {
             Ok(RawWindowHandle::Win32(h)) => {
                 windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _)
@@ -800,24 +2002,83 @@ impl DockApp {
         }
     }
     
-    fn check_fullscreen(&mut self) {
-        if !self.config.dock.hide_in_fullscreen {
+    fn check_fullscreen(&mut self) {
+        // Presentation mode already keeps the dock hidden
+        let watch_focus = !self.config.dock.hide_when_focused.is_empty() || self.screen.focus_hidden
+            || self.config.dock.stay_visible_when_app_active || self.screen.app_active;
+        if !(self.config.dock.hide_in_fullscreen || watch_focus) || self.presentation_mode {
+            return;
+        }
+        
+        if self.screen.last_fullscreen_check.elapsed() < self.config.dock.intervals.fullscreen_check() {
+            return;
+        }
+        self.screen.last_fullscreen_check = Instant::now();
+        
+        if watch_focus {
+            self.check_focused_app();
+        }
+        if !self.config.dock.hide_in_fullscreen {
+            return;
+        }
+        
+        let was_fullscreen = self.screen.fullscreen_active;
+        self.screen.fullscreen_active = is_fullscreen_app_active();
+        
+        // If fullscreen state changed, update dock visibility
+        if self.screen.fullscreen_active && !was_fullscreen {
+            // Entering fullscreen - force hide
+            self.screen.dock_y_target = self.screen.dock_y_hidden;
+            self.screen.hide_timer = None;
+            self.set_click_through(true);
+            self.update_tray_icon();
+        }
+    }
+    
+    /// Hide the dock while a `hide_when_focused` app is in the foreground, and
+    /// bring it back afterwards unless auto-hide will. rDock's own windows
+    /// never count, so opening a dialog from the dock leaves it alone.
+    fn check_focused_app(&mut self) {
+        let exe = window_focus::foreground_executable();
+        self.update_app_active(exe.as_deref());
+        let focused = exe.is_some_and(|exe| self.config.dock.hides_for(&exe));
+        if focused == self.screen.focus_hidden {
             return;
         }
-        
-        if self.last_fullscreen_check.elapsed() < FULLSCREEN_CHECK_INTERVAL {
+        self.screen.focus_hidden = focused;
+        if focused {
+            self.screen.dock_y_target = self.screen.dock_y_hidden;
+            self.screen.hide_timer = None;
+            self.screen.show_timer = None;
+            self.set_click_through(true);
+            self.update_tray_icon();
+        } else if !self.config.dock.auto_hide {
+            self.show_dock();
+        }
+    }
+    
+    /// With `stay_visible_when_app_active`, hold off auto-hide while the
+    /// foreground app is one of the dock's items, and start the usual hide
+    /// delay once it no longer is. Fullscreen hides the dock regardless.
+    fn update_app_active(&mut self, exe: Option<&Path>) {
+        let active = self.config.dock.stay_visible_when_app_active
+            && exe.is_some_and(|exe| self.dock_items.iter().any(|item| {
+                if item.is_separator() || item.special.is_some() || item.command.is_some() {
+                    return false;
+                }
+                match &item.match_pattern {
+                    Some(pattern) => app_monitor::matches_pattern(pattern, exe),
+                    None => same_exe(&item.path, exe),
+                }
+            }));
+        if active == self.screen.app_active {
             return;
         }
-        self.last_fullscreen_check = Instant::now();
-        
-        let was_fullscreen = self.fullscreen_active;
-        self.fullscreen_active = is_fullscreen_app_active();
-        
-        // If fullscreen state changed, update dock visibility
-        if self.fullscreen_active && !was_fullscreen {
-            // Entering fullscreen - force hide
-            self.dock_y_target = self.dock_y_hidden;
-            self.hide_timer = None;
+        self.screen.app_active = active;
+        if active {
+            self.screen.hide_timer = None;
+        } else if !self.screen.cursor_in_window && (self.screen.dock_y_current - self.screen.dock_y_visible).abs() < 5.0 {
+            self.start_hide();
         }
     }
     
@@ -826,18 +2087,18 @@ impl DockApp {
             return;
         }
         
-        if self.last_mouse_poll.elapsed() < MOUSE_POLL_INTERVAL {
+        if self.screen.last_mouse_poll.elapsed() < self.config.dock.intervals.mouse_poll() {
             return;
         }
-        self.last_mouse_poll = Instant::now();
+        self.screen.last_mouse_poll = Instant::now();
         
-        // When a fullscreen app is active we never want to show or move the
-        // dock, but we DO still need to clear `cursor_in_window` if it got
-        // latched true, otherwise callers relying on that flag keep thinking
-        // the cursor is over the dock forever.
-        if self.fullscreen_active {
-            if self.cursor_in_window {
-                self.cursor_in_window = false;
+        // When a fullscreen app is active (or presentation mode is on) we never
+        // want to show or move the dock, but we DO still need to clear
+        // `cursor_in_window` if it got latched true, otherwise callers relying
+        // on that flag keep thinking the cursor is over the dock forever.
+        if self.screen.fullscreen_active || self.screen.focus_hidden || self.presentation_mode {
+            if self.screen.cursor_in_window {
+                self.screen.cursor_in_window = false;
             }
             return;
         }
@@ -849,73 +2110,156 @@ impl DockApp {
             
             let mut point = POINT { x: 0, y: 0 };
             if GetCursorPos(&mut point).is_ok() {
-                let trigger_distance = 2;
-                let at_bottom_edge = point.y as u32 >= self.screen_height - trigger_distance;
+                // With docks on several monitors each one only reveals from
+                // its own monitor's edge
+                let on_screen = self.other_screens.is_empty() || self.screen.contains(point.x, point.y);
+                let in_trigger_zone = on_screen && in_reveal_zone(
+                    point.x - self.screen.left,
+                    point.y - self.screen.top,
+                    self.screen.width,
+                    self.screen.height,
+                    self.config.dock.trigger_distance(),
+                    self.config.dock.hot_corner,
+                );
                 
-                // Check if cursor is within the dock window bounds
-                let in_dock = if let (Some(window), Some(renderer)) = (&self.window, &self.renderer) {
+                // Check if cursor is within the dock. While the dock is
+                // showing, count the whole area it is animating into, so the
+                // cursor isn't "outside" just because the window hasn't
+                // caught up yet.
+                let dock_shown = self.screen.dock_y_target == self.screen.dock_y_visible;
+                let in_dock = if let (Some(window), Some(renderer)) = (&self.screen.window, &self.screen.renderer) {
                     let pos = window.outer_position().unwrap_or(PhysicalPosition::new(0, 0));
                     let dock_x = pos.x;
-                    let dock_y = pos.y;
                     let dock_w = renderer.width as i32;
-                    let dock_h = renderer.height as i32;
+                    let dock_top = if dock_shown { pos.y.min(self.screen.dock_y_visible as i32) } else { pos.y };
+                    let dock_bottom = if dock_shown { self.screen.top + self.screen.height as i32 } else { pos.y + renderer.height as i32 };
                     
                     point.x >= dock_x && point.x < dock_x + dock_w &&
-                    point.y >= dock_y && point.y < dock_y + dock_h
+                    point.y >= dock_top && point.y < dock_bottom
                 } else {
                     false
                 };
                 
-                if at_bottom_edge {
-                    // Cursor at bottom edge - start show timer or show immediately
-                    let show_delay = self.config.dock.auto_show_delay_ms;
-                    if show_delay == 0 {
-                        self.show_dock();
-                    } else if self.show_timer.is_none() && self.dock_y_target != self.dock_y_visible {
-                        self.show_timer = Some(Instant::now());
-                    }
-                    self.cursor_in_window = in_dock;
-                    if let Some(window) = &self.window {
-                        window.request_redraw();
-                    }
-                } else {
+                let action = auto_hide_action(
+                    in_trigger_zone,
+                    in_dock,
+                    dock_shown,
+                    self.screen.last_shown.map(|t| t.elapsed()),
+                );
+                
+                if !in_trigger_zone {
                     // Not at edge - cancel show timer
-                    self.show_timer = None;
+                    self.screen.show_timer = None;
                 }
                 
-                if !at_bottom_edge && !in_dock && self.dock_y_target == self.dock_y_visible {
-                    // Dock is visible but cursor is not in dock and not at edge - start hide timer
-                    if !self.cursor_in_window {
+                match action {
+                    AutoHideAction::Reveal => {
+                        // Cursor at bottom edge or hot corner - start show timer or show immediately
+                        let show_delay = self.config.dock.auto_show_delay_ms;
+                        if show_delay == 0 {
+                            self.show_dock();
+                        } else if self.screen.show_timer.is_none() && !dock_shown {
+                            self.screen.show_timer = Some(Instant::now());
+                        }
+                        self.screen.cursor_in_window = in_dock;
+                        if let Some(window) = &self.screen.window {
+                            window.request_redraw();
+                        }
+                    }
+                    AutoHideAction::Hide => {
+                        self.screen.cursor_in_window = false;
                         self.start_hide();
                     }
-                }
-                
-                // Update cursor_in_window based on actual position
-                if !in_dock && self.cursor_in_window {
-                    self.cursor_in_window = false;
-                    self.start_hide();
+                    AutoHideAction::Stay => {
+                        if !in_dock {
+                            self.screen.cursor_in_window = false;
+                        }
+                    }
                 }
             }
         }
     }
-
+    
     fn show_dock(&mut self) {
-        let transitioning = self.dock_y_target != self.dock_y_visible;
-        self.dock_y_target = self.dock_y_visible;
-        self.hide_timer = None;
-        self.show_timer = None;
+        if self.presentation_mode {
+            return;
+        }
+        let transitioning = self.screen.dock_y_target != self.screen.dock_y_visible;
+        self.screen.dock_y_target = self.screen.dock_y_visible;
+        self.screen.hide_timer = None;
+        self.screen.show_timer = None;
         // On hidden→visible transition, delay first reassertion by 500ms so
         // SetWindowPos doesn't fire during the animation (~280ms to complete).
         // Don't reset when called as a keep-alive (cursor moving while visible) -
         // that would prevent ensure_topmost() from ever firing while hovering.
         if transitioning {
-            self.last_zorder_reassert = Instant::now()
+            self.set_click_through(false);
+            self.update_tray_icon();
+            self.screen.last_shown = Some(Instant::now());
+            self.screen.last_zorder_reassert = Instant::now()
                 .checked_sub(ZORDER_REASSERT_INTERVAL - Duration::from_millis(500))
                 .unwrap_or_else(Instant::now);
+            if !self.config.dock.always_on_top {
+                self.raise_dock();
+            }
+            match self.config.dock.reveal_feedback {
+                // Like the other animations, skipped when Windows animations are off
+                RevealFeedback::Pulse if self.system_animations => self.screen.pulse_start = Some(Instant::now()),
+                RevealFeedback::Sound => sound::play_reveal(),
+                _ => {}
+            }
+        }
+    }
+    
+    /// Window level for `always_on_top`
+    fn window_level(&self) -> WindowLevel {
+        if self.config.dock.always_on_top {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        }
+    }
+    
+    fn apply_window_level(&self) {
+        if let Some(window) = &self.screen.window {
+            window.set_window_level(self.window_level());
+        }
+    }
+    
+    /// Bring the dock to the top of the normal Z-order without activating it,
+    /// so a revealed dock isn't left behind the window it was hidden under
+    fn raise_dock(&self) {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SetWindowPos, HWND_TOP, SWP_NOMOVE, SWP_NOSIZE, SWP_NOACTIVATE,
+        };
+        
+        let Some(window) = &self.screen.window else { return };
+        let hwnd = match window.window_handle().map(|h| h.as_raw()) {
+            Ok(RawWindowHandle::Win32(h)) => {
+                windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _)
+            }
+            _ => return,
+        };
+        
+        unsafe {
+            let _ = SetWindowPos(hwnd, HWND_TOP, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE);
+        }
+    }
+    
+    /// While hidden, let clicks fall through to the window underneath; the
+    /// reveal zone works off the cursor poll, not window input.
+    fn set_click_through(&self, click_through: bool) {
+        if let Some(window) = &self.screen.window {
+            // winit owns WS_EX_TRANSPARENT through the cursor hittest flag
+            let _ = window.set_cursor_hittest(!click_through);
         }
     }
     
     fn show_dock_at_cursor(&mut self) {
+        if self.presentation_mode {
+            return;
+        }
         // Get cursor position
         unsafe {
             use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
@@ -923,19 +2267,22 @@ impl DockApp {
             
             let mut point = POINT { x: 0, y: 0 };
             if GetCursorPos(&mut point).is_ok() {
-                // Position dock centered horizontally at cursor X, at normal bottom position
-                if let (Some(window), Some(renderer)) = (&self.window, &self.renderer) {
+                // Position dock centered horizontally at cursor X, at normal bottom
+                // position; a dock anchored to a side stays at that side
+                if let Some(renderer) = &self.screen.renderer {
                     let dock_w = renderer.width as i32;
                     
                     // Center on cursor X, clamped to screen bounds
-                    let mut x = point.x - (dock_w / 2);
-                    x = x.max(0).min((self.screen_width as i32) - dock_w);
+                    let x = if self.config.dock.anchor == config::Anchor::Center {
+                        let left = self.screen.left;
+                        (point.x - dock_w / 2).max(left).min(left + (self.screen.width as i32) - dock_w)
+                    } else {
+                        self.dock_x()
+                    };
                     
                     // Use normal visible Y position
-                    let y = self.dock_y_visible as i32;
-                    
-                    window.set_outer_position(PhysicalPosition::new(x, y));
-                    self.dock_y_current = y as f32;
+                    self.screen.dock_y_current = self.screen.dock_y_visible;
+                    self.apply_dock_y(x);
                 }
             }
         }
@@ -944,20 +2291,235 @@ impl DockApp {
         self.show_dock();
     }
 
+    /// (hidden, auto_hide) as shown by the tray icon; hidden means every dock is
+    fn tray_icon_state(&self) -> (bool, bool) {
+        let hidden = self.screens().all(|screen| (screen.dock_y_target - screen.dock_y_visible).abs() > 0.5);
+        (hidden, self.config.dock.auto_hide)
+    }
+    
+    /// Redraw the tray icon if the dock was shown, hidden, or auto-hide toggled
+    fn update_tray_icon(&mut self) {
+        let state = self.tray_icon_state();
+        if self.tray_state == Some(state) {
+            return;
+        }
+        let Some(tray) = &self.tray else { return };
+        if let Ok(icon) = create_tray_icon(&self.config.dock.indicator_color, state.0, state.1) {
+            let _ = tray.set_icon(Some(icon));
+            self.tray_state = Some(state);
+        }
+    }
+    
+    /// Tray "Show/Hide Dock": reveal hidden docks, otherwise slide them away
+    fn toggle_dock_visibility(&mut self) {
+        if self.presentation_mode {
+            self.toggle_presentation_mode();
+        } else if self.tray_icon_state().0 {
+            self.for_each_screen(Self::show_dock);
+        } else {
+            self.for_each_screen(|app| {
+                app.screen.dock_y_target = app.screen.dock_y_hidden;
+                app.screen.hide_timer = None;
+                app.set_click_through(true);
+                app.update_tray_icon();
+            });
+        }
+    }
+    
+    /// Tray "Auto-Hide": flip and save `auto_hide`; turning it off shows the dock
+    fn toggle_auto_hide(&mut self) {
+        self.config.dock.auto_hide = !self.config.dock.auto_hide;
+        if !self.config.dock.auto_hide {
+            self.for_each_screen(Self::show_dock);
+        }
+        self.save_config_with_settings();
+        self.update_appbar();
+        self.update_tray_icon();
+        self.sync_tray_checks();
+    }
+    
+    /// Tray "Always on Top": flip and save `always_on_top`
+    fn toggle_always_on_top(&mut self) {
+        self.config.dock.always_on_top = !self.config.dock.always_on_top;
+        self.save_config_with_settings();
+        self.for_each_screen(|app| {
+            app.apply_window_level();
+            if !app.config.dock.always_on_top {
+                app.raise_dock();
+            }
+        });
+        self.sync_tray_checks();
+    }
+    
+    /// Keep tray check marks in line with settings changed elsewhere
+    fn sync_tray_checks(&self) {
+        if let Some(item) = &self.auto_hide_item {
+            item.set_checked(self.config.dock.auto_hide);
+        }
+        if let Some(item) = &self.lock_item {
+            item.set_checked(self.config.dock.locked);
+        }
+        if let Some(item) = &self.presentation_item {
+            item.set_checked(self.presentation_mode);
+        }
+        if let Some(item) = &self.always_on_top_item {
+            item.set_checked(self.config.dock.always_on_top);
+        }
+    }
+    
+    /// Tray "Presentation Mode" / hotkey: hide the dock and hand the taskbar
+    /// back until toggled off again
+    fn toggle_presentation_mode(&mut self) {
+        self.presentation_mode = !self.presentation_mode;
+        self.for_each_screen(Self::apply_presentation_mode);
+        self.sync_tray_checks();
+    }
+    
+    /// Hide the dock in `self.screen` for presentation mode, or bring it back
+    fn apply_presentation_mode(&mut self) {
+        if self.presentation_mode {
+            self.screen.dock_y_target = self.screen.dock_y_hidden;
+            self.screen.hide_timer = None;
+            self.screen.show_timer = None;
+            self.screen.cursor_in_window = false;
+            self.set_click_through(true);
+            self.release_taskbar();
+            if let Some(tooltip) = &mut self.screen.tooltip {
+                tooltip.hide();
+            }
+            // Not even the hidden sliver should show up on a shared screen
+            if let Some(window) = &self.screen.window {
+                window.set_visible(false);
+            }
+            self.update_tray_icon();
+        } else {
+            if let Some(window) = &self.screen.window {
+                window.set_visible(true);
+            }
+            self.apply_taskbar_mode();
+            self.show_dock();
+        }
+    }
+    
+    /// Start a new rDock with the same config location and quit this one.
+    /// The new process waits for this one to exit (`--restarted-from`), so
+    /// the taskbar is restored before it gets hidden again.
+    fn restart(&mut self, event_loop: &ActiveEventLoop) {
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(e) => {
+                show_error_message(&format!("Couldn't restart rDock:\n\n{}", e));
+                return;
+            }
+        };
+        let mut cmd = Command::new(exe);
+        if std::env::args().skip(1).any(|arg| arg == "--appdata") {
+            cmd.arg("--appdata");
+        }
+        cmd.arg("--restarted-from").arg(std::process::id().to_string());
+        match cmd.spawn() {
+            Ok(_) => event_loop.exit(),
+            Err(e) => show_error_message(&format!("Couldn't restart rDock:\n\n{}", e)),
+        }
+    }
+    
+    /// The dock window's handle, or a null one before it exists
+    fn hwnd(&self) -> windows::Win32::Foundation::HWND {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        match self.screen.window.as_ref().map(|window| window.window_handle().map(|h| h.as_raw())) {
+            Some(Ok(RawWindowHandle::Win32(h))) => windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _),
+            _ => windows::Win32::Foundation::HWND::default(),
+        }
+    }
+    
+    /// Hide the taskbar or switch it to auto-hide, per `taskbar_mode`
+    fn apply_taskbar_mode(&mut self) {
+        match self.config.dock.taskbar_mode() {
+            TaskbarMode::Hide if !self.screen.taskbar_hidden => {
+                hide_taskbars(self.hwnd(), &mut self.screen.hidden_taskbars);
+                self.screen.taskbar_hidden = true;
+            }
+            TaskbarMode::AutoHide if self.taskbar_restore_state.is_none() => {
+                self.taskbar_restore_state = enable_taskbar_autohide();
+            }
+            _ => {}
+        }
+    }
+    
+    /// Undo `apply_taskbar_mode`
+    fn release_taskbar(&mut self) {
+        if self.screen.taskbar_hidden {
+            show_taskbars(&mut self.screen.hidden_taskbars);
+            self.screen.taskbar_hidden = false;
+        }
+        if let Some(state) = self.taskbar_restore_state.take() {
+            restore_taskbar_state(state);
+        }
+    }
+    
+    /// Register `presentation_hotkey`, replacing the previous one if it changed
+    fn update_hotkey(&mut self) {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        
+        let wanted = self.config.dock.presentation_hotkey.clone();
+        if wanted == self.registered_hotkey {
+            return;
+        }
+        // Registered on the main dock's window, whichever dock is in `self.screen`
+        let Some(window) = self.screens().find(|screen| screen.main).and_then(|screen| screen.window.as_ref()) else { return };
+        let hwnd = match window.window_handle().map(|h| h.as_raw()) {
+            Ok(RawWindowHandle::Win32(h)) => windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _),
+            _ => return,
+        };
+        
+        if self.registered_hotkey.take().is_some() {
+            hotkey::unregister(hwnd, PRESENTATION_HOTKEY_ID);
+        }
+        if let Some(spec) = wanted {
+            if hotkey::register(hwnd, PRESENTATION_HOTKEY_ID, &spec) {
+                self.registered_hotkey = Some(spec);
+            }
+        }
+    }
+    
     fn start_hide(&mut self) {
-        if self.config.dock.auto_hide && self.hide_timer.is_none() {
-            self.hide_timer = Some(Instant::now());
+        if self.config.dock.auto_hide && self.screen.hide_timer.is_none() && !self.screen.app_active {
+            self.screen.hide_timer = Some(Instant::now());
         }
     }
 
     fn setup_tray(&mut self) {
         let menu = Menu::new();
+        let toggle_dock = MenuItem::new("Show/Hide Dock", true, None);
+        let auto_hide = CheckMenuItem::new("Auto-Hide", true, self.config.dock.auto_hide, None);
+        let lock = CheckMenuItem::new("Lock Icons", true, self.config.dock.locked, None);
+        let compact = CheckMenuItem::new("Compact Mode", true, self.compact, None);
+        let presentation = CheckMenuItem::new("Presentation Mode", true, self.presentation_mode, None);
+        let always_on_top = CheckMenuItem::new("Always on Top", true, self.config.dock.always_on_top, None);
+        let reload = MenuItem::new("Reload Config", true, None);
+        let preview = MenuItem::new("Preview Config Changes", true, None);
+        let edit_config = MenuItem::new("Edit Config", true, None);
+        let restart = MenuItem::new("Restart rDock", true, None);
         let quit = MenuItem::new("Quit rDock", true, None);
         let qid = quit.id().clone();
+        let _ = menu.append(&toggle_dock);
+        let _ = menu.append(&PredefinedMenuItem::separator());
+        let _ = menu.append(&auto_hide);
+        let _ = menu.append(&lock);
+        let _ = menu.append(&compact);
+        let _ = menu.append(&presentation);
+        let _ = menu.append(&always_on_top);
+        let _ = menu.append(&PredefinedMenuItem::separator());
+        let _ = menu.append(&reload);
+        let _ = menu.append(&preview);
+        let _ = menu.append(&edit_config);
+        let _ = menu.append(&PredefinedMenuItem::separator());
+        let _ = menu.append(&restart);
         let _ = menu.append(&quit);
         
         // Create a dock-like tray icon (3 dots/squares)
-        let icon = create_tray_icon(&self.config.dock.indicator_color);
+        let state = self.tray_icon_state();
+        let icon = create_tray_icon(&self.config.dock.indicator_color, state.0, state.1);
         if let Ok(icon) = icon {
             if let Ok(tray) = TrayIconBuilder::new()
                 .with_menu(Box::new(menu))
@@ -965,18 +2527,30 @@ impl DockApp {
                 .with_icon(icon)
                 .build()
             {
-                self._tray = Some(tray);
+                self.tray = Some(tray);
+                self.tray_state = Some(state);
                 self.quit_id = Some(qid);
+                self.toggle_dock_id = Some(toggle_dock.id().clone());
+                self.reload_id = Some(reload.id().clone());
+                self.preview_config_id = Some(preview.id().clone());
+                self.restart_id = Some(restart.id().clone());
+                self.edit_config_id = Some(edit_config.id().clone());
+                self.compact_item = Some(compact);
+                self.auto_hide_item = Some(auto_hide);
+                self.lock_item = Some(lock);
+                self.presentation_item = Some(presentation);
+                self.always_on_top_item = Some(always_on_top);
             }
         }
     }
     
-    fn handle_right_click(&mut self, _position: PhysicalPosition<f64>, event_loop: &ActiveEventLoop) {
-        use context_menu::{show_context_menu, ContextMenuAction};
-        use item_editor::{show_item_editor, DialogResult};
+    /// Open the context menu at the cursor, or at `at` (window-local) when it
+    /// was opened from the keyboard
+    fn handle_right_click(&mut self, at: Option<PhysicalPosition<i32>>, event_loop: &ActiveEventLoop) {
+        use context_menu::show_context_menu;
         use raw_window_handle::{HasWindowHandle, RawWindowHandle};
         
-        let Some(window) = &self.window else { return };
+        let Some(window) = &self.screen.window else { return };
         
         // Get HWND
         let hwnd = match window.window_handle().map(|h| h.as_raw()) {
@@ -986,33 +2560,47 @@ impl DockApp {
         
         // Get screen coordinates and convert to window-local for hit test
         let (screen_x, screen_y, local_x, local_y) = unsafe {
-            let mut point = std::mem::zeroed::<windows::Win32::Foundation::POINT>();
-            windows::Win32::UI::WindowsAndMessaging::GetCursorPos(&mut point).ok();
-            let screen_x = point.x;
-            let screen_y = point.y;
-            
-            // Convert to window-local coordinates
             let hwnd_handle = windows::Win32::Foundation::HWND(hwnd as *mut _);
-            let _ = windows::Win32::Graphics::Gdi::ScreenToClient(hwnd_handle, &mut point);
-            (screen_x, screen_y, point.x, point.y)
+            let mut point = std::mem::zeroed::<windows::Win32::Foundation::POINT>();
+            if let Some(at) = at {
+                let (local_x, local_y) = (at.x, at.y);
+                point.x = local_x;
+                point.y = local_y;
+                let _ = windows::Win32::Graphics::Gdi::ClientToScreen(hwnd_handle, &mut point);
+                (point.x, point.y, local_x, local_y)
+            } else {
+                windows::Win32::UI::WindowsAndMessaging::GetCursorPos(&mut point).ok();
+                let screen_x = point.x;
+                let screen_y = point.y;
+                
+                // Convert to window-local coordinates
+                let _ = windows::Win32::Graphics::Gdi::ScreenToClient(hwnd_handle, &mut point);
+                (screen_x, screen_y, point.x, point.y)
+            }
         };
         
         // Perform hit test at click time using window-local cursor position
-        let clicked_item = if let Some(renderer) = &self.renderer {
-            renderer.hit_test(local_x, local_y, &self.config.items, &self.icon_scales)
+        let clicked_item = if let Some(renderer) = &self.screen.renderer {
+            renderer.hit_test(local_x, local_y, &self.dock_items, &self.screen.icon_scales)
         } else {
             None
         };
         
+        // New items go in the gap nearest the click, among the pinned items
+        let insert_index = self.screen.renderer.as_ref()
+            .map(|renderer| renderer.drop_index(local_x as f32, &self.dock_items, &self.screen.icon_scales, usize::MAX))
+            .unwrap_or(usize::MAX);
+        let insert_index = self.insert_position(insert_index);
+        
         // Check if clicked item is a separator
         let is_separator = clicked_item
-            .and_then(|i| self.config.items.get(i))
+            .and_then(|i| self.dock_items.get(i))
             .map(|item| item.is_separator())
             .unwrap_or(false);
         
         // Check if clicked item is a recycle bin
         let is_recycle_bin = clicked_item
-            .and_then(|i| self.config.items.get(i))
+            .and_then(|i| self.dock_items.get(i))
             .and_then(|item| item.special.as_ref())
             .map(|special| special == "recycle_bin")
             .unwrap_or(false);
@@ -1020,7 +2608,7 @@ impl DockApp {
         // Get app name for running non-special items
         let app_name: Option<String> = clicked_item
             .and_then(|i| {
-                let item = self.config.items.get(i)?;
+                let item = self.dock_items.get(i)?;
                 let is_running = self.running_states.get(i).copied().unwrap_or(false);
                 if is_running && !item.is_separator() && item.special.is_none() {
                     Some(item.name.clone())
@@ -1029,24 +2617,67 @@ impl DockApp {
                 }
             });
         
+        // Running-only items sit after the pinned ones
+        let is_pinned = clicked_item.map(|i| i < self.pinned_indices.len()).unwrap_or(true);
+        let included_from = clicked_item
+            .and_then(|i| self.pinned_indices.get(i))
+            .and_then(|&i| self.config.items[i].included_from.clone());
+        
+        // Only items that launch a file have a location to open
+        let has_location = clicked_item
+            .and_then(|i| self.dock_items.get(i))
+            .map(|item| {
+                !item.is_separator() && item.special.is_none() && item.command.is_none()
+                    && !item.path.as_os_str().is_empty()
+            })
+            .unwrap_or(false);
+        
+        let clipboard_path = if self.config.dock.locked { None } else { shortcut::clipboard_path() };
+        let has_hidden = self.config.items.iter().any(|item| item.hidden && item.included_from.is_none());
+        
         // Show unified context menu
-        let action = show_context_menu(hwnd, screen_x, screen_y, clicked_item, self.config.dock.locked, is_separator, is_recycle_bin, app_name.as_deref());
+        let action = show_context_menu(hwnd, screen_x, screen_y, clicked_item, insert_index, self.config.dock.locked, is_separator, is_recycle_bin, is_pinned, included_from.as_deref(), has_location, self.undo_config.is_some(), has_hidden, clipboard_path.as_deref(), app_name.as_deref());
+        
+        self.handle_menu_action(action, event_loop);
+    }
+    
+    /// Carry out a context menu action; the tray menu reuses these too
+    fn handle_menu_action(&mut self, action: context_menu::ContextMenuAction, event_loop: &ActiveEventLoop) {
+        use context_menu::ContextMenuAction;
+        use item_editor::{show_item_editor, show_item_properties, DialogResult};
+        
+        // Snapshot for undo, recorded by the arms below that change the config
+        let before = self.config.clone();
         
         match action {
-            ContextMenuAction::AddItem => {
+            ContextMenuAction::AddItem(index) => {
                 // Open item editor for new item
                 if let DialogResult::Ok(item) = show_item_editor(None, true) {
-                    self.config.items.push(item);
+                    self.config.items.insert(index.min(self.config.items.len()), *item);
+                    self.undo_config = Some(before.clone());
                     self.save_config();
                     self.needs_reload = true;
                 }
             }
-            ContextMenuAction::AddSeparator => {
-                self.config.items.push(DockItem::new_separator());
+            ContextMenuAction::AddSeparator(index) => {
+                self.config.items.insert(index.min(self.config.items.len()), DockItem::new_separator());
+                self.undo_config = Some(before.clone());
+                self.save_config();
+                self.needs_reload = true;
+            }
+            ContextMenuAction::AddSpacer(index) => {
+                self.config.items.insert(index.min(self.config.items.len()), DockItem::new_spacer());
+                self.undo_config = Some(before.clone());
                 self.save_config();
                 self.needs_reload = true;
             }
-            ContextMenuAction::AddSpecial(special_type) => {
+            ContextMenuAction::AddFromClipboard(path, index) => {
+                self.config.items.insert(index.min(self.config.items.len()), shortcut::item_for_file(&path));
+                self.undo_config = Some(before.clone());
+                self.save_config();
+                self.needs_reload = true;
+            }
+            ContextMenuAction::AddSpecial(special_type, index) => {
                 // Open item editor pre-filled with special type
                 let name = context_menu::SPECIAL_ITEMS.iter()
                     .find(|(id, _)| *id == special_type)
@@ -1059,34 +2690,66 @@ impl DockApp {
                     icon: None,
                     args: Vec::new(),
                     separator: false,
+                    region: false,
+                    spacer: None,
                     special: Some(special_type),
+                    match_pattern: None,
+                    confirm: None,
+                    command: None,
+                    tooltip: None,
+                    badge: None,
+                    hidden: false,
+                    children: Vec::new(),
+                    included_from: None,
                 };
                 
                 if let DialogResult::Ok(item) = show_item_editor(Some(&prefilled), true) {
-                    self.config.items.push(item);
+                    self.config.items.insert(index.min(self.config.items.len()), *item);
+                    self.undo_config = Some(before.clone());
                     self.save_config();
                     self.needs_reload = true;
                 }
             }
+            ContextMenuAction::SortByName => {
+                config::sort_sections(&mut self.config.items, |a, b| {
+                    a.name.to_lowercase().cmp(&b.name.to_lowercase())
+                });
+                self.undo_config = Some(before.clone());
+                self.save_config();
+                self.needs_reload = true;
+            }
+            ContextMenuAction::SortByLaunches => {
+                // Most launched first; the sort is stable, so ties keep their order
+                let history = &self.recent_apps;
+                config::sort_sections(&mut self.config.items, |a, b| {
+                    history.launch_count(b).cmp(&history.launch_count(a))
+                });
+                self.undo_config = Some(before.clone());
+                self.save_config();
+                self.needs_reload = true;
+            }
             ContextMenuAction::RemoveItem(idx) => {
-                if idx < self.config.items.len() {
+                if let Some(idx) = self.config_index(idx) {
                     self.config.items.remove(idx);
+                    self.undo_config = Some(before.clone());
                     self.save_config();
                     self.needs_reload = true;
                 }
             }
             ContextMenuAction::EditItem(idx) => {
                 // Open item editor for existing item
-                if idx < self.config.items.len() {
+                if let Some(idx) = self.config_index(idx) {
                     let existing = self.config.items[idx].clone();
                     match show_item_editor(Some(&existing), false) {
                         DialogResult::Ok(item) => {
-                            self.config.items[idx] = item;
+                            self.config.items[idx] = *item;
+                            self.undo_config = Some(before.clone());
                             self.save_config();
                             self.needs_reload = true;
                         }
                         DialogResult::Remove => {
                             self.config.items.remove(idx);
+                            self.undo_config = Some(before.clone());
                             self.save_config();
                             self.needs_reload = true;
                         }
@@ -1094,6 +2757,40 @@ impl DockApp {
                     }
                 }
             }
+            ContextMenuAction::DuplicateItem(idx) => {
+                // Insert a copy after the original, then edit the copy.
+                // Cancel keeps the copy as is; Remove drops it again.
+                if let Some(idx) = self.config_index(idx) {
+                    let copy = self.config.items[idx].clone();
+                    let copy = match show_item_editor(Some(&copy), false) {
+                        DialogResult::Ok(item) => Some(*item),
+                        DialogResult::Cancel => Some(copy),
+                        DialogResult::Remove => None,
+                    };
+                    if let Some(copy) = copy {
+                        self.config.items.insert(idx + 1, copy);
+                        self.undo_config = Some(before.clone());
+                        self.save_config();
+                        self.needs_reload = true;
+                    }
+                }
+            }
+            ContextMenuAction::HideItem(idx) => {
+                if let Some(idx) = self.config_index(idx) {
+                    self.config.items[idx].hidden = true;
+                    self.undo_config = Some(before.clone());
+                    self.save_config();
+                    self.needs_reload = true;
+                }
+            }
+            ContextMenuAction::ShowHiddenItems => {
+                for item in self.config.items.iter_mut().filter(|item| item.included_from.is_none()) {
+                    item.hidden = false;
+                }
+                self.undo_config = Some(before.clone());
+                self.save_config();
+                self.needs_reload = true;
+            }
             ContextMenuAction::ToggleLock => {
                 // Toggle lock state (not persisted - edit config file to change default)
                 self.config.dock.locked = !self.config.dock.locked;
@@ -1104,11 +2801,30 @@ impl DockApp {
                     .args(["/c", "start", "", self.config_path.to_str().unwrap_or("")])
                     .spawn();
             }
+            ContextMenuAction::ReloadConfig => {
+                // For edits the file watcher missed (network drives, some tools)
+                self.needs_reload = true;
+            }
+            ContextMenuAction::PreviewConfig => self.preview_config(),
+            ContextMenuAction::OpenSettings => {
+                // Edit appearance with live preview; revert if cancelled
+                let saved = self.config.dock.clone();
+                let result = settings_dialog::show_settings_dialog(&saved, &mut |draft| self.preview_settings(draft));
+                match result {
+                    Some(settings) => {
+                        self.config.dock = settings;
+                        self.undo_config = Some(before.clone());
+                        self.save_config_with_settings();
+                        self.needs_reload = true;
+                    }
+                    None => self.preview_settings(&saved),
+                }
+            }
             ContextMenuAction::SaveConfigAs => {
                 // Save config to a new location
                 if let Some(path) = context_menu::save_config_dialog(Some(&self.config_path)) {
                     if let Err(e) = self.config.save(&path) {
-                        eprintln!("Failed to save config: {}", e);
+                        log::error!("Failed to save config: {}", e);
                     }
                 }
             }
@@ -1119,11 +2835,12 @@ impl DockApp {
                         Ok(new_config) => {
                             self.config = new_config;
                             // Save to default config path to persist the loaded config
+                            self.undo_config = Some(before.clone());
                             self.save_config();
                             self.needs_reload = true;
                         }
                         Err(e) => {
-                            eprintln!("Failed to load config: {}", e);
+                            log::error!("Failed to load config: {}", e);
                         }
                     }
                 }
@@ -1131,186 +2848,125 @@ impl DockApp {
             ContextMenuAction::ResetSettings => {
                 // Reset dock settings to defaults, keep items
                 self.config.dock = DockSettings::default();
-                self.save_config();
+                self.undo_config = Some(before.clone());
+                self.save_config_with_settings();
                 self.needs_reload = true;
             }
             ContextMenuAction::ResetAll => {
                 // Write the default config template (full reset)
-                if let Err(e) = std::fs::write(&self.config_path, DEFAULT_CONFIG_TEMPLATE) {
-                    eprintln!("Failed to write default config: {}", e);
+                if let Err(e) = config::write_config_file(&self.config_path, DEFAULT_CONFIG_TEMPLATE) {
+                    log::error!("Failed to write default config: {}", e);
                 } else {
+                    self.undo_config = Some(before.clone());
+                    self.needs_reload = true;
+                }
+            }
+            ContextMenuAction::PinItem(idx) => {
+                if let Some(i) = idx.checked_sub(self.pinned_indices.len()).filter(|&i| i < self.running_items.len()) {
+                    let item = self.running_items.remove(i);
+                    self.config.items.push(item);
+                    self.undo_config = Some(before.clone());
+                    self.save_config();
                     self.needs_reload = true;
                 }
             }
             ContextMenuAction::QuitApp(idx) => {
-                if let Some(item) = self.config.items.get(idx) {
+                if let Some(item) = self.dock_items.get(idx) {
                     app_monitor::quit_application(&item.path);
                     // Force a process state refresh
-                    self.last_process_check = Instant::now() - PROCESS_CHECK_INTERVAL;
+                    self.last_process_check = time_ago(self.config.dock.intervals.process_check());
+                }
+            }
+            ContextMenuAction::OpenLocation(idx) => {
+                if let Some(item) = self.dock_items.get(idx) {
+                    open_file_location(&item.path);
+                }
+            }
+            ContextMenuAction::ItemProperties(idx) => {
+                if let Some(item) = self.dock_items.get(idx).cloned() {
+                    show_item_properties(&item);
                 }
             }
             ContextMenuAction::EmptyRecycleBin => {
                 self.empty_recycle_bin();
             }
+            ContextMenuAction::Undo => {
+                // Restore the config from before the last edit, [dock] included
+                if let Some(mut previous) = self.undo_config.take() {
+                    previous.dock.locked = self.config.dock.locked;
+                    self.config = previous;
+                    self.save_config_with_settings();
+                    self.needs_reload = true;
+                }
+            }
+            ContextMenuAction::Restart => self.restart(event_loop),
             ContextMenuAction::Quit => {
                 event_loop.exit();
             }
             ContextMenuAction::None => {}
         }
+        self.sync_tray_checks();
     }
     
     fn save_config(&self) {
+        if self.config_unreadable {
+            log::error!("Not saving: {} could not be read at startup", self.config_path.display());
+            return;
+        }
         if let Err(e) = self.config.save(&self.config_path) {
-            eprintln!("Failed to save config: {}", e);
+            log::error!("Failed to save config: {}", e);
+        }
+    }
+    
+    fn save_config_with_settings(&self) {
+        if self.config_unreadable {
+            log::error!("Not saving: {} could not be read at startup", self.config_path.display());
+            return;
+        }
+        if let Err(e) = self.config.save_with_dock(&self.config_path) {
+            log::error!("Failed to save config: {}", e);
         }
     }
     
     fn is_animating(&self) -> bool {
         // Check if dock position is animating
-        let dock_animating = (self.dock_y_target - self.dock_y_current).abs() > 0.5;
+        let dock_animating = (self.screen.dock_y_target - self.screen.dock_y_current).abs() > 0.5;
         
         // Check if any icon scale is animating
-        let icons_animating = self.icon_scales.iter().any(|&scale| (scale - 1.0).abs() > 0.01);
+        let icons_animating = self.screen.icon_scales.iter().any(|&scale| (scale - 1.0).abs() > 0.01);
         
         // Check if hide/show timers are active
-        let timer_pending = self.hide_timer.is_some() || self.show_timer.is_some();
+        let timer_pending = self.screen.hide_timer.is_some() || self.screen.show_timer.is_some();
         
         // NOTE: deliberately do NOT include `cursor_in_window` here. The icon wave
         // effect drives `icon_scales` which already trip `icons_animating` while
-        // the cursor is moving over the dock; once the cursor stops, scales
-        // converge to their targets and there is nothing left to animate.
-        // Including `cursor_in_window` caused the event loop to run at 60 FPS
-        // indefinitely whenever the flag got stuck true (e.g. missed CursorLeft
-        // during a spawn-time race, or cursor_in_window flipped true while
-        // `fullscreen_active` suppresses the reset path in check_mouse_position).
-        dock_animating || icons_animating || timer_pending
-    }
-    
-    fn get_drop_index(&self) -> usize {
-        // Calculate which position the cursor is over for dropping
-        let Some(renderer) = &self.renderer else { return 0 };
-        
-        let icon_size = renderer.icon_size as f32;
-        let spacing = renderer.spacing.x as f32;
-        let padding_left = renderer.padding.left as f32;
-        let num_items = self.config.items.len();
-        
-        if num_items == 0 {
-            return 0;
-        }
-        
-        // Calculate relative position within the icons area
-        let rel_x = self.cursor_x - padding_left;
-        
-        // Each item slot is (icon_size + spacing) wide, last one just icon_size
-        let slot_width = icon_size + spacing;
-        
-        if rel_x <= 0.0 {
-            return 0;
-        }
-        
-        // Find which slot we're in
-        let slot = (rel_x / slot_width) as usize;
-        let within_slot = rel_x - (slot as f32 * slot_width);
-        
-        // If we're in the right half of the slot, drop after this item
-        if within_slot > icon_size / 2.0 {
-            (slot + 1).min(num_items)
-        } else {
-            slot.min(num_items)
-        }
-    }
-}
-
-impl ApplicationHandler for DockApp {
-    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let monitor = event_loop.primary_monitor()
-            .or_else(|| event_loop.available_monitors().next())
-            .expect("No monitor found");
-        
-        let screen = monitor.size();
-        self.screen_width = screen.width;
-        self.screen_height = screen.height;
-
-        let renderer = Renderer::new(&self.config, &self.config.items)
-            .expect("Failed to create renderer");
-        
-        let dock_w = renderer.width;
-        let dock_h = renderer.height;
-
-        let x = (screen.width - dock_w) / 2;
-        let offset = self.config.dock.negative_vertical_offset;
-        // Positive offset = move down (bury into edge)
-        let y_vis = (screen.height as i32 - dock_h as i32 + offset) as u32;
-        // When hidden, push dock fully off-screen so cursor can't enter it directly.
-        // check_mouse_position() handles detection via global cursor polling.
-        let y_hid = screen.height + 20;
-        
-        self.dock_y_visible = y_vis as f32;
-        self.dock_y_hidden = y_hid as f32;
-        self.dock_y_current = y_vis as f32;
-        self.dock_y_target = y_vis as f32;
-
-        let attrs = Window::default_attributes()
-            .with_title("rDock")
-            .with_inner_size(PhysicalSize::new(dock_w, dock_h))
-            .with_position(PhysicalPosition::new(x as i32, y_vis as i32))
-            .with_decorations(false)
-            .with_transparent(true)
-            .with_resizable(false)
-            .with_window_level(WindowLevel::AlwaysOnTop)
-            .with_skip_taskbar(true);
-
-        let window = Rc::new(event_loop.create_window(attrs).unwrap());
-        
-        // Set position again after creation - with_position doesn't always work
-        window.set_outer_position(PhysicalPosition::new(x as i32, y_vis as i32));
-        
-        let ctx = softbuffer::Context::new(window.clone()).unwrap();
-        let mut surface = Surface::new(&ctx, window.clone()).unwrap();
-        surface.resize(NonZeroU32::new(dock_w).unwrap(), NonZeroU32::new(dock_h).unwrap()).unwrap();
-
-        self.window = Some(window);
-        self.surface = Some(surface);
-        self.renderer = Some(renderer);
-        self.running_states = vec![false; self.config.items.len()];
-        self.icon_scales = vec![1.0; self.config.items.len()];
-        
-        self.setup_tray();
-        self.start_watching();
-        
-        // Initialize tooltip with config background color
-        if let Some(window) = &self.window {
-            use raw_window_handle::{HasWindowHandle, RawWindowHandle};
-            if let Ok(RawWindowHandle::Win32(h)) = window.window_handle().map(|h| h.as_raw()) {
-                let hwnd = windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _);
-                self.tooltip = Tooltip::new_with_color(hwnd, &self.config.dock.background_color);
-            }
-        }
-        
-        // Hide Windows taskbar if configured
-        if self.config.dock.hide_windows_taskbar && !self.taskbar_hidden {
-            set_taskbar_visibility(false);
-            self.taskbar_hidden = true;
-        }
-        
-        // Force position by starting slightly off and animating to correct position
-        // This works around a winit/Windows issue where initial position is ignored
-        self.dock_y_current = y_vis as f32 + 10.0;
-        if let Some(window) = &self.window {
-            window.request_redraw();
-        }
+        // the cursor is moving over the dock; once the cursor stops, scales
+        // converge to their targets and there is nothing left to animate.
+        // Including `cursor_in_window` caused the event loop to run at 60 FPS
+        // indefinitely whenever the flag got stuck true (e.g. missed CursorLeft
+        // during a spawn-time race, or cursor_in_window flipped true while
+        // `fullscreen_active` suppresses the reset path in check_mouse_position).
+        dock_animating || icons_animating || timer_pending || self.screen.intro_start.is_some()
+            || self.screen.pulse_start.is_some() || self.icons_playing()
     }
     
-    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
-        // Restore taskbar when exiting
-        if self.taskbar_hidden {
-            set_taskbar_visibility(true);
-            self.taskbar_hidden = false;
-        }
+    /// Animated icons play while the dock is showing and sit still when hidden
+    fn icons_playing(&self) -> bool {
+        !self.presentation_mode
+            && self.screen.dock_y_target == self.screen.dock_y_visible
+            && self.screen.renderer.as_ref().is_some_and(|renderer| renderer.has_animated_icons())
     }
-
-    fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
+    
+    fn get_drop_index(&self) -> usize {
+        // Calculate which position the cursor is over for dropping
+        let Some(renderer) = &self.screen.renderer else { return 0 };
+        let Some(dragged) = self.screen.drag_start_idx else { return 0 };
+        
+        renderer.drop_index(self.screen.cursor_x, &self.dock_items, &self.screen.icon_scales, dragged)
+    }
+    
+    /// Handle an event for the window of the dock in `self.screen`
+    fn handle_window_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) {
         match event {
             WindowEvent::CloseRequested => event_loop.exit(),
 
@@ -1326,129 +2982,561 @@ impl ApplicationHandler for DockApp {
             }
 
             WindowEvent::CursorMoved { position, .. } => {
-                self.cursor_in_window = true;
-                self.cursor_x = position.x as f32;
-                self.cursor_y = position.y as f32;
+                self.screen.cursor_in_window = true;
+                self.screen.cursor_x = position.x as f32;
+                self.screen.cursor_y = position.y as f32;
                 
                 // Only show dock immediately if it's already intentionally showing/visible.
                 // If hiding or hidden, respect the show delay via show_timer.
-                let dock_is_showing = self.dock_y_target == self.dock_y_visible;
+                let dock_is_showing = self.screen.dock_y_target == self.screen.dock_y_visible;
                 if dock_is_showing {
                     self.show_dock();
-                } else if self.show_timer.is_none() {
-                    self.show_timer = Some(Instant::now());
+                } else if self.screen.show_timer.is_none() {
+                    self.screen.show_timer = Some(Instant::now());
                 }
                 
                 // Check if we should start dragging (mouse moved enough while button held)
-                if !self.dragging && self.drag_start_idx.is_some() && !self.config.dock.locked {
-                    let dx = (self.cursor_x - self.drag_start_x).abs();
-                    if dx > 5.0 {
+                if !self.screen.dragging && self.screen.drag_start_idx.is_some() && !self.config.dock.locked {
+                    let dx = (self.screen.cursor_x - self.screen.drag_start_x).abs();
+                    if dx > self.config.dock.drag_threshold_px as f32 {
                         // Start actual drag
-                        self.dragging = true;
+                        self.screen.dragging = true;
                     }
                 }
                 
-                if !self.dragging {
-                    if let Some(renderer) = &self.renderer {
+                if !self.screen.dragging {
+                    if let Some(renderer) = &self.screen.renderer {
                         let new_hovered = renderer.hit_test(
                             position.x as i32,
                             position.y as i32,
-                            &self.config.items,
-                            &self.icon_scales,
+                            &self.dock_items,
+                            &self.screen.icon_scales,
                         );
-                        self.hovered_item = new_hovered;
-                        
-                        // Update tooltip
-                        if let Some(tooltip) = &mut self.tooltip {
-                            if let Some(idx) = new_hovered {
-                                if let Some(item) = self.config.items.get(idx) {
-                                    if !item.is_separator() && !item.name.is_empty() {
-                                        // Get screen position for tooltip
-                                        if let Some(window) = &self.window {
-                                            let win_pos = window.outer_position().unwrap_or_default();
-                                            let screen_x = win_pos.x + position.x as i32;
-                                            let screen_y = win_pos.y;
-                                            tooltip.show(&item.name, screen_x, screen_y);
-                                        }
-                                    } else {
-                                        tooltip.hide();
-                                    }
-                                }
-                            } else {
-                                tooltip.hide();
-                            }
-                        }
+                        self.screen.hovered_item = new_hovered;
                     }
+                    self.update_tooltip();
                 }
-                if let Some(window) = &self.window {
+                if let Some(window) = &self.screen.window {
                     window.request_redraw();
                 }
             }
 
-            WindowEvent::CursorLeft { .. } => {
-                self.cursor_in_window = false;
-                self.cursor_x = -1000.0;
-                self.cursor_y = -1000.0;
-                self.hovered_item = None;
-                // Cancel any drag in progress
-                self.dragging = false;
-                self.drag_start_idx = None;
-                // Only start hide timer if dock is visible (prevents race conditions)
-                if (self.dock_y_current - self.dock_y_visible).abs() < 5.0 {
-                    self.start_hide();
+            WindowEvent::DroppedFile(path) => {
+                self.add_dropped_file(&path);
+                if let Some(window) = &self.screen.window {
+                    window.request_redraw();
                 }
-                // Hide tooltip
-                if let Some(tooltip) = &mut self.tooltip {
-                    tooltip.hide();
+            }
+
+            WindowEvent::CursorLeft { .. } => {
+                // The window keeps the mouse captured while the left button is
+                // down, so the release still comes here: keep tracking the
+                // press, which `drag_to_remove` needs well above the dock
+                if self.screen.drag_start_idx.is_some() && left_button_held() {
+                    return;
                 }
+                self.cursor_left();
             }
 
             WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
                 // Track click start for both launching (always) and dragging (if unlocked)
-                if let Some(idx) = self.hovered_item {
-                    self.drag_start_idx = Some(idx);
-                    self.drag_start_x = self.cursor_x;
+                if let Some(idx) = self.screen.hovered_item {
+                    self.screen.drag_start_idx = Some(idx);
+                    self.screen.drag_start_x = self.screen.cursor_x;
                 }
             }
             
             WindowEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left, .. } => {
-                if self.dragging {
+                if self.screen.dragging && self.is_dropped_off_dock() {
+                    // Dragged a pinned item off the dock - remove it
+                    if let Some(from_idx) = self.screen.drag_start_idx.and_then(|i| self.config_index(i)) {
+                        self.remove_dragged_item(from_idx);
+                    }
+                    self.screen.dragging = false;
+                    self.screen.drag_start_idx = None;
+                } else if self.screen.dragging {
                     // Complete the drag - reorder items
-                    if let Some(from_idx) = self.drag_start_idx {
+                    if let Some(from_idx) = self.screen.drag_start_idx {
                         let to_idx = self.get_drop_index();
-                        if to_idx != from_idx && to_idx != from_idx + 1 {
+                        let pinned = self.pinned_indices.len();
+                        let before = self.config.clone();
+                        if from_idx >= pinned {
+                            // Dragging a running-only item among the pinned ones pins it there
+                            if to_idx <= pinned {
+                                let item = self.running_items.remove(from_idx - pinned);
+                                let insert_idx = self.insert_position(to_idx);
+                                self.config.items.insert(insert_idx, item);
+                                self.undo_config = Some(before.clone());
+                                self.save_config();
+                                self.needs_reload = true;
+                            }
+                        } else if to_idx != from_idx && to_idx != from_idx + 1 && self.config_index(from_idx).is_some() {
                             // Remove from old position and insert at new position
+                            // (pinned items can't be dropped among running-only ones)
+                            // (indices are translated to `config.items`, past any hidden ones)
+                            let to_idx = self.insert_position(to_idx.min(pinned));
+                            let from_idx = self.pinned_indices[from_idx];
                             let item = self.config.items.remove(from_idx);
                             let insert_idx = if to_idx > from_idx { to_idx - 1 } else { to_idx };
                             self.config.items.insert(insert_idx, item);
+                            self.undo_config = Some(before.clone());
                             self.save_config();
                             self.needs_reload = true;
                         }
                     }
-                    self.dragging = false;
-                    self.drag_start_idx = None;
-                } else if let Some(index) = self.drag_start_idx {
-                    // Was a click, not a drag - launch the item
+                    self.screen.dragging = false;
+                    self.screen.drag_start_idx = None;
+                } else if self.config_preview.is_some() {
+                    // The click that keeps a previewed config doesn't launch anything
+                    self.config_preview = None;
+                    self.screen.drag_start_idx = None;
+                    if let Some(window) = &self.screen.window {
+                        window.request_redraw();
+                    }
+                } else if let Some(index) = self.screen.drag_start_idx {
+                    // Was a click, not a drag - launch the item, unless the
+                    // button came up off the dock
                     // Don't launch separators
-                    if !self.config.items.get(index).map(|i| i.is_separator()).unwrap_or(false) {
-                        self.launch_item(index);
+                    if self.cursor_over_dock() && !self.dock_items.get(index).map(|i| i.is_separator()).unwrap_or(false) {
+                        self.click_item(index, event_loop);
                     }
-                    self.drag_start_idx = None;
+                    self.screen.drag_start_idx = None;
+                }
+                // `CursorLeft` skipped the cleanup while the button was down
+                if !self.cursor_over_dock() {
+                    self.cursor_left();
                 }
             }
             
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+            }
+            
             WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Right, .. } => {
                 // Cancel any drag
-                self.dragging = false;
-                self.drag_start_idx = None;
-                // Get cursor position for context menu
-                let pos = PhysicalPosition::new(self.cursor_x as f64, self.cursor_y as f64);
-                self.handle_right_click(pos, event_loop);
+                self.screen.dragging = false;
+                self.screen.drag_start_idx = None;
+                self.handle_right_click(None, event_loop);
+            }
+            
+            WindowEvent::MouseWheel { delta, .. } if self.config.dock.scroll_magnify => self.scrub_magnification(delta),
+            WindowEvent::MouseWheel { delta, .. } => {
+                // Down or right moves on to later items
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => if y != 0.0 { -y } else { x },
+                    MouseScrollDelta::PixelDelta(pos) => {
+                        let step = self.screen.renderer.as_ref().map_or(48, |renderer| renderer.icon_size) as f32;
+                        -(pos.y as f32) / step
+                    }
+                };
+                self.screen.wheel_lines += lines;
+                let steps = self.screen.wheel_lines.trunc();
+                self.screen.wheel_lines -= steps;
+                if steps != 0.0 {
+                    self.scroll_items(steps as i32);
+                }
+            }
+            
+            // Left/Right scroll an overflowing dock while it has focus
+            WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed
+                && matches!(event.logical_key, Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowRight)) =>
+            {
+                let steps = if event.logical_key == Key::Named(NamedKey::ArrowLeft) { -1 } else { 1 };
+                self.scroll_items(steps);
+            }
+            
+            // The Menu key or Shift+F10 opens the context menu for the
+            // hovered item, or the dock itself, while the dock has focus
+            WindowEvent::KeyboardInput { event, .. } if event.state == ElementState::Pressed && !event.repeat => {
+                let menu_key = match event.logical_key {
+                    Key::Named(NamedKey::ContextMenu) => true,
+                    Key::Named(NamedKey::F10) => shift_held(),
+                    _ => false,
+                };
+                if menu_key {
+                    let at = self.screen.renderer.as_ref()
+                        .and_then(|renderer| {
+                            let index = self.screen.hovered_item?;
+                            renderer.item_center(index, &self.dock_items, &self.screen.icon_scales)
+                        })
+                        // Just above the middle of the dock: no item, menu at the top
+                        .or_else(|| self.screen.renderer.as_ref().map(|renderer| (renderer.width as i32 / 2, -1)))
+                        .map(|(x, y)| PhysicalPosition::new(x, y));
+                    self.handle_right_click(at, event_loop);
+                }
+            }
+            
+            WindowEvent::MouseInput { state: ElementState::Pressed, button, .. } => {
+                let Some(number) = extra_button_number(button) else { return };
+                if let Some(action) = self.config.dock.button_bindings.get(&number.to_string()).cloned() {
+                    self.run_button_action(&action, event_loop);
+                    if let Some(window) = &self.screen.window {
+                        window.request_redraw();
+                    }
+                }
             }
 
             _ => {}
         }
     }
+    
+    /// Add a file dropped from Explorer as a new pinned item where it landed
+    fn add_dropped_file(&mut self, path: &Path) {
+        if self.config.dock.locked {
+            return;
+        }
+        let Some(window) = &self.screen.window else { return };
+        let Some(renderer) = &self.screen.renderer else { return };
+        
+        // No cursor events arrive during an OLE drag, so cursor_x is stale
+        let mut point = windows::Win32::Foundation::POINT::default();
+        unsafe {
+            let _ = windows::Win32::UI::WindowsAndMessaging::GetCursorPos(&mut point);
+        }
+        let win_pos = window.outer_position().unwrap_or_default();
+        let x = (point.x - win_pos.x) as f32;
+        let index = renderer.drop_index(x, &self.dock_items, &self.screen.icon_scales, usize::MAX);
+        let index = self.insert_position(index);
+        
+        let before = self.config.clone();
+        self.config.items.insert(index, shortcut::item_for_file(path));
+        self.undo_config = Some(before);
+        self.save_config();
+        self.needs_reload = true;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AutoHideAction {
+    /// Cursor is in the reveal zone: show (or keep showing) the dock
+    Reveal,
+    /// Cursor is away from a shown dock: start the hide timer
+    Hide,
+    /// Leave the dock as it is
+    Stay,
+}
+
+/// `config.items` indices of the pinned items the dock shows
+fn pinned_indices(config: &Config) -> Vec<usize> {
+    let shown = config::shown_indices(&config.items);
+    if config.dock.tidy_separators {
+        config::tidy_separators(&config.items, &shown)
+    } else {
+        shown
+    }
+}
+
+/// Auto-hide decision for one cursor poll. `since_shown` is the time since the
+/// dock last started showing; hides are held off for `SHOW_GRACE_PERIOD` after.
+fn auto_hide_action(in_trigger_zone: bool, in_dock: bool, dock_shown: bool, since_shown: Option<Duration>) -> AutoHideAction {
+    if in_trigger_zone {
+        return AutoHideAction::Reveal;
+    }
+    if in_dock || !dock_shown {
+        return AutoHideAction::Stay;
+    }
+    if since_shown.map(|d| d < SHOW_GRACE_PERIOD).unwrap_or(false) {
+        return AutoHideAction::Stay;
+    }
+    AutoHideAction::Hide
+}
+
+/// Size of the square at a hot corner that reveals the dock
+const HOT_CORNER_SIZE: i32 = 16;
+
+/// Whether a cursor at (x, y) should reveal the dock: within `trigger_distance`
+/// of the bottom screen edge, or inside the hot corner square if configured.
+fn in_reveal_zone(x: i32, y: i32, screen_w: u32, screen_h: u32, trigger_distance: u32, hot_corner: Option<HotCorner>) -> bool {
+    let (screen_w, screen_h) = (screen_w as i32, screen_h as i32);
+    if y >= screen_h - trigger_distance as i32 {
+        return true;
+    }
+    let in_corner_row = y >= screen_h - HOT_CORNER_SIZE;
+    match hot_corner {
+        Some(HotCorner::BottomLeft) => in_corner_row && x < HOT_CORNER_SIZE,
+        Some(HotCorner::BottomRight) => in_corner_row && x >= screen_w - HOT_CORNER_SIZE,
+        None => false,
+    }
+}
+
+/// Number of a mouse button beyond the usual three, as used in `button_bindings`
+fn extra_button_number(button: MouseButton) -> Option<u16> {
+    match button {
+        MouseButton::Back => Some(4),
+        MouseButton::Forward => Some(5),
+        MouseButton::Other(n) => Some(n),
+        _ => None,
+    }
+}
+
+/// Whether a watcher event on the config directory touches the config file.
+/// Deletions are ignored so a briefly missing file keeps the current config.
+fn is_config_change(event: &Event, config_path: &Path) -> bool {
+    let relevant_kind = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+    relevant_kind && event.paths.iter().any(|p| p.file_name() == config_path.file_name())
+}
+
+/// Block until process `pid` has exited, for at most `timeout`
+#[cfg(windows)]
+fn wait_for_exit(pid: u32, timeout: Duration) {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{OpenProcess, WaitForSingleObject, PROCESS_SYNCHRONIZE};
+    
+    unsafe {
+        // Already gone
+        let Ok(process) = OpenProcess(PROCESS_SYNCHRONIZE, false, pid) else { return };
+        let _ = WaitForSingleObject(process, timeout.as_millis() as u32);
+        let _ = CloseHandle(process);
+    }
+}
+
+/// Show an error message box without blocking the dock's event loop.
+#[cfg(windows)]
+fn show_error_message(text: &str) {
+    use windows::core::PCWSTR;
+    use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, MB_ICONWARNING, MB_OK, MB_TOPMOST};
+    
+    let text: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    std::thread::spawn(move || unsafe {
+        let title: Vec<u16> = "rDock\0".encode_utf16().collect();
+        MessageBoxW(None, PCWSTR(text.as_ptr()), PCWSTR(title.as_ptr()), MB_OK | MB_ICONWARNING | MB_TOPMOST);
+    });
+}
+
+#[cfg(not(windows))]
+fn show_error_message(text: &str) {
+    eprintln!("{}", text);
+}
+
+/// Open Explorer on the folder containing `path`, with the file selected
+#[cfg(windows)]
+fn open_file_location(path: &Path) {
+    use std::os::windows::process::CommandExt;
+    
+    if !path.exists() {
+        show_error_message(&format!("Couldn't open file location:\n{}\n\nThe file does not exist", path.display()));
+        return;
+    }
+    // Explorer parses its own command line, so the quoting has to be passed through as-is
+    if let Err(e) = Command::new("explorer")
+        .raw_arg(format!("/select,\"{}\"", path.display()))
+        .spawn()
+    {
+        show_error_message(&format!("Couldn't open file location:\n{}\n\n{}", path.display(), e));
+    }
+}
+
+#[cfg(not(windows))]
+fn open_file_location(path: &Path) {
+    log::error!("Open file location not supported on this platform: {}", path.display());
+}
+
+/// Set the whole-window alpha (layered window), or drop the layered style
+/// entirely with `None`. Click-through is handled separately by `set_click_through`.
+fn set_window_fade(window: &Window, alpha: Option<u8>) {
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use windows::Win32::Foundation::COLORREF;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+    
+    let hwnd = match window.window_handle().map(|h| h.as_raw()) {
+        Ok(RawWindowHandle::Win32(h)) => {
+            windows::Win32::Foundation::HWND(h.hwnd.get() as *mut _)
+        }
+        _ => return,
+    };
+    
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+        let new_style = match alpha {
+            Some(_) => ex_style | WS_EX_LAYERED.0,
+            None => ex_style & !WS_EX_LAYERED.0,
+        };
+        if new_style != ex_style {
+            SetWindowLongW(hwnd, GWL_EXSTYLE, new_style as i32);
+        }
+        if let Some(alpha) = alpha {
+            let _ = SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA);
+        }
+    }
+}
+
+thread_local! {
+    static SETTING_CHANGED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    static DISPLAY_CHANGED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Whether Windows' "Show animations in Windows" / client area animation
+/// setting is on. Defaults to on if it can't be read.
+fn client_area_animation_enabled() -> bool {
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS};
+    
+    let mut enabled = BOOL(1);
+    unsafe {
+        let _ = SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            Some(&mut enabled as *mut BOOL as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        );
+    }
+    enabled.as_bool()
+}
+
+/// Subclass the dock window so WM_SETTINGCHANGE and WM_DISPLAYCHANGE (which
+/// winit doesn't surface) set flags for `take_setting_change` and
+/// `take_display_change`.
+fn watch_setting_changes(hwnd: windows::Win32::Foundation::HWND) {
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::UI::Shell::{DefSubclassProc, SetWindowSubclass};
+    use windows::Win32::UI::WindowsAndMessaging::{WM_DISPLAYCHANGE, WM_SETTINGCHANGE};
+    
+    unsafe extern "system" fn proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM, _id: usize, _data: usize) -> LRESULT {
+        if msg == WM_SETTINGCHANGE {
+            SETTING_CHANGED.with(|c| c.set(true));
+        }
+        // Sent for resolution changes and monitors being added or removed
+        if msg == WM_DISPLAYCHANGE {
+            DISPLAY_CHANGED.with(|c| c.set(true));
+        }
+        DefSubclassProc(hwnd, msg, wparam, lparam)
+    }
+    
+    unsafe {
+        let _ = SetWindowSubclass(hwnd, Some(proc), 0x5253, 0); // "RS"
+    }
+}
+
+/// The mouse cursor in desktop coordinates
+fn cursor_position() -> Option<(i32, i32)> {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point) }.ok()?;
+    Some((point.x, point.y))
+}
+
+/// Whether the left mouse button is down right now
+fn left_button_held() -> bool {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_LBUTTON};
+    unsafe { GetAsyncKeyState(VK_LBUTTON.0 as i32) < 0 }
+}
+
+/// Whether a Shift key is down right now
+fn shift_held() -> bool {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_SHIFT};
+    unsafe { GetAsyncKeyState(VK_SHIFT.0 as i32) < 0 }
+}
+
+/// True once after a system setting changed
+fn take_setting_change() -> bool {
+    SETTING_CHANGED.with(|c| c.replace(false))
+}
+
+/// True once after the display layout changed
+fn take_display_change() -> bool {
+    DISPLAY_CHANGED.with(|c| c.replace(false))
+}
+
+/// Build the process for a `command` item: `cmd /c` by default, PowerShell for
+/// a `ps:` prefix. Multiple lines run one after another.
+fn command_line_process(command: &str) -> Command {
+    let (shell, script) = match command.trim_start().strip_prefix("ps:") {
+        Some(script) => ("powershell", script),
+        None => ("cmd", command),
+    };
+    let separator = if shell == "cmd" { " & " } else { "; " };
+    let script = script.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(separator);
+    
+    let mut cmd = Command::new(shell);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        if shell == "cmd" {
+            // Passed through untouched so cmd sees the line exactly as written
+            cmd.arg("/c").raw_arg(&script);
+        } else {
+            cmd.args(["-NoProfile", "-Command", &script]);
+        }
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    #[cfg(not(windows))]
+    cmd.arg("-c").arg(&script);
+    cmd
+}
+
+/// Fraction of the remaining distance to cover after `dt` seconds of
+/// exponential decay at `rate` per second: `1 - exp(-rate * dt)`.
+fn ease_factor(rate: f32, dt: f32) -> f32 {
+    1.0 - (-rate * dt).exp()
+}
+
+/// Frame interval for a refresh rate in millihertz, falling back to 60fps
+/// when unknown and never faster than MIN_FRAME_TIME.
+fn frame_time_for_refresh(refresh_millihertz: Option<u32>) -> Duration {
+    match refresh_millihertz {
+        Some(mhz) if mhz > 0 => Duration::from_secs_f64(1000.0 / mhz as f64).max(MIN_FRAME_TIME),
+        _ => ANIMATION_FRAME_TIME,
+    }
+}
+
+/// An instant `interval` in the past, to make a periodic check due right away
+fn time_ago(interval: Duration) -> Instant {
+    let now = Instant::now();
+    now.checked_sub(interval).unwrap_or(now)
+}
+
+/// Compare executable paths the way Windows does (case-insensitively).
+fn same_exe(a: &Path, b: &Path) -> bool {
+    a.as_os_str().to_string_lossy().eq_ignore_ascii_case(&b.as_os_str().to_string_lossy())
+}
+
+impl ApplicationHandler for DockApp {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let monitor = event_loop.primary_monitor()
+            .or_else(|| event_loop.available_monitors().next())
+            .expect("No monitor found");
+        
+        self.system_animations = client_area_animation_enabled();
+        if let Err(e) = self.open_window(event_loop, &monitor) {
+            log::error!("Failed to open the dock: {:#}", e);
+            event_loop.exit();
+            return;
+        }
+        self.running_states = vec![false; self.dock_items.len()];
+        
+        self.setup_tray();
+        self.start_watching();
+        self.update_appbar();
+        self.update_hotkey();
+        
+        // Docks for the other monitors, with `all_monitors`
+        self.update_screens(event_loop);
+    }
+    
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        // Restore taskbars when exiting
+        self.for_each_screen(Self::release_taskbar);
+        if let Some(appbar) = self.appbar.take() {
+            appbar.unregister();
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        // Another monitor's dock is swapped in while its window's event is handled
+        let slot = self.other_screens.iter()
+            .position(|screen| screen.window.as_ref().is_some_and(|window| window.id() == id));
+        if let Some(slot) = slot {
+            self.swap_screen(slot);
+        }
+        self.handle_window_event(event_loop, event);
+        if let Some(slot) = slot {
+            self.swap_screen(slot);
+        }
+    }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         // Handle tray menu
@@ -1457,57 +3545,128 @@ impl ApplicationHandler for DockApp {
                 event_loop.exit();
                 return;
             }
+            if Some(&event.id) == self.restart_id.as_ref() {
+                self.restart(event_loop);
+                return;
+            }
+            if self.compact_item.as_ref().map(|item| item.id()) == Some(&event.id) {
+                self.toggle_compact();
+            }
+            if Some(&event.id) == self.toggle_dock_id.as_ref() {
+                self.toggle_dock_visibility();
+            }
+            if self.auto_hide_item.as_ref().map(|item| item.id()) == Some(&event.id) {
+                self.toggle_auto_hide();
+            }
+            if self.presentation_item.as_ref().map(|item| item.id()) == Some(&event.id) {
+                self.toggle_presentation_mode();
+            }
+            if self.always_on_top_item.as_ref().map(|item| item.id()) == Some(&event.id) {
+                self.toggle_always_on_top();
+            }
+            if self.lock_item.as_ref().map(|item| item.id()) == Some(&event.id) {
+                self.handle_menu_action(context_menu::ContextMenuAction::ToggleLock, event_loop);
+            }
+            if Some(&event.id) == self.reload_id.as_ref() {
+                self.handle_menu_action(context_menu::ContextMenuAction::ReloadConfig, event_loop);
+            }
+            if Some(&event.id) == self.preview_config_id.as_ref() {
+                self.handle_menu_action(context_menu::ContextMenuAction::PreviewConfig, event_loop);
+            }
+            if Some(&event.id) == self.edit_config_id.as_ref() {
+                self.handle_menu_action(context_menu::ContextMenuAction::OpenConfig, event_loop);
+            }
+            self.request_redraws();
         }
         
         // Handle tray icon clicks
         if let Ok(event) = TrayIconEvent::receiver().try_recv() {
             // Check for left click event
             if matches!(event, TrayIconEvent::Click { button, .. } if matches!(button, tray_icon::MouseButton::Left)) {
-                // Show dock at cursor position when tray icon is clicked
+                // Show dock at cursor position when tray icon is clicked,
+                // on the monitor the cursor is on
+                let slot = cursor_position().and_then(|(x, y)| {
+                    self.other_screens.iter().position(|screen| screen.contains(x, y))
+                });
+                if let Some(slot) = slot {
+                    self.swap_screen(slot);
+                }
                 self.show_dock_at_cursor();
-                if let Some(window) = &self.window {
+                if let Some(window) = &self.screen.window {
                     window.request_redraw();
                 }
+                if let Some(slot) = slot {
+                    self.swap_screen(slot);
+                }
             }
         }
         
-        // Poll mouse position to detect cursor at screen edge
-        self.check_mouse_position();
+        self.answer_ipc_requests();
+        self.check_config_preview();
         
-        // Check for fullscreen apps
-        self.check_fullscreen();
+        for id in hotkey::take_pressed() {
+            if id == PRESENTATION_HOTKEY_ID {
+                self.toggle_presentation_mode();
+            }
+        }
         
-        // Re-assert topmost z-order so dock stays above other topmost windows (e.g. Warp)
-        #[cfg(windows)]
-        self.ensure_topmost();
+        // Pick up accessibility changes made while we're running
+        if take_setting_change() {
+            self.system_animations = client_area_animation_enabled();
+        }
+        if take_display_change() || std::mem::take(&mut self.screens_changed) {
+            self.update_screens(event_loop);
+        }
+
+        // Each dock polls and animates on its own; the fastest animating
+        // monitor sets the pace
+        let mut frame_time = None;
+        self.for_each_screen(|app| {
+            // Pressing or releasing Shift over an item switches its tooltip
+            if app.screen.hovered_item.is_some() && !app.screen.dragging && shift_held() != app.screen.tooltip_stats {
+                app.update_tooltip();
+            }
+
+            // Poll mouse position to detect cursor at screen edge
+            app.check_mouse_position();
+            
+            // Check for fullscreen apps
+            app.check_fullscreen();
+            
+            // Re-assert topmost z-order so dock stays above other topmost windows (e.g. Warp)
+            #[cfg(windows)]
+            app.ensure_topmost();
+            
+            if app.is_animating() {
+                if let Some(window) = &app.screen.window {
+                    window.request_redraw();
+                }
+                frame_time = Some(frame_time.map_or(app.screen.frame_time, |t: Duration| t.min(app.screen.frame_time)));
+            }
+        });
 
         // Check if we need to animate
-        let needs_animation = self.is_animating();
-        let needs_process_check = self.last_process_check.elapsed() >= PROCESS_CHECK_INTERVAL;
+        let intervals = &self.config.dock.intervals;
+        let needs_process_check = self.last_process_check.elapsed() >= intervals.process_check();
         let needs_config_check = self.last_config_poll.elapsed() >= Duration::from_millis(500);
-        let needs_mouse_check = self.last_mouse_poll.elapsed() >= MOUSE_POLL_INTERVAL;
-        let needs_fullscreen_check = self.last_fullscreen_check.elapsed() >= FULLSCREEN_CHECK_INTERVAL;
+        let needs_mouse_check = self.screens().any(|screen| screen.last_mouse_poll.elapsed() >= intervals.mouse_poll());
+        let needs_fullscreen_check = self.screens().any(|screen| screen.last_fullscreen_check.elapsed() >= intervals.fullscreen_check());
         
-        if needs_animation {
-            // Animating - run at 60fps
-            if let Some(window) = &self.window {
-                window.request_redraw();
-            }
+        if let Some(frame_time) = frame_time {
+            // Animating - run at the monitor's refresh rate
             event_loop.set_control_flow(ControlFlow::WaitUntil(
-                Instant::now() + ANIMATION_FRAME_TIME
+                Instant::now() + frame_time
             ));
-        } else if needs_process_check || needs_config_check || self.needs_reload || needs_mouse_check || needs_fullscreen_check {
+        } else if needs_process_check || needs_config_check || self.needs_reload || self.pending_reload_since.is_some() || needs_mouse_check || needs_fullscreen_check {
             // Need to check something - do it now then wait
-            if let Some(window) = &self.window {
-                window.request_redraw();
-            }
+            self.request_redraws();
             event_loop.set_control_flow(ControlFlow::WaitUntil(
                 Instant::now() + Duration::from_millis(50)
             ));
         } else {
             // Idle - wait for events, but wake up periodically to check processes and mouse
             event_loop.set_control_flow(ControlFlow::WaitUntil(
-                Instant::now() + MOUSE_POLL_INTERVAL.min(PROCESS_CHECK_INTERVAL)
+                Instant::now() + intervals.mouse_poll().min(intervals.process_check())
             ));
         }
     }
@@ -1520,42 +3679,116 @@ const DEFAULT_CONFIG_TEMPLATE: &str = r##"# ╔═══════════
 
 [dock]
 # ─── Size & Layout ───────────────────────────────────────────
-icon_size = 48                     # Icon size in pixels (default: 48)
+icon_size = 48                     # Icon size in pixels, or "5%" of the screen's shorter side (default: 48)
 spacing = 12                       # Space between icons in pixels (default: 12)
 padding = [0, 12]                  # Dock padding [horizontal, vertical] (default: [0, 12])
 negative_vertical_offset = 8       # Push dock DOWN into bottom edge in pixels (default: 8)
+edge_margin = 0                    # Float the dock this many pixels above the edge; reveal still at the edge (default: 0)
+anchor = "center"                  # Line the dock up with the "center", "left", or "right" of the screen (default: "center")
+anchor_offset_px = 0               # Move the dock this far in from its anchored edge, or right of center (default: 0)
+all_monitors = false               # A dock on every monitor, each hiding at its own bottom edge (default: false)
+overflow = "visible"               # Too many items for the screen: "visible" runs past the edges, "scroll" scrolls with the wheel, "wrap" is the same as "visible" (default: "visible")
 
 # ─── Appearance ──────────────────────────────────────────────
-background_color = "#1a1928"       # Dock background color (hex, default: #1a1928)
+background_color = "#1a1928"       # Dock background color (#rgb, #rrggbb, or #aarrggbb; default: #1a1928)
+flat_background = false            # Solid fill without the glass highlight and gradient (default: false)
+intro_animation = false            # Rise and fade in when the dock starts (default: false)
 background_opacity = 1.0           # Background transparency 0.0-1.0 (default: 1.0)
 corner_radius = 12                 # Corner roundness in pixels (default: 12)
+icon_corner_radius = 0             # Round icon corners in pixels, 0 = square (default: 0)
+separator_style = "line"           # Separator look: "line", "dot", or "space" (default: "line")
+placeholder_style = "square"       # Missing icons: "square" tile or "monogram" letter (default: "square")
+effects = "full"                   # "full", "lite" (no glow, faster scaling), or "none" (also no reflections) (default: "full")
+animated_icons = false             # Play animated .gif/.png icons; redraws continuously while shown (default: false)
+gamma_correct_blending = false     # Blend icon edges in linear light so they don't darken (default: false)
+icon_load_scale = 6                # Icons load at this multiple of icon_size for sharp magnification, 1-8 (default: 6)
+max_icon_load_px = 1024            # Cap on loaded icon resolution; lower saves memory (default: 1024)
+# separator_width = 16             # Separator slot width in pixels (default: icon_size / 3)
+# icon_theme = 'C:\Icons\MyPack'   # Folder of <name>.png / <exe>.ico icons (default: none)
 indicator_color = "#f38ba8"        # Color for running app indicators (default: #f38ba8)
+indicator_offset = 12              # Gap from icon bottom to the running indicator in px (default: 12)
 
 # ─── Behavior ────────────────────────────────────────────────
 auto_hide = true                   # Hide dock when not in use (default: true)
+reserve_space = false              # With auto_hide off, keep maximized windows above the dock (default: false)
 auto_hide_delay_ms = 250           # Delay before hiding in ms (default: 250)
 auto_show_delay_ms = 250           # Delay before showing when cursor hits edge in ms (default: 250)
+launch_cooldown_ms = 1500         # Ignore clicks on an item this long after launching it (default: 1500)
+drag_threshold_px = 5              # Mouse travel before a press becomes a drag (default: 5)
+drag_to_remove = true              # Drop an item well above the dock to remove it (default: true)
 magnification = 1.5                # Icon magnification on hover, 1.0 = no zoom (default: 1.5)
+magnification_mode = "wave"        # "wave" (neighbors grow too), "single" (hovered icon only), or "none" (default: "wave")
+fixed_hit_area = false             # Click/hover targets stay at the unmagnified size; overrides the magnified hit area (default: false)
+scroll_magnify = false             # Scrolling over the dock moves the magnified spot instead of the pointer (default: false)
 locked = true                      # Prevent drag reordering of icons (default: true)
+show_running = false               # Also show running apps that aren't pinned (default: false)
+tidy_separators = false            # Collapse doubled separators and drop ones at the ends (default: false)
+notify_on_error = false            # Show a message box when a launch fails (default: false)
+show_not_responding = false        # Amber indicator for apps that stop responding (default: false)
+hide_style = "slide"               # Auto-hide animation: "slide" off the edge or "fade" in place (default: "slide")
+reveal_feedback = "none"           # Mark the dock coming back from auto-hide: "none", "pulse" (brief flash), or "sound" (default: "none")
 
 # ─── Windows Integration ─────────────────────────────────────
-hide_windows_taskbar = true        # Hide Windows taskbar when dock is active (default: true)
+hide_windows_taskbar = true        # Hide the Windows taskbar on the dock's monitor while the dock runs (default: true)
+# taskbar_mode = "autohide"        # Or pick explicitly: "hide", "autohide" (taskbar's own auto-hide), "leave"
 hide_in_fullscreen = true          # Hide dock when fullscreen app/game is active (default: true)
+hide_when_focused = []             # Also hide while these apps are in front, e.g. ["vlc.exe"] (default: [])
+stay_visible_when_app_active = false # Don't auto-hide while a dock item's app is in front; fullscreen still hides (default: false)
+always_on_top = true               # Float above other windows; false lets apps cover the dock (default: true)
+compact = false                    # Start in compact mode (toggle from the tray menu) (default: false)
+compact_scale = 0.75               # Size multiplier in compact mode, 0.25-1.0 (default: 0.75)
+remember_compact = false           # Save the tray toggle to `compact` (default: false)
+# presentation_hotkey = "Ctrl+Alt+P" # Hotkey for presentation mode (also in the tray menu)
+# button_bindings = { 4 = "toggle_dock", 5 = "toggle_lock" } # Extra mouse buttons over the dock; actions:
+#                                  # toggle_dock, toggle_lock, toggle_auto_hide, toggle_compact,
+#                                  # presentation_mode, reload_config, open_settings, recent_apps
+# modifier_clicks = { shift = "new_instance", ctrl = "open_location", alt = "edit" }
+#                                  # Clicks with a modifier held (defaults shown); also "launch" = plain click
+# intervals = { process_check_ms = 5000, mouse_poll_ms = 50, taskbar_check_ms = 1000, fullscreen_check_ms = 500 }
+#                                  # Polling periods (defaults shown): raise them on battery, lower for snappier indicators
+# include = ["work.toml", "games.toml"] # Add the [[items]] of these files after this file's
+#                                  # (paths relative to this file); edit those items in their own files
+trigger_distance_px = 2            # Reveal zone at the bottom edge in pixels, 0-50, 0 = off (default: 2)
+# hot_corner = "bottom-left"       # Also reveal from a corner: "bottom-left" or "bottom-right"
 
 # ═══════════════════════════════════════════════════════════
 # Dock Items
 # ═══════════════════════════════════════════════════════════
 # Each [[items]] section defines an application in the dock.
 # Required: name, path (for apps) OR special (for system items)
-# Optional: icon, args (command line arguments)
+# Optional: icon, args (command line arguments),
+#           match_pattern (glob for the running indicator, e.g. 'C:\Apps\Foo\*\foo.exe'),
+#           tooltip (hover text instead of the name),
+#           badge (short text in a pill on the icon's corner, e.g. "3"),
+#           hidden (true keeps the item in the config but out of the dock),
+#           confirm (Yes/No question asked before launching),
+#           command (command line run via cmd /c instead of path; "ps:" prefix = PowerShell),
+#           [[items.children]] (makes the item a group that opens a flyout of these items)
 #
-# Special items: file_explorer, settings, recycle_bin, show_desktop,
-#                task_view, action_center, control_panel, run_dialog
+# Special items: file_explorer, settings, recycle_bin, show_desktop, toggle_desktop,
+#                task_view, action_center, control_panel, run_dialog,
+#                recent_apps
 #
 # To add a visual separator between icons:
 # [[items]]
 # separator = true
 #
+# Give a separator a name to label the section that follows it:
+# [[items]]
+# name = "Work"
+# separator = true
+#
+# Add region = true for a wider gap, with the rest of the dock on a lighter band
+# (each further region separator switches the band back and forth):
+# [[items]]
+# separator = true
+# region = true
+#
+# Or leave an empty gap of any width, with no divider:
+# [[items]]
+# name = "---"
+# spacer = 40
+#
 # Drag items to reorder them (unless locked = true above)
 # ═══════════════════════════════════════════════════════════
 
@@ -1641,15 +3874,105 @@ fn get_config_dir() -> std::path::PathBuf {
     std::path::PathBuf::from(home).join(".config").join("rdock")
 }
 
+/// %APPDATA%\rDock, used with `--appdata` or when the default dir is read-only
+fn get_appdata_config_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(|appdata| PathBuf::from(appdata).join("rDock"))
+}
+
+/// Whether config can be written into `dir`, creating it if needed
+fn is_dir_writable(dir: &Path) -> bool {
+    if std::fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(".rdock-write-test");
+    let writable = std::fs::write(&probe, b"").is_ok();
+    let _ = std::fs::remove_file(&probe);
+    writable
+}
+
+/// Pick the config directory: %APPDATA%\rDock when asked for (or when the
+/// default location can't be written), otherwise the default.
+fn resolve_config_dir(use_appdata: bool) -> PathBuf {
+    let default_dir = get_config_dir();
+    if !use_appdata && is_dir_writable(&default_dir) {
+        return default_dir;
+    }
+    match get_appdata_config_dir() {
+        Some(dir) => {
+            if !use_appdata {
+                log::warn!("{} is not writable, using {}", default_dir.display(), dir.display());
+            }
+            // Carry an existing config over instead of starting from the template
+            let existing = default_dir.join("config.toml");
+            let target = dir.join("config.toml");
+            if existing.exists() && !target.exists() {
+                let _ = std::fs::create_dir_all(&dir);
+                let _ = std::fs::copy(&existing, &target);
+            }
+            dir
+        }
+        None => default_dir,
+    }
+}
+
+/// The argument following `flag` on the command line, if given
+fn arg_value(flag: &str) -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    args.find(|arg| arg == flag)?;
+    args.next().map(PathBuf::from)
+}
+
 fn main() -> Result<()> {
     env_logger::init();
 
-    // Config lives in ~/.config/rdock/config.toml
-    let config_dir = get_config_dir();
+    // Config lives in ~/.config/rdock/config.toml, or %APPDATA%\rDock with --appdata
+    let use_appdata = std::env::args().skip(1).any(|arg| arg == "--appdata");
+    let config_dir = resolve_config_dir(use_appdata);
     let config_path = config_dir.join("config.toml");
+
+    if std::env::args().skip(1).any(|arg| arg == "--doctor") {
+        doctor::run(&config_path);
+        return Ok(());
+    }
+    if std::env::args().skip(1).any(|arg| arg == "--status") {
+        doctor::attach_console();
+        print!("{}", ipc::send("status")?);
+        return Ok(());
+    }
+    if let Some(pos) = std::env::args().position(|arg| arg == "--badge") {
+        doctor::attach_console();
+        let args: Vec<String> = std::env::args().skip(pos + 1).take(2).collect();
+        if args.is_empty() {
+            anyhow::bail!("usage: rdock --badge <item> [text]");
+        }
+        let command = format!("badge {}", shell_words::join(&args));
+        print!("{}", ipc::send(&command)?);
+        return Ok(());
+    }
+    if let Some(bundle_path) = arg_value("--export") {
+        doctor::attach_console();
+        return bundle::export(&config_path, &bundle_path);
+    }
+    if let Some(bundle_path) = arg_value("--import") {
+        doctor::attach_console();
+        return bundle::import(&bundle_path, &config_path);
+    }
     
+    // After "Restart rDock", let the old instance finish exiting first: it
+    // restores the taskbar and gives up the IPC pipe on the way out
+    if let Some(pid) = arg_value("--restarted-from").and_then(|pid| pid.to_str()?.parse().ok()) {
+        wait_for_exit(pid, RESTART_WAIT);
+    }
+    
+    // A broken config shouldn't keep the dock from starting: run on the
+    // defaults and let the user fix the file with Edit Config
+    let mut config_error = None;
     let config = if config_path.exists() {
-        Config::load(&config_path)?
+        Config::load(&config_path).unwrap_or_else(|e| {
+            config_error = Some(e);
+            toml::from_str(DEFAULT_CONFIG_TEMPLATE)
+                .unwrap_or_else(|_| Config { dock: DockSettings::default(), items: Vec::new() })
+        })
     } else {
         // Create config directory and generate default config
         std::fs::create_dir_all(&config_dir)?;
@@ -1660,8 +3983,82 @@ fn main() -> Result<()> {
     let event_loop = EventLoop::new()?;
     event_loop.set_control_flow(ControlFlow::Wait);
 
-    let mut app = DockApp::new(config, config_path);
+    let mut app = DockApp::new(config, config_path.clone());
+    if let Some(e) = config_error {
+        log::error!("Failed to load config: {:#}", e);
+        app.config_unreadable = true;
+        show_error_message(&format!(
+            "rDock couldn't read its config file:\n{}\n\n{:#}\n\nThe dock started with the default settings and won't save changes \
+             until the file is fixed. Right-click the dock and choose Edit Config; it reloads when you save.",
+            config_path.display(), e
+        ));
+    }
     event_loop.run_app(&mut app)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigger_zone_reveals() {
+        assert_eq!(auto_hide_action(true, false, false, None), AutoHideAction::Reveal);
+        assert_eq!(auto_hide_action(true, true, true, Some(Duration::from_secs(5))), AutoHideAction::Reveal);
+    }
+
+    #[test]
+    fn leaving_the_edge_while_showing_does_not_hide() {
+        // The dock is sliding up and the cursor left the edge but isn't in it yet
+        let just_shown = Some(Duration::from_millis(50));
+        assert_eq!(auto_hide_action(false, false, true, just_shown), AutoHideAction::Stay);
+        assert_eq!(auto_hide_action(false, false, true, Some(SHOW_GRACE_PERIOD)), AutoHideAction::Hide);
+    }
+
+    #[test]
+    fn hides_only_a_shown_dock_the_cursor_is_away_from() {
+        let long_ago = Some(Duration::from_secs(5));
+        assert_eq!(auto_hide_action(false, true, true, long_ago), AutoHideAction::Stay);
+        assert_eq!(auto_hide_action(false, false, false, long_ago), AutoHideAction::Stay);
+        assert_eq!(auto_hide_action(false, false, true, long_ago), AutoHideAction::Hide);
+        assert_eq!(auto_hide_action(false, false, true, None), AutoHideAction::Hide);
+    }
+
+    /// A watcher event of `kind` on `path`
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn config_replaced_by_delete_and_recreate() {
+        use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
+        let config = Path::new(r"C:\Users\me\.rdock\config.toml");
+        // The file vanishing keeps the current config
+        assert!(!is_config_change(&event(EventKind::Remove(RemoveKind::File), r"C:\Users\me\.rdock\config.toml"), config));
+        assert!(is_config_change(&event(EventKind::Create(CreateKind::File), r"C:\Users\me\.rdock\config.toml"), config));
+        // Saved elsewhere and renamed over it
+        let renamed = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(PathBuf::from(r"C:\Users\me\.rdock\config.toml.tmp"))
+            .add_path(PathBuf::from(r"C:\Users\me\.rdock\config.toml"));
+        assert!(is_config_change(&renamed, config));
+        // Other files in the directory
+        assert!(!is_config_change(&event(EventKind::Create(CreateKind::File), r"C:\Users\me\.rdock\recent.toml"), config));
+    }
+
+    #[test]
+    fn ease_factor_matches_the_old_per_frame_steps() {
+        let frame = 1.0 / 60.0;
+        assert!((ease_factor(DOCK_EASE_RATE, frame) - 0.15).abs() < 0.001);
+        assert!((ease_factor(ICON_EASE_RATE, frame) - 0.30).abs() < 0.001);
+        assert_eq!(ease_factor(DOCK_EASE_RATE, 0.0), 0.0);
+    }
+
+    #[test]
+    fn ease_factor_is_independent_of_frame_rate() {
+        // Two 120fps frames cover as much as one 60fps frame
+        let half = ease_factor(DOCK_EASE_RATE, 1.0 / 120.0);
+        let two_halves = 1.0 - (1.0 - half) * (1.0 - half);
+        assert!((two_halves - ease_factor(DOCK_EASE_RATE, 1.0 / 60.0)).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,363 @@
+//! Recently launched apps: history kept by rDock and the popup that lists it
+//!
+//! Every app launched (or focused) from the dock is pushed to the front of a
+//! short history persisted next to the config. The `recent_apps` special item
+//! shows it as a popup menu with the apps' own icons. The same file keeps a
+//! launch count and last launch time per item, which "Sort Items > By Launch
+//! Count" orders by and Shift+hover shows in the tooltip.
+//! Group items reuse the popup for their flyout.
+
+use crate::config::{self, DockItem};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Graphics::Gdi::{
+    CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, SelectObject, BITMAPINFO,
+    BITMAPINFOHEADER, DIB_RGB_COLORS, HBITMAP,
+};
+use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
+use windows::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_SMALLICON};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+/// Most entries kept in the history
+pub const MAX_RECENT: usize = 10;
+
+/// File name of the history, stored beside config.toml
+pub const RECENT_FILE_NAME: &str = "recent.toml";
+
+const ID_RECENT_BASE: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RecentEntry {
+    pub name: String,
+    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+impl RecentEntry {
+    /// Dock item that launches this entry again
+    pub fn to_item(&self) -> DockItem {
+        let mut item = DockItem::from_exe(self.path.clone());
+        item.name = self.name.clone();
+        item.args = self.args.clone();
+        item
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct RecentApps {
+    #[serde(default)]
+    recent: Vec<RecentEntry>,
+    /// Launches per item, keyed by `launch_key`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    launches: BTreeMap<String, u64>,
+    /// Unix time (seconds) of each item's last launch, keyed by `launch_key`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    last_launched: BTreeMap<String, u64>,
+}
+
+impl RecentApps {
+    /// Load the history; a missing or unreadable file is an empty history.
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match toml::from_str::<Self>(&content) {
+            Ok(mut history) => {
+                history.recent.truncate(MAX_RECENT);
+                history
+            }
+            Err(e) => {
+                log::warn!("Ignoring unreadable recent apps file {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string(self).context("Failed to serialize recent apps")?;
+        config::write_config_file(path, &content)
+    }
+
+    pub fn entries(&self) -> &[RecentEntry] {
+        &self.recent
+    }
+
+    /// Move `item` to the front of the history. Specials, separators, and
+    /// items without a path aren't tracked. Returns true if the list changed.
+    pub fn record(&mut self, item: &DockItem) -> bool {
+        if item.is_separator() || item.special.is_some() || item.path.as_os_str().is_empty() {
+            return false;
+        }
+        let entry = RecentEntry {
+            name: item.name.clone(),
+            path: item.path.clone(),
+            args: item.args.clone(),
+        };
+        if self.recent.first() == Some(&entry) {
+            return false;
+        }
+        self.recent.retain(|e| e != &entry);
+        self.recent.insert(0, entry);
+        self.recent.truncate(MAX_RECENT);
+        true
+    }
+
+    /// Count one launch of `item`. Returns false for items that can't be
+    /// launched (separators and empty items).
+    pub fn count_launch(&mut self, item: &DockItem) -> bool {
+        let Some(key) = launch_key(item) else { return false };
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        self.last_launched.insert(key.clone(), now);
+        *self.launches.entry(key).or_default() += 1;
+        true
+    }
+
+    pub fn launch_count(&self, item: &DockItem) -> u64 {
+        launch_key(item).and_then(|key| self.launches.get(&key).copied()).unwrap_or(0)
+    }
+
+    /// When `item` was last launched from the dock, if ever
+    pub fn last_launched(&self, item: &DockItem) -> Option<SystemTime> {
+        let secs = launch_key(item).and_then(|key| self.last_launched.get(&key).copied())?;
+        Some(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    /// Tooltip line for `item`: "12 launches, last 3 hours ago"
+    pub fn launch_summary(&self, item: &DockItem) -> String {
+        let count = self.launch_count(item);
+        let launches = if count == 1 { "1 launch".to_string() } else { format!("{} launches", count) };
+        match self.last_launched(item).and_then(|t| t.elapsed().ok()) {
+            Some(ago) => format!("{}, last {}", launches, format_ago(ago)),
+            None => launches,
+        }
+    }
+}
+
+/// "just now", "5 minutes ago", "3 hours ago", "2 days ago"
+fn format_ago(ago: Duration) -> String {
+    let secs = ago.as_secs();
+    let (n, unit) = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => (secs / 60, "minute"),
+        3600..=86399 => (secs / 3600, "hour"),
+        _ => (secs / 86400, "day"),
+    };
+    format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" })
+}
+
+/// What identifies an item across edits: its special type, its command, or
+/// its (case-insensitive) path
+pub fn launch_key(item: &DockItem) -> Option<String> {
+    if item.is_separator() {
+        None
+    } else if let Some(special) = &item.special {
+        Some(format!("special:{}", special))
+    } else if let Some(command) = &item.command {
+        Some(format!("command:{}", command))
+    } else if !item.path.as_os_str().is_empty() {
+        Some(item.path.to_string_lossy().to_lowercase())
+    } else {
+        None
+    }
+}
+
+/// Show the recent apps popup at screen position (x, y).
+/// Returns the index of the chosen entry.
+pub fn show_recent_popup(hwnd: isize, x: i32, y: i32, entries: &[RecentEntry]) -> Option<usize> {
+    let apps: Vec<(&str, &Path)> = entries.iter().map(|e| (e.name.as_str(), e.path.as_path())).collect();
+    show_app_popup(hwnd, x, y, &apps, "No recent apps")
+}
+
+/// Show a popup of (name, icon file) entries at screen position (x, y), each
+/// with the shell's small icon for its file. Returns the chosen index.
+pub fn show_app_popup(hwnd: isize, x: i32, y: i32, entries: &[(&str, &Path)], empty_text: &str) -> Option<usize> {
+    unsafe {
+        let hmenu = CreatePopupMenu().ok()?;
+        let mut bitmaps = Vec::new();
+
+        if entries.is_empty() {
+            let text: Vec<u16> = format!("{}\0", empty_text).encode_utf16().collect();
+            let _ = AppendMenuW(hmenu, MF_STRING | MF_GRAYED, 0, PCWSTR(text.as_ptr()));
+        }
+        for (i, (name, icon_path)) in entries.iter().enumerate() {
+            let text: Vec<u16> = format!("{}\0", name).encode_utf16().collect();
+            let id = ID_RECENT_BASE + i as u32;
+            let _ = AppendMenuW(hmenu, MF_STRING, id as usize, PCWSTR(text.as_ptr()));
+
+            if let Some(bitmap) = small_icon_bitmap(icon_path) {
+                let info = MENUITEMINFOW {
+                    cbSize: std::mem::size_of::<MENUITEMINFOW>() as u32,
+                    fMask: MIIM_BITMAP,
+                    hbmpItem: bitmap,
+                    ..Default::default()
+                };
+                let _ = SetMenuItemInfoW(hmenu, id, false, &info);
+                bitmaps.push(bitmap);
+            }
+        }
+
+        let hwnd_handle = HWND(hwnd as *mut _);
+        let _ = SetForegroundWindow(hwnd_handle);
+        let cmd = TrackPopupMenu(
+            hmenu,
+            TPM_RETURNCMD | TPM_LEFTBUTTON | TPM_NONOTIFY | TPM_BOTTOMALIGN | TPM_CENTERALIGN,
+            x,
+            y,
+            0,
+            hwnd_handle,
+            None,
+        );
+        let _ = PostMessageW(hwnd_handle, WM_NULL, None, None);
+        let _ = DestroyMenu(hmenu);
+        for bitmap in bitmaps {
+            let _ = DeleteObject(bitmap);
+        }
+
+        let cmd_id = cmd.0 as u32;
+        if cmd_id >= ID_RECENT_BASE {
+            let index = (cmd_id - ID_RECENT_BASE) as usize;
+            (index < entries.len()).then_some(index)
+        } else {
+            None
+        }
+    }
+}
+
+/// Render the shell's small icon for `path` into a 32bpp ARGB bitmap, the
+/// format menus draw with per-pixel alpha.
+fn small_icon_bitmap(path: &Path) -> Option<HBITMAP> {
+    unsafe {
+        let wide_path: Vec<u16> = path.to_string_lossy().encode_utf16().chain(std::iter::once(0)).collect();
+        let mut info = SHFILEINFOW::default();
+        let result = SHGetFileInfoW(
+            PCWSTR(wide_path.as_ptr()),
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            Some(&mut info),
+            std::mem::size_of::<SHFILEINFOW>() as u32,
+            SHGFI_ICON | SHGFI_SMALLICON,
+        );
+        if result == 0 || info.hIcon.is_invalid() {
+            return None;
+        }
+
+        let size = GetSystemMetrics(SM_CXSMICON);
+        let bmi = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: size,
+                biHeight: -size, // top-down
+                biPlanes: 1,
+                biBitCount: 32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let hdc = CreateCompatibleDC(None);
+        let mut bits = std::ptr::null_mut();
+        let bitmap = CreateDIBSection(hdc, &bmi, DIB_RGB_COLORS, &mut bits, None, 0).ok();
+        if let Some(bitmap) = bitmap {
+            let old = SelectObject(hdc, bitmap);
+            let _ = DrawIconEx(hdc, 0, 0, info.hIcon, size, size, 0, None, DI_NORMAL);
+            SelectObject(hdc, old);
+        }
+        let _ = DeleteDC(hdc);
+        let _ = DestroyIcon(info.hIcon);
+        bitmap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str) -> DockItem {
+        DockItem::from_exe(PathBuf::from(format!(r"C:\Apps\{}.exe", name)))
+    }
+
+    fn names(history: &RecentApps) -> Vec<&str> {
+        history.entries().iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn record_moves_an_app_to_the_front() {
+        let mut history = RecentApps::default();
+        assert!(history.record(&item("a")));
+        assert!(history.record(&item("b")));
+        assert!(!history.record(&item("b")));
+        assert!(history.record(&item("a")));
+        assert_eq!(names(&history), ["a", "b"]);
+    }
+
+    #[test]
+    fn record_skips_untracked_items_and_caps_the_list() {
+        let mut history = RecentApps::default();
+        let mut special = item("s");
+        special.special = Some("settings".to_string());
+        assert!(!history.record(&special));
+        assert!(!history.record(&DockItem::new_separator()));
+
+        for i in 0..MAX_RECENT + 2 {
+            history.record(&item(&i.to_string()));
+        }
+        assert_eq!(history.entries().len(), MAX_RECENT);
+        assert_eq!(history.entries()[0].name, (MAX_RECENT + 1).to_string());
+    }
+
+    #[test]
+    fn count_launch_counts_per_item() {
+        let mut history = RecentApps::default();
+        assert!(history.count_launch(&item("a")));
+        assert!(history.count_launch(&item("a")));
+        assert!(!history.count_launch(&DockItem::new_separator()));
+        assert_eq!(history.launch_count(&item("a")), 2);
+        assert_eq!(history.launch_count(&item("b")), 0);
+        assert!(history.last_launched(&item("a")).is_some());
+        assert_eq!(history.last_launched(&item("b")), None);
+        assert!(history.launch_summary(&item("a")).starts_with("2 launches, last "));
+    }
+
+    #[test]
+    fn launch_key_identifies_items() {
+        assert_eq!(launch_key(&item("Tool")).as_deref(), Some(r"c:\apps\tool.exe"));
+        let mut special = item("Tool");
+        special.special = Some("trash".to_string());
+        assert_eq!(launch_key(&special).as_deref(), Some("special:trash"));
+        let mut command = item("Tool");
+        command.command = Some("echo hi".to_string());
+        assert_eq!(launch_key(&command).as_deref(), Some("command:echo hi"));
+        assert_eq!(launch_key(&DockItem::new_separator()), None);
+        assert_eq!(launch_key(&DockItem::from_exe(PathBuf::new())), None);
+    }
+
+    #[test]
+    fn format_ago_picks_the_largest_unit() {
+        assert_eq!(format_ago(Duration::from_secs(59)), "just now");
+        assert_eq!(format_ago(Duration::from_secs(60)), "1 minute ago");
+        assert_eq!(format_ago(Duration::from_secs(5 * 60 + 30)), "5 minutes ago");
+        assert_eq!(format_ago(Duration::from_secs(3600)), "1 hour ago");
+        assert_eq!(format_ago(Duration::from_secs(86399)), "23 hours ago");
+        assert_eq!(format_ago(Duration::from_secs(2 * 86400)), "2 days ago");
+    }
+
+    #[test]
+    fn history_survives_save() {
+        let path = std::env::temp_dir().join(format!("rdock_test_recent_{}.toml", std::process::id()));
+        let mut history = RecentApps::default();
+        history.record(&item("a"));
+        history.count_launch(&item("a"));
+        history.save(&path).unwrap();
+
+        let loaded = RecentApps::load(&path);
+        assert_eq!(names(&loaded), ["a"]);
+        assert_eq!(loaded.launch_count(&item("a")), 1);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("toml.bak"));
+    }
+}
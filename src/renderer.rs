@@ -1,7 +1,12 @@
-use crate::config::{parse_hex_color, parse_hex_rgb, Config, DockItem, Spacing, ItemSpacing};
+use crate::config::{parse_hex_color, parse_hex_rgb, Config, DockItem, DockSettings, Effects, PlaceholderStyle, SeparatorStyle, Spacing, ItemSpacing};
+use crate::glyphs;
+use crate::text;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
 
 #[cfg(windows)]
 use windows::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON, ExtractIconExW};
@@ -21,19 +26,72 @@ pub struct Renderer {
     pub negative_vertical_offset: i32,
     pub corner_radius: u32,
     pub bg_color: u32,
+    /// Solid background fill (`flat_background`)
+    pub flat_background: bool,
     pub indicator_color: (u8, u8, u8),
+    pub indicator_offset: u32,
+    pub separator_style: SeparatorStyle,
+    pub separator_slot: u32,
+    pub icon_corner_radius: u32,
+    pub placeholder_style: PlaceholderStyle,
+    pub effects: Effects,
+    /// Composite icons with `alpha_blend_linear` (`gamma_correct_blending`)
+    pub gamma_correct_blending: bool,
+    /// `hit_test` ignores `scales` (`fixed_hit_area`)
+    pub fixed_hit_area: bool,
+    /// How far the reveal pulse has brightened the background, 0 to 1
+    pub pulse: f32,
+    /// First item shown while only `visible_items` fit (`overflow = "scroll"`)
+    pub scroll: usize,
+    /// How many items fit after `fit_width` narrowed the dock; None shows all
+    visible_items: Option<usize>,
+    /// Side length of every buffer in `icons`
+    icon_src_size: u32,
     icons: HashMap<PathBuf, Vec<u32>>,
+    /// `draw_background` output, copied in at the start of every frame
+    background: Vec<u32>,
+    /// Animated GIF/PNG icons (`animated_icons`), keyed like `icons`
+    animations: HashMap<PathBuf, AnimatedIcon>,
+    /// Why an item's icon file or executable gave no icon, keyed by that path.
+    /// Cleared with the renderer, so every reload tries again.
+    icon_errors: HashMap<PathBuf, String>,
 }
 
+/// Frames of an animated icon, each `icon_src_size` square, and where
+/// playback is
+struct AnimatedIcon {
+    frames: Vec<Vec<u32>>,
+    delays: Vec<Duration>,
+    current: usize,
+    elapsed: Duration,
+}
+
+/// Frames kept per animated icon; each one costs as much as a still icon
+const MAX_ANIMATION_FRAMES: usize = 120;
+
+/// White laid over the background of alternate regions (`region = true`)
+const REGION_TINT: u32 = 0x14ffffff;
+
+/// How far toward white the reveal pulse takes the background at its peak
+const PULSE_LIGHTEN: f32 = 0.25;
+
 impl Renderer {
     pub fn new(config: &Config, items: &[DockItem]) -> Result<Self> {
-        let icon_size = config.dock.icon_size;
+        let icon_size = config.dock.icon_size.pixels();
         let spacing = config.dock.spacing.clone();
         let padding = config.dock.padding.clone();
         
         // Calculate dock dimensions
         let num_items = items.len() as u32;
-        let mag_extra_width = (icon_size as f32 * 0.4) as u32;
+        // Region separators take a second divider's width on top of their slot
+        let region_extra = items.iter().filter(|item| item.is_region()).count() as u32
+            * config.dock.separator_slot();
+        // Spacers wider than an icon need the difference
+        let spacer_extra: u32 = items.iter()
+            .filter_map(|item| item.spacer)
+            .map(|width| width.saturating_sub(icon_size))
+            .sum();
+        let mag_extra_width = (icon_size as f32 * 0.4) as u32 + region_extra + spacer_extra;
         let reflection_h = (icon_size as f32 * 0.2) as u32;
         let width = if num_items > 0 {
             (num_items * icon_size) + ((num_items - 1) * spacing.x) + padding.left + padding.right + mag_extra_width
@@ -51,16 +109,32 @@ impl Renderer {
             icon_size,
             spacing,
             padding,
-            negative_vertical_offset: config.dock.negative_vertical_offset,
+            negative_vertical_offset: config.dock.edge_offset(),
             corner_radius: config.dock.corner_radius,
             bg_color,
+            flat_background: config.dock.flat_background,
             indicator_color,
+            indicator_offset: config.dock.indicator_offset,
+            separator_style: config.dock.separator_style,
+            separator_slot: config.dock.separator_slot(),
+            icon_corner_radius: config.dock.icon_corner_radius,
+            placeholder_style: config.dock.placeholder_style,
+            effects: config.dock.effects,
+            gamma_correct_blending: config.dock.gamma_correct_blending,
+            fixed_hit_area: config.dock.fixed_hit_area,
+            pulse: 0.0,
+            scroll: 0,
+            visible_items: None,
+            icon_src_size: config.dock.icon_load_size(),
             icons: HashMap::new(),
+            background: Vec::new(),
+            animations: HashMap::new(),
+            icon_errors: HashMap::new(),
         };
+        renderer.refresh_background();
 
-        // Pre-load icons at very high resolution for quality scaling
-        // Use 6x size to ensure we have enough data for sharp rendering
-        let base_load_size = (icon_size * 6).max(384);
+        // Pre-load icons at high resolution for quality scaling
+        let base_load_size = renderer.icon_src_size;
         for item in items {
             // Skip separators
             if item.is_separator() {
@@ -69,25 +143,56 @@ impl Renderer {
             
             // Try custom icon first
             if let Some(icon_path) = &item.icon {
-                if let Ok(pixels) = renderer.load_icon(icon_path, base_load_size) {
-                    renderer.icons.insert(icon_path.clone(), pixels);
-                    continue;
+                if config.dock.animated_icons {
+                    if let Some(animation) = load_animated_icon(icon_path, base_load_size) {
+                        renderer.animations.insert(icon_path.clone(), animation);
+                        continue;
+                    }
+                }
+                match renderer.load_icon(icon_path, base_load_size) {
+                    Ok(pixels) => {
+                        if renderer.insert_icon(icon_path.clone(), pixels) {
+                            continue;
+                        }
+                    }
+                    Err(e) => renderer.record_icon_error(icon_path, format!("{:#}", e)),
+                }
+            }
+
+            // Then the icon theme, stored under the key the item would use anyway
+            if let (Some(theme), Some(key)) = (&config.dock.icon_theme, Self::get_icon_key(item)) {
+                if let Some(theme_icon) = find_theme_icon(theme, item) {
+                    match renderer.load_icon(&theme_icon, base_load_size) {
+                        Ok(pixels) => {
+                            if renderer.insert_icon(key, pixels) {
+                                continue;
+                            }
+                        }
+                        Err(e) => log::warn!("Skipping theme icon: {:#}", e),
+                    }
                 }
             }
             
-            // Try to get icon for special items
+            // Try to get icon for special items, drawing the built-in one
+            // if Windows doesn't have it
             if let Some(special) = &item.special {
-                if let Some(pixels) = renderer.load_special_icon(special, base_load_size) {
+                let pixels = renderer.load_special_icon(special, base_load_size)
+                    .or_else(|| glyphs::special_glyph(special, base_load_size));
+                if let Some(pixels) = pixels {
                     // Use special name as key
-                    renderer.icons.insert(PathBuf::from(format!("special:{}", special)), pixels);
-                    continue;
+                    if renderer.insert_icon(PathBuf::from(format!("special:{}", special)), pixels) {
+                        continue;
+                    }
                 }
             }
             
             // Try to extract icon from executable path
             if !item.path.as_os_str().is_empty() && item.path.exists() {
-                if let Some(pixels) = renderer.extract_exe_icon(&item.path, base_load_size) {
-                    renderer.icons.insert(item.path.clone(), pixels);
+                match renderer.extract_exe_icon(&item.path, base_load_size) {
+                    Some(pixels) => {
+                        renderer.insert_icon(item.path.clone(), pixels);
+                    }
+                    None => renderer.record_icon_error(&item.path, "the shell returned no icon".to_string()),
                 }
             }
         }
@@ -95,6 +200,151 @@ impl Renderer {
         Ok(renderer)
     }
     
+    /// Store a loaded icon, refusing buffers that aren't `icon_src_size` square:
+    /// the draw functions sample with that stride and would read garbage.
+    fn insert_icon(&mut self, key: PathBuf, pixels: Vec<u32>) -> bool {
+        let expected = (self.icon_src_size * self.icon_src_size) as usize;
+        if pixels.len() != expected {
+            self.record_icon_error(&key, format!("{} pixels, expected {}", pixels.len(), expected));
+            return false;
+        }
+        self.icons.insert(key, pixels);
+        true
+    }
+
+    fn record_icon_error(&mut self, key: &Path, error: String) {
+        log::warn!("Skipping icon {}: {}", key.display(), error);
+        self.icon_errors.insert(key.to_path_buf(), error);
+    }
+
+    /// Why `item` draws the placeholder, if its icon file or executable was
+    /// tried and failed
+    pub fn icon_error(&self, item: &DockItem) -> Option<&str> {
+        if self.has_icon(item) {
+            return None;
+        }
+        item.icon.iter().chain(Some(&item.path))
+            .find_map(|key| self.icon_errors.get(key))
+            .map(String::as_str)
+    }
+
+    /// Update colors and shape from dock settings without reloading icons.
+    /// Settings that change the dock's dimensions need a full rebuild instead.
+    pub fn apply_style(&mut self, dock: &DockSettings) {
+        let background = (self.bg_color, self.corner_radius, self.flat_background);
+        self.bg_color = parse_hex_color(&dock.background_color, dock.background_opacity);
+        self.flat_background = dock.flat_background;
+        self.indicator_color = parse_hex_rgb(&dock.indicator_color);
+        self.indicator_offset = dock.indicator_offset;
+        self.corner_radius = dock.corner_radius;
+        self.negative_vertical_offset = dock.edge_offset();
+        self.separator_style = dock.separator_style;
+        self.icon_corner_radius = dock.icon_corner_radius;
+        self.placeholder_style = dock.placeholder_style;
+        self.effects = dock.effects;
+        self.gamma_correct_blending = dock.gamma_correct_blending;
+        self.fixed_hit_area = dock.fixed_hit_area;
+        // The settings preview calls this on every change; most don't touch the background
+        if (self.bg_color, self.corner_radius, self.flat_background) != background {
+            self.refresh_background();
+        }
+    }
+
+    /// Narrow a dock wider than `max_width` to the whole items that fit, for
+    /// `overflow = "scroll"`; the rest are reached with `scroll_by`
+    pub fn fit_width(&mut self, max_width: u32, items: &[DockItem]) {
+        if self.width <= max_width || items.is_empty() {
+            return;
+        }
+        // Room for magnification, like `new`, then the row at rest, with
+        // separators and spacers as wide as `render` lays them out
+        let extra = self.padding.left + self.padding.right + (self.icon_size as f32 * 0.4) as u32;
+        let room = max_width.saturating_sub(extra);
+        let widths: Vec<u32> = items.iter()
+            .map(|item| if item.is_separator() { self.separator_width(item).ceil() as u32 } else { self.icon_size })
+            .collect();
+        let spacing = self.spacing.x;
+        let run = |first: usize, count: usize| -> u32 {
+            widths[first..first + count].iter().sum::<u32>() + (count as u32 - 1) * spacing
+        };
+        // The most items that fit wherever the scrolled-to run starts
+        let mut count = widths.len();
+        while count > 1 && (0..=widths.len() - count).any(|first| run(first, count) > room) {
+            count -= 1;
+        }
+        let widest = (0..=widths.len() - count).map(|first| run(first, count)).max().unwrap_or(0);
+        self.width = widest + extra;
+        self.visible_items = Some(count);
+        self.refresh_background();
+    }
+
+    /// Indices of the items laid out: all of them, or the scrolled-to run
+    /// when the dock was narrowed by `fit_width`
+    fn visible_range(&self, len: usize) -> Range<usize> {
+        match self.visible_items {
+            Some(count) if count < len => {
+                let first = self.scroll.min(len - count);
+                first..first + count
+            }
+            _ => 0..len,
+        }
+    }
+
+    /// First item laid out at the left edge
+    pub fn first_visible(&self, len: usize) -> usize {
+        self.visible_range(len).start
+    }
+
+    /// Scroll `steps` items right (negative: left), stopping at either end.
+    /// Returns false if nothing moved.
+    pub fn scroll_by(&mut self, steps: i32, len: usize) -> bool {
+        let Some(count) = self.visible_items else { return false };
+        let last = len.saturating_sub(count);
+        let first = self.scroll.min(last);
+        self.scroll = (first as i64 + steps as i64).clamp(0, last as i64) as usize;
+        self.scroll != first
+    }
+
+    /// Redraw the cached background; it only changes with the dock's size,
+    /// color, corner radius, and `flat_background`.
+    fn refresh_background(&mut self) {
+        let mut background = vec![0; (self.width * self.height) as usize];
+        self.draw_background(&mut background, self.width as usize, self.height as usize);
+        self.background = background;
+    }
+
+    /// Whether an icon was loaded for `item` (false means it draws the placeholder)
+    pub fn has_icon(&self, item: &DockItem) -> bool {
+        Self::get_icon_key(item).is_some_and(|key| self.icon_pixels(&key).is_some())
+    }
+
+    /// Pixels to draw for an icon key: the current frame for animated icons
+    fn icon_pixels(&self, key: &Path) -> Option<&[u32]> {
+        if let Some(animation) = self.animations.get(key) {
+            return Some(&animation.frames[animation.current]);
+        }
+        self.icons.get(key).map(Vec::as_slice)
+    }
+
+    /// Whether any animated icon was loaded, so the caller keeps rendering
+    pub fn has_animated_icons(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    /// Move animated icons on by `delta`. Returns true when any frame changed.
+    pub fn advance_animations(&mut self, delta: Duration) -> bool {
+        let mut changed = false;
+        for animation in self.animations.values_mut() {
+            animation.elapsed += delta;
+            while animation.elapsed >= animation.delays[animation.current] {
+                animation.elapsed -= animation.delays[animation.current];
+                animation.current = (animation.current + 1) % animation.frames.len();
+                changed = true;
+            }
+        }
+        changed
+    }
+
     /// Get the icon key for an item (for looking up in the icons HashMap)
     pub fn get_icon_key(item: &DockItem) -> Option<PathBuf> {
         // Custom icon path takes priority
@@ -109,6 +359,10 @@ impl Renderer {
         if !item.path.as_os_str().is_empty() {
             return Some(item.path.clone());
         }
+        // Pathless items (commands) can still get an icon from the theme
+        if !item.name.is_empty() {
+            return Some(PathBuf::from(format!("name:{}", item.name)));
+        }
         None
     }
     
@@ -139,12 +393,14 @@ impl Renderer {
             "downloads" => (r"C:\Windows\System32\shell32.dll", 3), // Folder (downloads)
             "network" => (r"C:\Windows\System32\shell32.dll", 17), // Network neighborhood
             "run_dialog" => (r"C:\Windows\System32\shell32.dll", 24), // Run
-            "show_desktop" => (r"C:\Windows\System32\shell32.dll", 34), // Desktop
+            "show_desktop" | "toggle_desktop" => (r"C:\Windows\System32\shell32.dll", 34), // Desktop
             "task_view" => (r"C:\Windows\System32\shell32.dll", 15), // Use computer icon for task view
             "action_center" | "notification_center" => (r"C:\Windows\System32\shell32.dll", 13), // Notifications/info
             "quick_settings" => (r"C:\Windows\System32\shell32.dll", 21), // Settings/config
             "start_menu" => (r"C:\Windows\System32\shell32.dll", 319), // Windows logo
             "system_tray" => (r"C:\Windows\System32\shell32.dll", 43), // Tray/folder with star
+            "recent_apps" => (r"C:\Windows\System32\shell32.dll", 20), // Recent documents
+            "app_launcher" => (r"C:\Windows\System32\shell32.dll", 22), // Search
             _ => return None,
         };
         
@@ -380,52 +636,61 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         
         img = img.resize_exact(size, size, filter);
 
-        let mut rgba = img.to_rgba8();
-        
-        // Apply subtle sharpening to improve edge clarity
-        rgba = sharpen_image(rgba, 0.3);
-        
-        let pixels: Vec<u32> = rgba
-            .chunks_exact(4)
-            .map(|c| {
-                let a = c[3] as u32;
-                let r = c[0] as u32;
-                let g = c[1] as u32;
-                let b = c[2] as u32;
-                (a << 24) | (r << 16) | (g << 8) | b
-            })
-            .collect();
-        
-        Ok(pixels)
+        Ok(to_icon_pixels(img.to_rgba8()))
     }
 
+    /// Draw the dock into `buffer`, `width` x `height` ARGB pixels. `running`,
+    /// `hung` (not responding), and `scales` (magnification, 1.0 = normal) are
+    /// indexed like `items`; missing entries count as false and 1.0.
     /// drag_state: Option<(from_idx, to_idx, cursor_x)>
-    pub fn render(&self, buffer: &mut [u32], items: &[DockItem], running: &[bool], _hovered: Option<usize>, scales: &[f32], drag_state: Option<(usize, usize, f32)>) {
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(&self, buffer: &mut [u32], items: &[DockItem], running: &[bool], hung: &[bool], _hovered: Option<usize>, scales: &[f32], drag_state: Option<(usize, usize, f32)>) {
         let width = self.width as usize;
         let height = self.height as usize;
 
-        buffer.fill(0);
-
-        // Draw background
-        self.draw_background(buffer, width, height);
+        // Start from the cached background
+        if buffer.len() == self.background.len() {
+            buffer.copy_from_slice(&self.background);
+        } else {
+            buffer.fill(0);
+            self.draw_background(buffer, width, height);
+        }
+        if self.pulse > 0.0 {
+            self.draw_pulse(buffer);
+        }
 
         // Extract drag info
         let (drag_from, drag_to, drag_cursor_x) = drag_state.unwrap_or((usize::MAX, usize::MAX, -1000.0));
         let is_dragging = drag_state.is_some();
+        // drag_to indexes the full item list; convert it to a position among
+        // the items still laid out (the dragged one is lifted out)
+        let drop_slot = if drag_to > drag_from { drag_to - 1 } else { drag_to };
+        let range = self.visible_range(items.len());
+        // Slots count from the first item laid out
+        let drop_slot = drop_slot.saturating_sub(range.start);
 
-        // First pass: calculate total width with current scales to center properly
+        // First pass: calculate total width with current scales to center properly,
+        // noting where each region starts (the middle of its separator)
         let mut total_width: f32 = 0.0;
-        for i in 0..items.len() {
+        let mut region_starts: Vec<f32> = Vec::new();
+        let mut laid_out = 0;
+        for i in range.clone() {
             if is_dragging && i == drag_from {
                 continue; // Don't count dragged item in normal layout
             }
             let scale = scales.get(i).copied().unwrap_or(1.0);
+            if items[i].is_region() {
+                // The drop gap opens before the item at drop_slot
+                let drop_gap = if is_dragging && laid_out >= drop_slot { self.spacing.x as f32 } else { 0.0 };
+                region_starts.push(total_width + drop_gap + self.separator_width(&items[i]) / 2.0);
+            }
+            laid_out += 1;
             if items[i].is_separator() {
-                total_width += (self.icon_size / 3) as f32;
+                total_width += self.separator_width(&items[i]);
             } else {
                 total_width += self.icon_size as f32 * scale;
             }
-            if i < items.len() - 1 {
+            if i < range.end - 1 {
                 total_width += self.spacing.x as f32;
             }
         }
@@ -439,22 +704,30 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         let start_x = (self.width as f32 - total_width) / 2.0;
         let base_y = self.padding.top as f32;
         
+        // Tint every other region, starting with the one after the first
+        // region separator; the last one runs to the dock's edge
+        for (k, start) in region_starts.iter().enumerate().step_by(2) {
+            let from = (start_x + start).max(0.0) as usize;
+            let to = region_starts.get(k + 1).map_or(width, |end| (start_x + end).max(0.0) as usize);
+            self.draw_region_tint(buffer, width, height, from, to.min(width));
+        }
+        
         let mut x_pos = start_x;
         
         // Store icon positions for reflection pass
-        let mut icon_draws: Vec<(u32, u32, u32, &Vec<u32>, u32)> = Vec::new();
+        let mut icon_draws: Vec<(u32, u32, u32, &[u32], u32)> = Vec::new();
         
         // Track position for drop indicator
         let mut rendered_count = 0;
         
-        for (i, item) in items.iter().enumerate() {
+        for (i, item) in items.iter().enumerate().take(range.end).skip(range.start) {
             // Skip the dragged item in normal rendering
             if is_dragging && i == drag_from {
                 continue;
             }
             
             // Insert gap at drop position
-            if is_dragging && rendered_count == drag_to && drag_to != drag_from {
+            if is_dragging && rendered_count == drop_slot {
                 // Draw drop indicator line
                 self.draw_drop_indicator(buffer, width, x_pos as u32, self.padding.top, self.icon_size);
                 x_pos += self.spacing.x as f32;
@@ -468,42 +741,62 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
             let x = x_pos as u32;
             let y = (base_y - y_lift).max(2.0) as u32;
             
-            // Check if this is a separator
+            // Check if this is a separator; a region's gap and tint divide it
+            // already, and a spacer is only a gap
             if item.is_separator() {
-                self.draw_separator(buffer, width, x, self.padding.top, self.icon_size);
-                x_pos += (self.icon_size / 3) as f32 + self.spacing.x as f32;
+                if !item.is_region() && item.spacer.is_none() {
+                    self.draw_separator(buffer, width, x, self.padding.top, self.icon_size);
+                }
+                if let Some(label) = item.separator_label() {
+                    self.draw_separator_label(buffer, width, x + self.divider_width(item), self.padding.top, label);
+                }
+                x_pos += self.separator_width(item) + self.spacing.x as f32;
                 rendered_count += 1;
                 continue;
             }
             
             // Glow behind magnified icons
-            if scale > 1.05 {
+            if scale > 1.05 && self.effects == Effects::Full {
                 let glow_intensity = ((scale - 1.0) * 2.0).min(1.0);
                 self.draw_glow_scaled(buffer, width, x + scaled_size / 2, y + scaled_size / 2, scaled_size, glow_intensity);
             }
             
             // Draw icon - use get_icon_key to find the right icon
-            let src_size = (self.icon_size * 6).max(384);
+            let src_size = self.icon_src_size;
             if let Some(icon_key) = Self::get_icon_key(item) {
-                if let Some(pixels) = self.icons.get(&icon_key) {
-                    self.draw_icon_bicubic(buffer, width, pixels, src_size, x, y, scaled_size);
-                    icon_draws.push((x, y, scaled_size, pixels, src_size));
+                if let Some(pixels) = self.icon_pixels(&icon_key) {
+                    self.draw_icon(buffer, width, pixels, src_size, x, y, scaled_size);
+                    if self.effects != Effects::None {
+                        icon_draws.push((x, y, scaled_size, pixels, src_size));
+                    }
                 } else {
-                    self.draw_placeholder(buffer, width, x, y, scaled_size);
+                    self.draw_placeholder(buffer, width, x, y, scaled_size, &item.name);
                 }
             } else {
-                self.draw_placeholder(buffer, width, x, y, scaled_size);
+                self.draw_placeholder(buffer, width, x, y, scaled_size, &item.name);
+            }
+            if let Some(badge) = item.badge.as_deref().filter(|badge| !badge.is_empty()) {
+                self.draw_badge(buffer, width, x, y, scaled_size, badge);
             }
 
             // Running indicator
             if running.get(i).copied().unwrap_or(false) {
                 let ind_x = x + scaled_size / 2;
-                let ind_y = if self.negative_vertical_offset > 0 {
-                    (self.height as i32 - 5 - self.negative_vertical_offset).max(self.padding.top as i32 + self.icon_size as i32) as u32
+                let ind_y = indicator_y(
+                    self.padding.top + self.icon_size,
+                    self.indicator_offset,
+                    self.height,
+                    self.negative_vertical_offset,
+                );
+                let color = if hung.get(i).copied().unwrap_or(false) {
+                    NOT_RESPONDING_COLOR
                 } else {
-                    self.height - 5
+                    self.indicator_color
                 };
-                self.draw_indicator_glow(buffer, width, ind_x, ind_y);
+                if self.effects == Effects::Full {
+                    self.draw_indicator_glow(buffer, width, ind_x, ind_y, color);
+                }
+                self.draw_indicator_dot(buffer, width, ind_x, ind_y, color);
             }
             
             x_pos += scaled_size as f32 + self.spacing.x as f32;
@@ -511,29 +804,39 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         }
         
         // Draw drop indicator at end if needed
-        if is_dragging && drag_to >= rendered_count {
+        if is_dragging && drop_slot >= rendered_count {
             self.draw_drop_indicator(buffer, width, x_pos as u32, self.padding.top, self.icon_size);
         }
         
-        // Draw reflections (using bicubic for quality)
+        // Draw reflections (bicubic for quality unless effects are reduced)
         for (x, y, scaled_size, pixels, src_size) in icon_draws {
             let reflection_y = y + scaled_size + 2;
-            self.draw_reflection_bicubic(buffer, width, pixels, src_size, x, reflection_y, scaled_size);
+            if self.effects == Effects::Full {
+                self.draw_reflection_bicubic(buffer, width, pixels, src_size, x, reflection_y, scaled_size);
+            } else {
+                self.draw_reflection(buffer, width, pixels, src_size, x, reflection_y, scaled_size);
+            }
         }
         
         // Draw dragged icon following cursor
         if is_dragging && drag_from < items.len() {
             let item = &items[drag_from];
-            if !item.is_separator() {
+            if item.is_separator() {
+                let drag_x = (drag_cursor_x - (self.separator_slot / 2) as f32).max(0.0) as u32;
+                self.draw_separator(buffer, width, drag_x, self.padding.top, self.icon_size);
+                if let Some(label) = item.separator_label() {
+                    self.draw_separator_label(buffer, width, drag_x + self.separator_slot, self.padding.top, label);
+                }
+            } else {
                 if let Some(icon_key) = Self::get_icon_key(item) {
-                    if let Some(pixels) = self.icons.get(&icon_key) {
-                        let src_size = (self.icon_size * 6).max(384);
+                    if let Some(pixels) = self.icon_pixels(&icon_key) {
+                        let src_size = self.icon_src_size;
                         let drag_size = self.icon_size;
                         let drag_x = (drag_cursor_x - drag_size as f32 / 2.0).max(0.0) as u32;
                         let drag_y = self.padding.top;
                         
                         // Draw with slight transparency effect (draw darker/lighter)
-                        self.draw_icon_bicubic(buffer, width, pixels, src_size, drag_x, drag_y, drag_size);
+                        self.draw_icon(buffer, width, pixels, src_size, drag_x, drag_y, drag_size);
                     }
                 }
             }
@@ -558,7 +861,11 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
             };
             
             // Subtle overall gradient
-            let grad = 1.0 + (1.0 - yf) * 0.08 + top_highlight;
+            let grad = if self.flat_background {
+                1.0
+            } else {
+                1.0 + (1.0 - yf) * 0.08 + top_highlight
+            };
             let gr = (base_r * grad).min(255.0) as u32;
             let gg = (base_g * grad).min(255.0) as u32;
             let gb = (base_b * grad).min(255.0) as u32;
@@ -636,6 +943,7 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         let scale = src_size as f32 / dst_size as f32;
         let src_w = src_size as usize;
         let reflection_height = (dst_size as f32 * 0.35) as u32;
+        let radius = self.icon_radius(dst_size);
         
         for iy in 0..reflection_height.min(dst_size) {
             let fade = 1.0 - (iy as f32 / reflection_height as f32);
@@ -652,7 +960,11 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
                 let dst_idx = dst_y * buf_width + dst_x;
 
                 if dst_idx < buffer.len() {
-                    let src_alpha = (pixel >> 24) & 0xFF;
+                    let mut src_alpha = (pixel >> 24) & 0xFF;
+                    if radius > 0 && src_alpha > 0 {
+                        let coverage = rounded_rect_coverage(ix, dst_size - 1 - iy, dst_size, radius);
+                        src_alpha = (src_alpha as f32 * coverage) as u32;
+                    }
                     if src_alpha > 0 {
                         let final_alpha = (src_alpha * row_alpha / 255).min(row_alpha);
                         let r = (pixel >> 16) & 0xFF;
@@ -666,7 +978,6 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         }
     }
     
-    #[allow(dead_code)]
     #[allow(clippy::too_many_arguments)]
     fn draw_reflection(&self, buffer: &mut [u32], buf_width: usize, pixels: &[u32], src_size: u32, x: u32, y: u32, dst_size: u32) {
         let scale = src_size as f32 / dst_size as f32;
@@ -715,37 +1026,73 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         }
     }
 
+    /// Scale an icon into place with the sampler `effects` calls for
+    #[allow(clippy::too_many_arguments)]
+    fn draw_icon(&self, buffer: &mut [u32], buf_width: usize, pixels: &[u32], src_size: u32, x: u32, y: u32, dst_size: u32) {
+        if self.effects == Effects::Full {
+            self.draw_icon_bicubic(buffer, buf_width, pixels, src_size, x, y, dst_size);
+        } else {
+            self.draw_icon_bilinear(buffer, buf_width, pixels, src_size, x, y, dst_size);
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn draw_icon_bicubic(&self, buffer: &mut [u32], buf_width: usize, pixels: &[u32], src_size: u32, x: u32, y: u32, dst_size: u32) {
         let scale = src_size as f32 / dst_size as f32;
         let src_w = src_size as usize;
+        let radius = self.icon_radius(dst_size);
+        let blend = self.icon_blend();
         
         for iy in 0..dst_size {
             for ix in 0..dst_size {
                 let src_x = ix as f32 * scale;
                 let src_y = iy as f32 * scale;
                 
-                let pixel = bicubic_sample(pixels, src_w, src_x, src_y);
+                let mut pixel = bicubic_sample(pixels, src_w, src_x, src_y);
                 
                 let dst_x = x as usize + ix as usize;
                 let dst_y = y as usize + iy as usize;
                 let dst_idx = dst_y * buf_width + dst_x;
 
                 if dst_idx < buffer.len() {
-                    let alpha = (pixel >> 24) & 0xFF;
+                    let mut alpha = (pixel >> 24) & 0xFF;
+                    if radius > 0 && alpha > 0 {
+                        // Mask square icons to a rounded square
+                        let coverage = rounded_rect_coverage(ix, iy, dst_size, radius);
+                        alpha = (alpha as f32 * coverage) as u32;
+                        pixel = (alpha << 24) | (pixel & 0x00FF_FFFF);
+                    }
                     if alpha > 0 {
-                        buffer[dst_idx] = alpha_blend(buffer[dst_idx], pixel);
+                        buffer[dst_idx] = blend(buffer[dst_idx], pixel);
                     }
                 }
             }
         }
     }
     
-    #[allow(dead_code)]
+    /// How scaled icon pixels are composited onto the buffer
+    fn icon_blend(&self) -> fn(u32, u32) -> u32 {
+        if self.gamma_correct_blending {
+            alpha_blend_linear
+        } else {
+            alpha_blend
+        }
+    }
+
+    /// `icon_corner_radius` scaled to an icon drawn `dst_size` pixels wide
+    fn icon_radius(&self, dst_size: u32) -> u32 {
+        if self.icon_corner_radius == 0 {
+            return 0;
+        }
+        (self.icon_corner_radius * dst_size / self.icon_size.max(1)).min(dst_size / 2)
+    }
+    
     #[allow(clippy::too_many_arguments)]
     fn draw_icon_bilinear(&self, buffer: &mut [u32], buf_width: usize, pixels: &[u32], src_size: u32, x: u32, y: u32, dst_size: u32) {
         let scale = src_size as f32 / dst_size as f32;
         let src_w = src_size as usize;
+        let radius = self.icon_radius(dst_size);
+        let blend = self.icon_blend();
         
         for iy in 0..dst_size {
             for ix in 0..dst_size {
@@ -767,24 +1114,28 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
                 let p11 = pixels.get(y1 * src_w + x1).copied().unwrap_or(0);
                 
                 // Bilinear interpolation for each channel
-                let pixel = bilinear_blend(p00, p10, p01, p11, fx, fy);
+                let mut pixel = bilinear_blend(p00, p10, p01, p11, fx, fy);
                 
                 let dst_x = x as usize + ix as usize;
                 let dst_y = y as usize + iy as usize;
                 let dst_idx = dst_y * buf_width + dst_x;
 
                 if dst_idx < buffer.len() {
-                    let alpha = (pixel >> 24) & 0xFF;
+                    let mut alpha = (pixel >> 24) & 0xFF;
+                    if radius > 0 && alpha > 0 {
+                        let coverage = rounded_rect_coverage(ix, iy, dst_size, radius);
+                        alpha = (alpha as f32 * coverage) as u32;
+                        pixel = (alpha << 24) | (pixel & 0x00FF_FFFF);
+                    }
                     if alpha > 0 {
-                        buffer[dst_idx] = alpha_blend(buffer[dst_idx], pixel);
+                        buffer[dst_idx] = blend(buffer[dst_idx], pixel);
                     }
                 }
             }
         }
     }
 
-    fn draw_indicator_glow(&self, buffer: &mut [u32], buf_width: usize, center_x: u32, center_y: u32) {
-        let (r, g, b) = self.indicator_color;
+    fn draw_indicator_glow(&self, buffer: &mut [u32], buf_width: usize, center_x: u32, center_y: u32, (r, g, b): (u8, u8, u8)) {
         
         // Outer glow
         let glow_radius = 8i32;
@@ -809,10 +1160,12 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
                 }
             }
         }
+    }
 
-        // Solid center
+    /// Solid center of the running indicator
+    fn draw_indicator_dot(&self, buffer: &mut [u32], buf_width: usize, center_x: u32, center_y: u32, (r, g, b): (u8, u8, u8)) {
         let color = 0xFF000000 | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
-        let radius = 3i32;
+        let radius = INDICATOR_RADIUS as i32;
         for dy in -radius..=radius {
             for dx in -radius..=radius {
                 if dx * dx + dy * dy <= radius * radius {
@@ -829,7 +1182,7 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         }
     }
 
-    fn draw_placeholder(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, size: u32) {
+    fn draw_placeholder(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, size: u32, name: &str) {
         // Draw a simple rounded square placeholder for missing icons
         let (ir, ig, ib) = self.indicator_color;
         let color = 0x80000000 | ((ir as u32 / 2) << 16) | ((ig as u32 / 2) << 8) | (ib as u32 / 2);
@@ -873,17 +1226,78 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
                 }
             }
         }
+        
+        if self.placeholder_style == PlaceholderStyle::Monogram {
+            if let Some(letter) = monogram_letter(name) {
+                self.draw_monogram(buffer, buf_width, x, y, size, letter);
+            }
+        }
+    }
+    
+    /// Draw `letter` centered on a `size` placeholder tile at (x, y), as large
+    /// as the bitmap font's integer scales allow within half the tile.
+    fn draw_monogram(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, size: u32, letter: char) {
+        let letter = letter.to_string();
+        let scale = (size / 2 / text::GLYPH_HEIGHT).max(1);
+        let (text_w, text_h) = text::measure_text(&letter, scale);
+        let origin_x = x + size.saturating_sub(text_w) / 2;
+        let origin_y = y + size.saturating_sub(text_h) / 2;
+        text::draw_text(buffer, buf_width, origin_x as i32, origin_y as i32, &letter, 0xE0F0F0F0, scale);
+    }
+
+    /// Draw `badge` in a red pill over the top-right corner of a `size` icon
+    /// at (x, y). The pill grows with the icon; text past its width is cut.
+    fn draw_badge(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, size: u32, badge: &str) {
+        let scale = (size / 40).max(1);
+        let pad = (size / 16).max(2);
+        let max_chars = (size.saturating_sub(2 * pad) / (text::GLYPH_ADVANCE * scale)).max(1) as usize;
+        let badge: String = badge.chars().take(max_chars).collect();
+        let (text_w, text_h) = text::measure_text(&badge, scale);
+        let pill_h = text_h + 2 * pad;
+        // At least round, so a single digit sits in a circle
+        let pill_w = (text_w + 2 * pad).max(pill_h);
+        let left = (x + size).saturating_sub(pill_w);
+        let buf_height = buffer.len() / buf_width.max(1);
+
+        let radius = pill_h as f32 / 2.0;
+        for py in 0..pill_h {
+            for px in 0..pill_w {
+                let (bx, by) = ((left + px) as usize, (y + py) as usize);
+                if bx >= buf_width || by >= buf_height {
+                    continue;
+                }
+                // Distance from the pill's center line, ends rounded
+                let fx = px as f32 + 0.5;
+                let fy = py as f32 + 0.5;
+                let dx = fx - fx.clamp(radius, pill_w as f32 - radius);
+                let dist = (dx * dx + (fy - radius) * (fy - radius)).sqrt();
+                let coverage = (radius - dist + 0.5).clamp(0.0, 1.0);
+                if coverage > 0.0 {
+                    let idx = by * buf_width + bx;
+                    let alpha = (coverage * 255.0) as u32;
+                    buffer[idx] = alpha_blend(buffer[idx], (alpha << 24) | BADGE_COLOR);
+                }
+            }
+        }
+        let text_x = left + (pill_w - text_w) / 2;
+        text::draw_text(buffer, buf_width, text_x as i32, (y + pad) as i32, &badge, 0xFFFF_FFFF, scale);
     }
 
     fn draw_separator(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, icon_size: u32) {
+        match self.separator_style {
+            SeparatorStyle::Line => {}
+            SeparatorStyle::Dot => return self.draw_separator_dot(buffer, buf_width, x, y, icon_size),
+            SeparatorStyle::Space => return,
+        }
+        
         // Draw a subtle vertical separator line
         let (ir, ig, ib) = self.indicator_color;
         let sep_width = 2u32;
         let sep_height = (icon_size as f32 * 0.6) as u32;
         let y_offset = (icon_size - sep_height) / 2;
         
-        // Center the separator in its allocated space (icon_size / 3)
-        let sep_x = x + (icon_size / 6) - (sep_width / 2);
+        // Center the separator in its allocated slot
+        let sep_x = (x + self.separator_slot / 2).saturating_sub(sep_width / 2);
         
         for dy in 0..sep_height {
             // Fade at top and bottom
@@ -913,6 +1327,112 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         }
     }
 
+    /// Small anti-aliased dot centered in the separator slot
+    fn draw_separator_dot(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, icon_size: u32) {
+        let (ir, ig, ib) = self.indicator_color;
+        let radius = (icon_size as f32 / 24.0).max(1.5);
+        let cx = x as f32 + self.separator_slot as f32 / 2.0;
+        let cy = y as f32 + icon_size as f32 / 2.0;
+        let extent = radius.ceil() as i32 + 1;
+        
+        for dy in -extent..=extent {
+            for dx in -extent..=extent {
+                let px = cx as i32 + dx;
+                let py = cy as i32 + dy;
+                if px < 0 || py < 0 || px as usize >= buf_width {
+                    continue;
+                }
+                let dist = ((px as f32 + 0.5 - cx).powi(2) + (py as f32 + 0.5 - cy).powi(2)).sqrt();
+                let coverage = (radius - dist + 0.5).clamp(0.0, 1.0);
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let alpha = (160.0 * coverage) as u32;
+                let color = (alpha << 24) | ((ir as u32) << 16) | ((ig as u32) << 8) | (ib as u32);
+                let idx = py as usize * buf_width + px as usize;
+                if idx < buffer.len() {
+                    buffer[idx] = alpha_blend(buffer[idx], color);
+                }
+            }
+        }
+    }
+
+    /// Lighten the dock background between columns `from` and `to`, leaving
+    /// the transparent rounded corners alone.
+    fn draw_region_tint(&self, buffer: &mut [u32], buf_width: usize, height: usize, from: usize, to: usize) {
+        for y in 0..height {
+            for x in from..to {
+                let idx = y * buf_width + x;
+                let Some(&pixel) = buffer.get(idx) else { continue };
+                // Scale the tint by the background's own coverage
+                let coverage = pixel >> 24;
+                let alpha = ((REGION_TINT >> 24) * coverage / 255) << 24;
+                buffer[idx] = alpha_blend(pixel, alpha | (REGION_TINT & 0x00ffffff));
+            }
+        }
+    }
+
+    /// Gap a separator leaves before its caption: the configured slot, twice
+    /// that for region separators
+    fn divider_width(&self, item: &DockItem) -> u32 {
+        if let Some(width) = item.spacer {
+            width
+        } else if item.is_region() {
+            self.separator_slot * 2
+        } else {
+            self.separator_slot
+        }
+    }
+
+    /// Width of a separator slot: the divider, plus a column for the caption
+    /// of labeled separators.
+    fn separator_width(&self, item: &DockItem) -> f32 {
+        let divider = self.divider_width(item) as f32;
+        match item.separator_label() {
+            Some(_) => divider + (text::GLYPH_HEIGHT * self.label_scale()) as f32 + 2.0,
+            None => divider,
+        }
+    }
+    
+    fn label_scale(&self) -> u32 {
+        (self.icon_size / 48).max(1)
+    }
+    
+    /// Draw a separator caption rotated to read bottom-to-top, centered on the
+    /// icon row, truncated to fit within one icon height.
+    fn draw_separator_label(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, label: &str) {
+        let (ir, ig, ib) = self.indicator_color;
+        let color = (200u32 << 24) | ((ir as u32) << 16) | ((ig as u32) << 8) | (ib as u32);
+        let scale = self.label_scale();
+        
+        let max_chars = (self.icon_size / (text::GLYPH_ADVANCE * scale)) as usize;
+        let label: String = label.chars().take(max_chars).collect();
+        let text_w = text::text_width(&label, scale);
+        let bottom = y + (self.icon_size + text_w) / 2;
+        
+        text::for_each_pixel(&label, scale, |tx, ty| {
+            let px = x + ty;
+            let py = bottom.saturating_sub(tx + 1);
+            let idx = py as usize * buf_width + px as usize;
+            if px < buf_width as u32 && idx < buffer.len() {
+                buffer[idx] = alpha_blend(buffer[idx], color);
+            }
+        });
+    }
+
+    /// Lighten the background toward white by `pulse`, keeping its alpha so
+    /// the rounded corners stay as they are
+    fn draw_pulse(&self, buffer: &mut [u32]) {
+        let amount = (self.pulse.clamp(0.0, 1.0) * PULSE_LIGHTEN * 255.0) as u32;
+        for pixel in buffer.iter_mut().filter(|pixel| **pixel >> 24 != 0) {
+            let lighten = |shift: u32| {
+                let c = (*pixel >> shift) & 0xFF;
+                (c + div255((255 - c) * amount)) << shift
+            };
+            *pixel = (*pixel & 0xFF00_0000) | lighten(16) | lighten(8) | lighten(0);
+        }
+    }
+
     fn draw_drop_indicator(&self, buffer: &mut [u32], buf_width: usize, x: u32, y: u32, icon_size: u32) {
         // Draw a bright vertical line indicating where the dragged item will be dropped
         let (ir, ig, ib) = self.indicator_color;
@@ -947,7 +1467,51 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
         }
     }
 
+    /// Index in `items` to insert the dragged item at if dropped at `x`.
+    /// Mirrors the drag layout in `render`: the dragged item is lifted out,
+    /// a gap is reserved for the drop, and separators take their narrower slot.
+    /// With nothing dragged (`dragged` past the end, as for file drops and
+    /// "insert here") the items stay where they are drawn.
+    pub fn drop_index(&self, x: f32, items: &[DockItem], scales: &[f32], dragged: usize) -> usize {
+        let spacing = self.spacing.x as f32;
+        let range = self.visible_range(items.len());
+        let slots: Vec<(usize, f32)> = items.iter().enumerate()
+            .take(range.end)
+            .skip(range.start)
+            .filter(|(i, _)| *i != dragged)
+            .map(|(i, item)| {
+                let item_width = if item.is_separator() {
+                    self.separator_width(item)
+                } else {
+                    self.icon_size as f32 * scales.get(i).copied().unwrap_or(1.0)
+                };
+                (i, item_width)
+            })
+            .collect();
+        
+        let mut x_pos = if dragged < items.len() {
+            // Spacing between the remaining items plus one gap for the drop position
+            let total_width: f32 = slots.iter().map(|(_, w)| w).sum::<f32>() + spacing * slots.len() as f32;
+            (self.width as f32 - total_width) / 2.0
+        } else {
+            self.items_start_x(items, scales)
+        };
+        
+        // Drop before the first item whose center is right of the cursor
+        for (i, item_width) in slots {
+            if x < x_pos + item_width / 2.0 {
+                return i;
+            }
+            x_pos += item_width + spacing;
+        }
+        
+        range.end
+    }
+
+    /// The item under window position (x, y), laid out at `scales`, or at
+    /// the unmagnified size when `fixed_hit_area` is on
     pub fn hit_test(&self, x: i32, y: i32, items: &[DockItem], scales: &[f32]) -> Option<usize> {
+        let scales = if self.fixed_hit_area { &[] } else { scales };
         // Generous vertical hit area
         let extra = (self.icon_size as f32 * 0.3) as i32;
         let top = self.padding.top as i32 - extra;
@@ -957,28 +1521,12 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
             return None;
         }
 
-        // Calculate total width the same way render does, using current scales
-        let mut total_width: f32 = 0.0;
-        for (i, item) in items.iter().enumerate() {
-            if item.is_separator() {
-                total_width += (self.icon_size / 3) as f32;
-            } else {
-                let scale = scales.get(i).copied().unwrap_or(1.0);
-                total_width += self.icon_size as f32 * scale;
-            }
-            if i < items.len() - 1 {
-                total_width += self.spacing.x as f32;
-            }
-        }
-        
-        // Center the icons (matching render logic)
-        let start_x = (self.width as f32 - total_width) / 2.0;
-        
         // Walk through items and check hit areas
-        let mut x_pos = start_x;
-        for (i, item) in items.iter().enumerate() {
+        let range = self.visible_range(items.len());
+        let mut x_pos = self.items_start_x(items, scales);
+        for (i, item) in items.iter().enumerate().take(range.end).skip(range.start) {
             let item_width = if item.is_separator() {
-                (self.icon_size / 3) as f32
+                self.separator_width(item)
             } else {
                 let scale = scales.get(i).copied().unwrap_or(1.0);
                 self.icon_size as f32 * scale
@@ -986,8 +1534,8 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
             
             // Hit area is the icon itself plus half the spacing on each side (if not first/last)
             let half_spacing = self.spacing.x as f32 / 2.0;
-            let is_first = i == 0;
-            let is_last = i == items.len() - 1;
+            let is_first = i == range.start;
+            let is_last = i == range.end - 1;
             
             let hit_left = if is_first {
                 x_pos  // First icon: no extra space on left
@@ -1010,6 +1558,63 @@ fn load_icon(&self, path: &Path, size: u32) -> Result<Vec<u32>> {
 
         None
     }
+
+    /// Window-local center of item `index` at the current scales, the point
+    /// `hit_test` maps back to it
+    pub fn item_center(&self, index: usize, items: &[DockItem], scales: &[f32]) -> Option<(i32, i32)> {
+        let range = self.visible_range(items.len());
+        let mut x_pos = self.items_start_x(items, scales);
+        for (i, item) in items.iter().enumerate().take(range.end).skip(range.start) {
+            let item_width = if item.is_separator() {
+                self.separator_width(item)
+            } else {
+                self.icon_size as f32 * scales.get(i).copied().unwrap_or(1.0)
+            };
+            if i == index {
+                let y = self.padding.top + self.icon_size / 2;
+                return Some(((x_pos + item_width / 2.0) as i32, y as i32));
+            }
+            x_pos += item_width + self.spacing.x as f32;
+        }
+        None
+    }
+
+    /// Center X of each item laid out at rest (scale 1), where the
+    /// magnification wave peaks for it; None for separators and for items
+    /// scrolled out of view
+    pub fn rest_centers(&self, items: &[DockItem]) -> Vec<Option<f32>> {
+        let range = self.visible_range(items.len());
+        let mut centers = vec![None; items.len()];
+        let mut x_pos = self.items_start_x(items, &[]);
+        for (i, item) in items.iter().enumerate().take(range.end).skip(range.start) {
+            let item_width = if item.is_separator() {
+                self.separator_width(item)
+            } else {
+                centers[i] = Some(x_pos + self.icon_size as f32 / 2.0);
+                self.icon_size as f32
+            };
+            x_pos += item_width + self.spacing.x as f32;
+        }
+        centers
+    }
+
+    /// Left edge of the first item, with the row centered the way render does
+    fn items_start_x(&self, items: &[DockItem], scales: &[f32]) -> f32 {
+        let range = self.visible_range(items.len());
+        let mut total_width: f32 = 0.0;
+        for (i, item) in items.iter().enumerate().take(range.end).skip(range.start) {
+            if item.is_separator() {
+                total_width += self.separator_width(item);
+            } else {
+                let scale = scales.get(i).copied().unwrap_or(1.0);
+                total_width += self.icon_size as f32 * scale;
+            }
+            if i < range.end - 1 {
+                total_width += self.spacing.x as f32;
+            }
+        }
+        (self.width as f32 - total_width) / 2.0
+    }
 }
 
 #[allow(dead_code)]
@@ -1033,8 +1638,12 @@ fn bilinear_blend(p00: u32, p10: u32, p01: u32, p11: u32, fx: f32, fy: f32) -> u
     (a << 24) | (r << 16) | (g << 8) | b
 }
 
+/// Composite `src` over `dst`, both straight-alpha ARGB. Every channel is
+/// within 1 of the exact result. This runs for nearly every pixel of every
+/// frame, so the divisions by 255 are multiply-and-shift, and a translucent
+/// `dst` costs one reciprocal shared by the three color channels.
 #[inline(always)]
-fn alpha_blend(dst: u32, src: u32) -> u32 {
+pub fn alpha_blend(dst: u32, src: u32) -> u32 {
     let sa = (src >> 24) & 0xFF;
     if sa == 0 {
         return dst;
@@ -1050,17 +1659,101 @@ fn alpha_blend(dst: u32, src: u32) -> u32 {
     let dr = (dst >> 16) & 0xFF;
     let dg = (dst >> 8) & 0xFF;
     let db = dst & 0xFF;
+    let inv_sa = 255 - sa;
+
+    // Opaque destination (most of the dock background): plain lerp
+    if da == 255 {
+        let out_r = div255(sr * sa + dr * inv_sa);
+        let out_g = div255(sg * sa + dg * inv_sa);
+        let out_b = div255(sb * sa + db * inv_sa);
+        return 0xFF00_0000 | (out_r << 16) | (out_g << 8) | out_b;
+    }
+
+    // Destination weight, scaled by 255
+    let dst_weight = da * inv_sa;
+    let out_a = sa + div255(dst_weight);
+    // Each channel is (s·sa·255 + d·dst_weight) / (sa·255 + dst_weight), rounded
+    let recip = (1u64 << 40) / (sa * 255 + dst_weight) as u64;
+    let channel = |s: u32, d: u32| (((s * sa * 255 + d * dst_weight) as u64 * recip + (1 << 39)) >> 40) as u32;
+
+    (out_a << 24) | (channel(sr, dr) << 16) | (channel(sg, dg) << 8) | channel(sb, db)
+}
+
+/// sRGB value to linear light scaled to 0..=65535, and linear light scaled
+/// to 0..=4095 back to sRGB. Built on first use by `alpha_blend_linear`.
+struct GammaTables {
+    to_linear: [u16; 256],
+    to_srgb: [u8; 4096],
+}
+
+fn gamma_tables() -> &'static GammaTables {
+    static TABLES: OnceLock<GammaTables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = GammaTables { to_linear: [0; 256], to_srgb: [0; 4096] };
+        for (i, v) in tables.to_linear.iter_mut().enumerate() {
+            *v = (srgb_to_linear(i as f64 / 255.0) * 65535.0).round() as u16;
+        }
+        for (i, v) in tables.to_srgb.iter_mut().enumerate() {
+            *v = (linear_to_srgb(i as f64 / 4095.0) * 255.0).round() as u8;
+        }
+        tables
+    })
+}
+
+/// The sRGB transfer function, on values in 0..=1
+pub fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
 
-    let out_a = sa + da * (255 - sa) / 255;
-    if out_a == 0 {
-        return 0;
+/// Inverse of `srgb_to_linear`
+pub fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// `alpha_blend` done in linear light: the color channels are decoded from
+/// sRGB, mixed, and encoded again, so a half-covered white edge over black
+/// comes out at 188 rather than 128. Alpha is already linear and blends the
+/// same way. Every channel is within 1 of the exact result.
+pub fn alpha_blend_linear(dst: u32, src: u32) -> u32 {
+    let sa = (src >> 24) & 0xFF;
+    if sa == 0 {
+        return dst;
+    }
+    if sa == 255 {
+        return src;
     }
 
-    let out_r = (sr * sa + dr * da * (255 - sa) / 255) / out_a;
-    let out_g = (sg * sa + dg * da * (255 - sa) / 255) / out_a;
-    let out_b = (sb * sa + db * da * (255 - sa) / 255) / out_a;
+    let tables = gamma_tables();
+    let da = (dst >> 24) & 0xFF;
+    let inv_sa = 255 - sa;
+    // Source and destination weights, both scaled by 255 * 255
+    let src_weight = (sa * 255) as u64;
+    let dst_weight = (da * inv_sa) as u64;
+    let total = src_weight + dst_weight;
+    let out_a = sa + div255(da * inv_sa);
+    let channel = |shift: u32| {
+        let s = tables.to_linear[((src >> shift) & 0xFF) as usize] as u64;
+        let d = tables.to_linear[((dst >> shift) & 0xFF) as usize] as u64;
+        // Mix at 16 bits, then round to the 12-bit table index
+        let linear = (s * src_weight + d * dst_weight + total / 2) / total;
+        tables.to_srgb[((linear * 4095 + 32767) / 65535) as usize] as u32
+    };
 
-    (out_a << 24) | (out_r << 16) | (out_g << 8) | out_b
+    (out_a << 24) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+}
+
+/// `x / 255` rounded down, for `x` up to 255 * 255
+#[inline(always)]
+fn div255(x: u32) -> u32 {
+    (x * 257 + 257) >> 16
 }
 
 #[allow(dead_code)]
@@ -1133,8 +1826,148 @@ fn sharpen_image(img: image::RgbaImage, strength: f32) -> image::RgbaImage {
     sharpened
 }
 
+/// Running indicator color for apps that aren't responding
+const NOT_RESPONDING_COLOR: (u8, u8, u8) = (0xf9, 0xb3, 0x3f);
+
+/// Fill of item badges, RGB only (alpha comes from the pill's coverage)
+const BADGE_COLOR: u32 = 0x00E5_3935;
+
+/// Radius of the running indicator's solid dot
+const INDICATOR_RADIUS: u32 = 3;
+
+/// Center Y of the running indicator: `offset` below the icons' resting
+/// bottom edge, pulled up if needed so the whole dot stays above the screen
+/// edge (the bottom `negative_vertical_offset` rows of the window are off
+/// screen), but never onto the icons themselves.
+fn indicator_y(icon_bottom: u32, offset: u32, height: u32, negative_vertical_offset: i32) -> u32 {
+    let visible_bottom = height.saturating_sub(negative_vertical_offset.max(0) as u32);
+    let lowest = visible_bottom.saturating_sub(INDICATOR_RADIUS + 1);
+    (icon_bottom + offset).min(lowest).max(icon_bottom + INDICATOR_RADIUS)
+}
+
+/// Letter shown on a monogram placeholder: the name's first letter or digit,
+/// uppercased. Names the bitmap font can't draw fall back to the plain tile.
+fn monogram_letter(name: &str) -> Option<char> {
+    name.chars()
+        .find(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+}
+
+/// Extensions tried for each theme icon name, in order
+const THEME_ICON_EXTENSIONS: [&str; 2] = ["png", "ico"];
+
+/// Sharpen a resized icon and pack it as ARGB
+fn to_icon_pixels(rgba: image::RgbaImage) -> Vec<u32> {
+    // Apply subtle sharpening to improve edge clarity
+    let rgba = sharpen_image(rgba, 0.3);
+    
+    rgba
+        .chunks_exact(4)
+        .map(|c| {
+            let a = c[3] as u32;
+            let r = c[0] as u32;
+            let g = c[1] as u32;
+            let b = c[2] as u32;
+            (a << 24) | (r << 16) | (g << 8) | b
+        })
+        .collect()
+}
+
+/// Decode every frame of an animated GIF or PNG at `size` x `size`. Still
+/// images (and anything that fails to decode) give None and load as usual.
+fn load_animated_icon(path: &Path, size: u32) -> Option<AnimatedIcon> {
+    use image::codecs::gif::GifDecoder;
+    use image::codecs::png::PngDecoder;
+    use image::{AnimationDecoder, Frame, ImageResult};
+    use std::io::BufReader;
+
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let reader = BufReader::new(std::fs::File::open(path).ok()?);
+    let frames: ImageResult<Vec<Frame>> = match ext.as_str() {
+        "gif" => GifDecoder::new(reader).ok()?.into_frames().take(MAX_ANIMATION_FRAMES).collect(),
+        "png" | "apng" => {
+            let decoder = PngDecoder::new(reader).ok()?;
+            if !decoder.is_apng().ok()? {
+                return None;
+            }
+            decoder.apng().ok()?.into_frames().take(MAX_ANIMATION_FRAMES).collect()
+        }
+        _ => return None,
+    };
+    let frames = match frames {
+        Ok(frames) if frames.len() > 1 => frames,
+        Ok(_) => return None,
+        Err(e) => {
+            log::warn!("Failed to decode animated icon {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    let mut animation = AnimatedIcon {
+        frames: Vec::with_capacity(frames.len()),
+        delays: Vec::with_capacity(frames.len()),
+        current: 0,
+        elapsed: Duration::ZERO,
+    };
+    for frame in frames {
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let ms = numer / denom.max(1);
+        // Browsers play 0-10ms delays at 100ms; files rely on it
+        animation.delays.push(Duration::from_millis(if ms <= 10 { 100 } else { ms as u64 }));
+        let img = image::DynamicImage::ImageRgba8(frame.into_buffer())
+            .resize_exact(size, size, image::imageops::FilterType::Lanczos3);
+        animation.frames.push(to_icon_pixels(img.to_rgba8()));
+    }
+    Some(animation)
+}
+
+/// First icon in `theme` matching `item`, see `theme_icon_names`
+fn find_theme_icon(theme: &Path, item: &DockItem) -> Option<PathBuf> {
+    theme_icon_names(item)
+        .into_iter()
+        .flat_map(|name| THEME_ICON_EXTENSIONS.iter().map(move |ext| format!("{}.{}", name, ext)))
+        .map(|file| theme.join(file))
+        .find(|path| path.is_file())
+}
+
+/// File stems an icon theme can use for `item`, most specific first: the
+/// item's name as written, then lowercased, then the special item type or the
+/// executable's file stem. Characters Windows won't allow in file names are dropped.
+fn theme_icon_names(item: &DockItem) -> Vec<String> {
+    let stem = match &item.special {
+        Some(special) => Some(special.clone()),
+        None => item.path.file_stem().map(|s| s.to_string_lossy().into_owned()),
+    };
+    let candidates = [Some(item.name.clone()), Some(item.name.to_lowercase()), stem];
+
+    let mut names: Vec<String> = Vec::new();
+    for name in candidates.into_iter().flatten() {
+        let name: String = name
+            .chars()
+            .filter(|c| !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
+            .collect();
+        let name = name.trim().to_string();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Coverage (0..1) of pixel (x, y) inside a `size` x `size` square with
+/// corners of `radius`, anti-aliased the same way as the dock background.
+fn rounded_rect_coverage(x: u32, y: u32, size: u32, radius: u32) -> f32 {
+    let (xi, yi, s, r) = (x as i32, y as i32, size as i32, radius as i32);
+    let dx = if xi < r { r - xi } else if xi >= s - r { xi - (s - r - 1) } else { return 1.0 };
+    let dy = if yi < r { r - yi } else if yi >= s - r { yi - (s - r - 1) } else { return 1.0 };
+    let dist = ((dx * dx + dy * dy) as f32).sqrt() - r as f32;
+    (1.0 - dist).clamp(0.0, 1.0)
+}
+
+/// Sample a square ARGB image `src_w` pixels wide at (x, y) with bicubic
+/// interpolation, clamping at the edges
 #[inline(always)]
-fn bicubic_sample(pixels: &[u32], src_w: usize, x: f32, y: f32) -> u32 {
+pub fn bicubic_sample(pixels: &[u32], src_w: usize, x: f32, y: f32) -> u32 {
     let x0 = x.floor() as isize;
     let y0 = y.floor() as isize;
     let fx = x - x0 as f32;
@@ -1183,7 +2016,7 @@ fn bicubic_sample(pixels: &[u32], src_w: usize, x: f32, y: f32) -> u32 {
 
     let mut channels = [0f32; 4]; // ARGB
 
-    for ch in 0..4 {
+    for (ch, channel) in channels.iter_mut().enumerate() {
         let shift = (3 - ch) * 8;
         let mut cols = [0f32; 4];
 
@@ -1198,8 +2031,172 @@ fn bicubic_sample(pixels: &[u32], src_w: usize, x: f32, y: f32) -> u32 {
             cols[j] = cubic_hermite(row[0], row[1], row[2], row[3], fx);
         }
 
-        channels[ch] = cubic_hermite(cols[0], cols[1], cols[2], cols[3], fy).clamp(0.0, 255.0);
+        *channel = cubic_hermite(cols[0], cols[1], cols[2], cols[3], fy).clamp(0.0, 255.0);
     }
 
     ((channels[0] as u32) << 24) | ((channels[1] as u32) << 16) | ((channels[2] as u32) << 8) | (channels[3] as u32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A renderer `width` wide with 48px icons, 8px spacing and 16px
+    /// separator slots, without loading any icons
+    fn renderer(width: u32) -> Renderer {
+        let config = Config { dock: DockSettings::default(), items: Vec::new() };
+        let mut renderer = Renderer::new(&config, &[]).unwrap();
+        renderer.icon_size = 48;
+        renderer.spacing.x = 8;
+        renderer.separator_slot = 16;
+        renderer.width = width;
+        renderer
+    }
+
+    fn item(name: &str) -> DockItem {
+        DockItem::from_exe(PathBuf::from(format!(r"C:\Apps\{}.exe", name)))
+    }
+
+    #[test]
+    fn drop_index_uses_separator_width() {
+        let renderer = renderer(400);
+        let items = [item("a"), DockItem::new_separator(), item("b"), item("c")];
+        // Dragging "c" leaves a (132..180), the separator (188..204) and b (212..260)
+        assert_eq!(renderer.drop_index(150.0, &items, &[], 3), 0);
+        assert_eq!(renderer.drop_index(190.0, &items, &[], 3), 1);
+        assert_eq!(renderer.drop_index(200.0, &items, &[], 3), 2);
+        assert_eq!(renderer.drop_index(240.0, &items, &[], 3), 4);
+    }
+
+    #[test]
+    fn drop_index_without_a_drag_uses_item_midpoints() {
+        let renderer = renderer(400);
+        let items = [item("a"), DockItem::new_separator(), item("b")];
+        // Nothing lifted out and no gap: a (136..184), the separator (192..208) and b (216..264)
+        assert_eq!(renderer.drop_index(159.0, &items, &[], usize::MAX), 0);
+        assert_eq!(renderer.drop_index(161.0, &items, &[], usize::MAX), 1);
+        assert_eq!(renderer.drop_index(201.0, &items, &[], usize::MAX), 2);
+        assert_eq!(renderer.drop_index(239.0, &items, &[], usize::MAX), 2);
+        assert_eq!(renderer.drop_index(241.0, &items, &[], usize::MAX), 3);
+    }
+
+    #[test]
+    fn separators_can_be_dragged() {
+        let renderer = renderer(400);
+        let items = [item("a"), DockItem::new_separator(), item("b")];
+        // Without the separator: a (144..192) and b (200..248)
+        assert_eq!(renderer.drop_index(160.0, &items, &[], 1), 0);
+        assert_eq!(renderer.drop_index(190.0, &items, &[], 1), 2);
+        assert_eq!(renderer.drop_index(230.0, &items, &[], 1), 3);
+    }
+
+    #[test]
+    fn theme_icon_names_most_specific_first() {
+        assert_eq!(theme_icon_names(&item("Firefox")), ["Firefox", "firefox"]);
+        let mut settings = item("unused");
+        settings.name = "My: Settings?".to_string();
+        settings.special = Some("settings".to_string());
+        assert_eq!(theme_icon_names(&settings), ["My Settings", "my settings", "settings"]);
+    }
+
+    #[test]
+    fn theme_icon_falls_back_to_the_exe_name() {
+        let theme = std::env::temp_dir().join(format!("rdock_test_theme_{}", std::process::id()));
+        std::fs::create_dir_all(&theme).unwrap();
+        let mut web = item("firefox");
+        web.name = "Web".to_string();
+        assert_eq!(find_theme_icon(&theme, &web), None);
+        std::fs::write(theme.join("firefox.ico"), b"").unwrap();
+        assert_eq!(find_theme_icon(&theme, &web), Some(theme.join("firefox.ico")));
+        // PNG before ICO for the same name
+        std::fs::write(theme.join("firefox.png"), b"").unwrap();
+        assert_eq!(find_theme_icon(&theme, &web), Some(theme.join("firefox.png")));
+        let _ = std::fs::remove_dir_all(&theme);
+    }
+
+    #[test]
+    fn indicator_sits_offset_below_the_icons() {
+        assert_eq!(indicator_y(60, 12, 100, 0), 72);
+        // A negative offset is ignored
+        assert_eq!(indicator_y(60, 12, 100, -8), 72);
+    }
+
+    #[test]
+    fn indicator_stays_on_screen_but_off_the_icons() {
+        // The bottom 30 rows are below the screen edge
+        assert_eq!(indicator_y(60, 12, 100, 30), 66);
+        // No room below the icons at all: it rests just under them
+        assert_eq!(indicator_y(60, 12, 100, 40), 60 + INDICATOR_RADIUS);
+    }
+
+    /// `alpha_blend` trades exact division for speed, but stays within 1 per
+    /// channel of the real-valued compositing formula
+    #[test]
+    fn alpha_blend_is_within_one_of_exact() {
+        let channel = |pixel: u32, shift: u32| ((pixel >> shift) & 0xFF) as f64;
+        for sa in 1..255u32 {
+            for da in 0..=255u32 {
+                for c in (0..=255u32).step_by(5) {
+                    let src = (sa << 24) | (c << 16) | ((255 - c) << 8) | (c / 2);
+                    let dst = (da << 24) | ((255 - c) << 16) | (c << 8) | (255 - c / 3);
+                    let out = alpha_blend(dst, src);
+
+                    let (sa_f, da_f) = (sa as f64, da as f64);
+                    let dst_weight = da_f * (255.0 - sa_f) / 255.0;
+                    let out_a = sa_f + dst_weight;
+                    assert!((channel(out, 24) - out_a).abs() <= 1.0, "alpha off for {src:08x} over {dst:08x}");
+                    for shift in [16, 8, 0] {
+                        let exact = (channel(src, shift) * sa_f + channel(dst, shift) * dst_weight) / out_a;
+                        assert!(
+                            (channel(out, shift) - exact).abs() <= 1.0,
+                            "channel {shift} off for {src:08x} over {dst:08x}: {} vs {exact:.2}",
+                            channel(out, shift)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// `alpha_blend_linear` against the same blend done in f64 linear light
+    #[test]
+    fn alpha_blend_linear_matches_f64() {
+        // Half-transparent white over black is half the light, not half the value
+        assert_eq!(alpha_blend_linear(0xFF00_0000, 0x80FF_FFFF), 0xFFBC_BCBC);
+
+        let channel = |pixel: u32, shift: u32| ((pixel >> shift) & 0xFF) as f64;
+        for sa in 1..255u32 {
+            for da in (0..=255u32).step_by(3) {
+                for c in (0..=255u32).step_by(5) {
+                    let src = (sa << 24) | (c << 16) | ((255 - c) << 8) | (c / 2);
+                    let dst = (da << 24) | ((255 - c) << 16) | (c << 8) | (255 - c / 3);
+                    let out = alpha_blend_linear(dst, src);
+
+                    let (sa_f, da_f) = (sa as f64 / 255.0, da as f64 / 255.0);
+                    let dst_weight = da_f * (1.0 - sa_f);
+                    let out_a = sa_f + dst_weight;
+                    assert!((channel(out, 24) - out_a * 255.0).abs() <= 1.0, "alpha off for {src:08x} over {dst:08x}");
+                    for shift in [16, 8, 0] {
+                        let s = srgb_to_linear(channel(src, shift) / 255.0);
+                        let d = srgb_to_linear(channel(dst, shift) / 255.0);
+                        let exact = linear_to_srgb((s * sa_f + d * dst_weight) / out_a) * 255.0;
+                        assert!(
+                            (channel(out, shift) - exact).abs() <= 1.0,
+                            "linear channel {shift} off for {src:08x} over {dst:08x}: {} vs {exact:.2}",
+                            channel(out, shift)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rest_centers_follow_separator_and_spacer_widths() {
+        let renderer = renderer(400);
+        let items = [item("a"), DockItem::new_separator(), item("b")];
+        assert_eq!(renderer.rest_centers(&items), [Some(160.0), None, Some(240.0)]);
+        let items = [item("a"), DockItem::new_spacer(), item("b")];
+        assert_eq!(renderer.rest_centers(&items), [Some(152.0), None, Some(248.0)]);
+    }
+}
@@ -0,0 +1,74 @@
+//! Dock state kept between runs that isn't configuration
+//!
+//! Stored beside the config as `session.toml`. For now that is where each
+//! dock with `overflow = "scroll"` was scrolled to, restored at startup.
+
+use crate::config;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// File name of the session state, stored beside config.toml
+pub const SESSION_FILE_NAME: &str = "session.toml";
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Session {
+    /// First item shown by each scrolled dock, keyed by `ScreenDock::session_key`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    scroll: BTreeMap<String, usize>,
+}
+
+impl Session {
+    /// Load the session; a missing or unreadable file is a fresh session.
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        toml::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring unreadable session file {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string(self).context("Failed to serialize session")?;
+        config::write_config_file(path, &content)
+    }
+
+    pub fn scroll(&self, key: &str) -> usize {
+        self.scroll.get(key).copied().unwrap_or(0)
+    }
+
+    /// Remember `scroll` for the dock `key`. Returns true if it changed.
+    pub fn set_scroll(&mut self, key: &str, scroll: usize) -> bool {
+        if self.scroll(key) == scroll {
+            return false;
+        }
+        if scroll == 0 {
+            self.scroll.remove(key);
+        } else {
+            self.scroll.insert(key.to_string(), scroll);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_survives_save() {
+        let path = std::env::temp_dir().join(format!("rdock_test_session_{}.toml", std::process::id()));
+        let mut session = Session::default();
+        assert!(session.set_scroll("main", 4));
+        assert!(!session.set_scroll("main", 4));
+        session.save(&path).unwrap();
+        let loaded = Session::load(&path);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("toml.bak"));
+        assert_eq!(loaded.scroll("main"), 4);
+        assert_eq!(loaded.scroll("DISPLAY2"), 0);
+    }
+}
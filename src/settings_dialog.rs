@@ -0,0 +1,370 @@
+//! Dock settings dialog with live preview of appearance changes
+
+use std::cell::{Cell, RefCell};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{HWND, WPARAM, LPARAM, LRESULT};
+use windows::Win32::UI::WindowsAndMessaging::*;
+use windows::Win32::UI::Controls::{
+    InitCommonControlsEx, INITCOMMONCONTROLSEX, ICC_BAR_CLASSES, TRACKBAR_CLASSW,
+    TBM_SETPOS, TBM_SETRANGEMAX, TBM_SETRANGEMIN, TBS_HORZ, TBS_NOTICKS,
+};
+use windows::Win32::Graphics::Gdi::HBRUSH;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+
+use crate::config::{parse_color, DockSettings, IconSize};
+
+// Control IDs
+const ID_ICON_SIZE: i32 = 201;
+const ID_MAGNIFICATION: i32 = 202;
+const ID_OPACITY: i32 = 203;
+const ID_CORNER_RADIUS: i32 = 204;
+const ID_BG_COLOR: i32 = 205;
+const ID_INDICATOR_COLOR: i32 = 206;
+const ID_ICON_SIZE_VALUE: i32 = 211;
+const ID_MAGNIFICATION_VALUE: i32 = 212;
+const ID_OPACITY_VALUE: i32 = 213;
+const ID_CORNER_RADIUS_VALUE: i32 = 214;
+const ID_OK: i32 = 1;
+const ID_CANCEL: i32 = 2;
+
+// Style constants
+const SS_RIGHT: u32 = 0x0002;
+const ES_AUTOHSCROLL: u32 = 0x0080;
+const COLOR_BTNFACE: u32 = 15;
+const TBM_GETPOS: u32 = WM_USER; // not exported by windows-rs
+
+/// Slider rows: (control id, value label id, label, min, max)
+const SLIDERS: &[(i32, i32, &str, i32, i32)] = &[
+    (ID_ICON_SIZE, ID_ICON_SIZE_VALUE, "Icon size:", 16, 128),
+    (ID_MAGNIFICATION, ID_MAGNIFICATION_VALUE, "Magnification:", 100, 300),
+    (ID_OPACITY, ID_OPACITY_VALUE, "Opacity:", 0, 100),
+    (ID_CORNER_RADIUS, ID_CORNER_RADIUS_VALUE, "Corner radius:", 0, 40),
+];
+
+// Dialog state stored in thread-local for the dialog proc
+thread_local! {
+    static DRAFT: RefCell<Option<DockSettings>> = const { RefCell::new(None) };
+    static COMMITTED: Cell<bool> = const { Cell::new(false) };
+    static PREVIEW_PENDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Show the dock settings dialog.
+///
+/// `on_preview` is called with the draft settings whenever a control changes so
+/// the caller can restyle the dock live. Returns the final settings on OK, or
+/// `None` on Cancel, in which case the caller should revert to what it had.
+pub fn show_settings_dialog(settings: &DockSettings, on_preview: &mut dyn FnMut(&DockSettings)) -> Option<DockSettings> {
+    DRAFT.with(|cell| *cell.borrow_mut() = Some(settings.clone()));
+    COMMITTED.with(|c| c.set(false));
+    PREVIEW_PENDING.with(|p| p.set(false));
+
+    unsafe {
+        let icc = INITCOMMONCONTROLSEX {
+            dwSize: std::mem::size_of::<INITCOMMONCONTROLSEX>() as u32,
+            dwICC: ICC_BAR_CLASSES,
+        };
+        let _ = InitCommonControlsEx(&icc);
+
+        let hinstance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default().0 as *mut _;
+        let hinstance = windows::Win32::Foundation::HINSTANCE(hinstance);
+
+        // Register window class
+        let class_name: Vec<u16> = "RDockSettings\0".encode_utf16().collect();
+        let wc = WNDCLASSEXW {
+            cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+            style: CS_HREDRAW | CS_VREDRAW,
+            lpfnWndProc: Some(dialog_proc),
+            hInstance: hinstance,
+            hCursor: LoadCursorW(None, IDC_ARROW).unwrap_or_default(),
+            hbrBackground: HBRUSH((COLOR_BTNFACE + 1) as *mut _),
+            lpszClassName: PCWSTR(class_name.as_ptr()),
+            ..Default::default()
+        };
+
+        RegisterClassExW(&wc);
+
+        let width = 460;
+        let height = 340;
+        let screen_w = GetSystemMetrics(SM_CXSCREEN);
+        let screen_h = GetSystemMetrics(SM_CYSCREEN);
+        let x = (screen_w - width) / 2;
+        let y = (screen_h - height) / 2;
+
+        let title: Vec<u16> = "Dock Settings\0".encode_utf16().collect();
+        let hwnd = CreateWindowExW(
+            WS_EX_DLGMODALFRAME | WS_EX_TOPMOST,
+            PCWSTR(class_name.as_ptr()),
+            PCWSTR(title.as_ptr()),
+            WS_POPUP | WS_CAPTION | WS_SYSMENU,
+            x, y, width, height,
+            HWND::default(),
+            HMENU::default(),
+            hinstance,
+            None,
+        ).unwrap_or_default();
+
+        if hwnd.is_invalid() {
+            return None;
+        }
+
+        let _ = ShowWindow(hwnd, SW_SHOW);
+        let _ = windows::Win32::Graphics::Gdi::UpdateWindow(hwnd);
+
+        // Modal message loop
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).into() {
+            if !IsDialogMessageW(hwnd, &msg).as_bool() {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+
+            // Check if dialog was closed
+            if !IsWindow(hwnd).as_bool() {
+                break;
+            }
+
+            // Coalesce bursts of changes (e.g. dragging a slider) into a single
+            // preview once the queue has drained, since a preview may rebuild
+            // the renderer.
+            let mut peek = MSG::default();
+            if PREVIEW_PENDING.with(|p| p.get()) && !PeekMessageW(&mut peek, None, 0, 0, PM_NOREMOVE).as_bool() {
+                PREVIEW_PENDING.with(|p| p.set(false));
+                if let Some(draft) = DRAFT.with(|cell| cell.borrow().clone()) {
+                    on_preview(&draft);
+                }
+            }
+        }
+
+        let draft = DRAFT.with(|cell| cell.borrow_mut().take());
+        if COMMITTED.with(|c| c.get()) {
+            draft
+        } else {
+            None
+        }
+    }
+}
+
+unsafe extern "system" fn dialog_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_CREATE => {
+            create_controls(hwnd);
+            populate_controls(hwnd);
+            LRESULT(0)
+        }
+        WM_HSCROLL => {
+            read_sliders(hwnd);
+            LRESULT(0)
+        }
+        WM_COMMAND => {
+            let id = (wparam.0 & 0xFFFF) as i32;
+            let code = ((wparam.0 >> 16) & 0xFFFF) as u32;
+            handle_command(hwnd, id, code);
+            LRESULT(0)
+        }
+        WM_CLOSE => {
+            let _ = DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+unsafe fn create_controls(hwnd: HWND) {
+    let hinstance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default().0 as *mut _;
+    let hinstance = windows::Win32::Foundation::HINSTANCE(hinstance);
+
+    let mut y = 20;
+    let label_w = 110;
+    let ctrl_x = 130;
+    let slider_w = 220;
+    let value_x = 355;
+    let row_h = 40;
+
+    let static_class: Vec<u16> = "STATIC\0".encode_utf16().collect();
+    let edit_class: Vec<u16> = "EDIT\0".encode_utf16().collect();
+    let button_class: Vec<u16> = "BUTTON\0".encode_utf16().collect();
+
+    for &(id, value_id, label, min, max) in SLIDERS {
+        let label_text: Vec<u16> = format!("{}\0", label).encode_utf16().collect();
+        let _ = CreateWindowExW(
+            WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(label_text.as_ptr()),
+            WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
+            10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
+        );
+        let slider = CreateWindowExW(
+            WINDOW_EX_STYLE(0), TRACKBAR_CLASSW, PCWSTR::null(),
+            WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(TBS_HORZ | TBS_NOTICKS),
+            ctrl_x, y, slider_w, 26, hwnd, HMENU(id as *mut _), hinstance, None
+        ).unwrap_or_default();
+        SendMessageW(slider, TBM_SETRANGEMIN, WPARAM(0), LPARAM(min as isize));
+        SendMessageW(slider, TBM_SETRANGEMAX, WPARAM(1), LPARAM(max as isize));
+        let _ = CreateWindowExW(
+            WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR::null(),
+            WS_CHILD | WS_VISIBLE,
+            value_x, y + 3, 80, 20, hwnd, HMENU(value_id as *mut _), hinstance, None
+        );
+        y += row_h;
+    }
+
+    // Color fields
+    for (id, label) in [(ID_BG_COLOR, "Background:"), (ID_INDICATOR_COLOR, "Indicator:")] {
+        let label_text: Vec<u16> = format!("{}\0", label).encode_utf16().collect();
+        let _ = CreateWindowExW(
+            WINDOW_EX_STYLE(0), PCWSTR(static_class.as_ptr()), PCWSTR(label_text.as_ptr()),
+            WS_CHILD | WS_VISIBLE | WINDOW_STYLE(SS_RIGHT),
+            10, y + 3, label_w, 20, hwnd, HMENU::default(), hinstance, None
+        );
+        let _ = CreateWindowExW(
+            WS_EX_CLIENTEDGE, PCWSTR(edit_class.as_ptr()), PCWSTR::null(),
+            WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(ES_AUTOHSCROLL),
+            ctrl_x, y, 120, 24, hwnd, HMENU(id as *mut _), hinstance, None
+        );
+        y += row_h;
+    }
+
+    // Buttons
+    let ok_text: Vec<u16> = "OK\0".encode_utf16().collect();
+    let cancel_text: Vec<u16> = "Cancel\0".encode_utf16().collect();
+    let btn_y = y + 5;
+
+    let _ = CreateWindowExW(
+        WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(ok_text.as_ptr()),
+        WS_CHILD | WS_VISIBLE | WS_TABSTOP | WINDOW_STYLE(0x0001), // BS_DEFPUSHBUTTON
+        250, btn_y, 90, 30, hwnd, HMENU(ID_OK as *mut _), hinstance, None
+    );
+    let _ = CreateWindowExW(
+        WINDOW_EX_STYLE(0), PCWSTR(button_class.as_ptr()), PCWSTR(cancel_text.as_ptr()),
+        WS_CHILD | WS_VISIBLE | WS_TABSTOP,
+        350, btn_y, 90, 30, hwnd, HMENU(ID_CANCEL as *mut _), hinstance, None
+    );
+}
+
+unsafe fn populate_controls(hwnd: HWND) {
+    let Some(draft) = DRAFT.with(|cell| cell.borrow().clone()) else { return };
+
+    set_slider(hwnd, ID_ICON_SIZE, draft.icon_size.pixels() as i32);
+    set_slider(hwnd, ID_MAGNIFICATION, (draft.magnification * 100.0).round() as i32);
+    set_slider(hwnd, ID_OPACITY, (draft.background_opacity * 100.0).round() as i32);
+    set_slider(hwnd, ID_CORNER_RADIUS, draft.corner_radius as i32);
+    update_value_labels(hwnd, &draft);
+
+    set_edit_text(hwnd, ID_BG_COLOR, &draft.background_color);
+    set_edit_text(hwnd, ID_INDICATOR_COLOR, &draft.indicator_color);
+}
+
+unsafe fn set_slider(hwnd: HWND, id: i32, pos: i32) {
+    if let Ok(ctrl) = GetDlgItem(hwnd, id) {
+        SendMessageW(ctrl, TBM_SETPOS, WPARAM(1), LPARAM(pos as isize));
+    }
+}
+
+unsafe fn get_slider(hwnd: HWND, id: i32) -> i32 {
+    match GetDlgItem(hwnd, id) {
+        Ok(ctrl) => SendMessageW(ctrl, TBM_GETPOS, WPARAM(0), LPARAM(0)).0 as i32,
+        Err(_) => 0,
+    }
+}
+
+unsafe fn update_value_labels(hwnd: HWND, draft: &DockSettings) {
+    set_edit_text(hwnd, ID_ICON_SIZE_VALUE, &draft.icon_size.to_string());
+    set_edit_text(hwnd, ID_MAGNIFICATION_VALUE, &format!("{:.2}x", draft.magnification));
+    set_edit_text(hwnd, ID_OPACITY_VALUE, &format!("{}%", (draft.background_opacity * 100.0).round()));
+    set_edit_text(hwnd, ID_CORNER_RADIUS_VALUE, &format!("{} px", draft.corner_radius));
+}
+
+/// Pull slider positions into the draft and schedule a preview if anything moved.
+unsafe fn read_sliders(hwnd: HWND) {
+    let icon_size = get_slider(hwnd, ID_ICON_SIZE).max(1) as u32;
+    let magnification = get_slider(hwnd, ID_MAGNIFICATION) as f32 / 100.0;
+    let opacity = get_slider(hwnd, ID_OPACITY) as f32 / 100.0;
+    let corner_radius = get_slider(hwnd, ID_CORNER_RADIUS).max(0) as u32;
+
+    let changed = DRAFT.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let draft = cell.as_mut()?;
+        // A percentage size is kept until the slider actually moves
+        let size_moved = draft.icon_size.pixels() != icon_size;
+        let changed = size_moved
+            || (draft.magnification - magnification).abs() > f32::EPSILON
+            || (draft.background_opacity - opacity).abs() > f32::EPSILON
+            || draft.corner_radius != corner_radius;
+        if size_moved {
+            draft.icon_size = IconSize::Pixels(icon_size);
+        }
+        draft.magnification = magnification;
+        draft.background_opacity = opacity;
+        draft.corner_radius = corner_radius;
+        changed.then(|| draft.clone())
+    });
+
+    if let Some(draft) = changed {
+        update_value_labels(hwnd, &draft);
+        PREVIEW_PENDING.with(|p| p.set(true));
+    }
+}
+
+/// Accept any form `parse_color` does while typing; anything else is ignored until valid.
+fn is_hex_color(text: &str) -> bool {
+    parse_color(text).is_ok()
+}
+
+unsafe fn read_color(hwnd: HWND, id: i32) {
+    let text = get_edit_text(hwnd, id);
+    if !is_hex_color(&text) {
+        return;
+    }
+    let color = format!("#{}", text.trim().trim_start_matches('#'));
+
+    let changed = DRAFT.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let Some(draft) = cell.as_mut() else { return false };
+        let field = if id == ID_BG_COLOR { &mut draft.background_color } else { &mut draft.indicator_color };
+        if field.eq_ignore_ascii_case(&color) {
+            return false;
+        }
+        *field = color;
+        true
+    });
+
+    if changed {
+        PREVIEW_PENDING.with(|p| p.set(true));
+    }
+}
+
+unsafe fn set_edit_text(hwnd: HWND, id: i32, text: &str) {
+    if let Ok(ctrl) = GetDlgItem(hwnd, id) {
+        let text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        let _ = SetWindowTextW(ctrl, PCWSTR(text_wide.as_ptr()));
+    }
+}
+
+unsafe fn get_edit_text(hwnd: HWND, id: i32) -> String {
+    let Ok(ctrl) = GetDlgItem(hwnd, id) else { return String::new() };
+    let len = GetWindowTextLengthW(ctrl) as usize;
+    if len == 0 {
+        return String::new();
+    }
+    let mut buf: Vec<u16> = vec![0; len + 1];
+    GetWindowTextW(ctrl, &mut buf);
+    String::from_utf16_lossy(&buf[..len])
+}
+
+unsafe fn handle_command(hwnd: HWND, id: i32, code: u32) {
+    match id {
+        ID_BG_COLOR | ID_INDICATOR_COLOR if code == EN_CHANGE => {
+            read_color(hwnd, id);
+        }
+        ID_OK => {
+            COMMITTED.with(|c| c.set(true));
+            let _ = DestroyWindow(hwnd);
+        }
+        ID_CANCEL => {
+            let _ = DestroyWindow(hwnd);
+        }
+        _ => {}
+    }
+}
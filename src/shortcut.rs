@@ -0,0 +1,139 @@
+//! Turning files dropped onto the dock into dock items
+//!
+//! Executables become plain items, `.lnk` shortcuts are resolved through the
+//! shell's `IShellLinkW` to their target and arguments, and anything else
+//! (documents, folders) opens with its default handler via `start`. A path
+//! copied to the clipboard goes through the same conversion.
+
+use crate::config::DockItem;
+use std::path::{Path, PathBuf};
+use windows::core::{Interface, PCWSTR};
+use windows::Win32::Foundation::HGLOBAL;
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, IPersistFile, CLSCTX_INPROC_SERVER,
+    COINIT_APARTMENTTHREADED, STGM_READ,
+};
+use windows::Win32::System::DataExchange::{CloseClipboard, GetClipboardData, IsClipboardFormatAvailable, OpenClipboard};
+use windows::Win32::System::Memory::{GlobalLock, GlobalUnlock};
+use windows::Win32::System::Ole::{CF_HDROP, CF_UNICODETEXT};
+use windows::Win32::UI::Shell::{DragQueryFileW, IShellLinkW, ShellLink, HDROP};
+
+/// What a `.lnk` file points at
+struct Shortcut {
+    target: PathBuf,
+    args: String,
+    icon: Option<PathBuf>,
+}
+
+/// Build the dock item for a file dropped onto the dock
+pub fn item_for_file(path: &Path) -> DockItem {
+    let is_ext = |ext: &str| path.extension().is_some_and(|e| e.eq_ignore_ascii_case(ext));
+
+    if is_ext("lnk") {
+        if let Some(shortcut) = resolve(path) {
+            let mut item = item_for_file(&shortcut.target);
+            // The shortcut's own name is what the user sees in the Start menu
+            if let Some(stem) = path.file_stem() {
+                item.name = stem.to_string_lossy().into_owned();
+            }
+            if item.command.is_none() {
+                item.args = shell_words::split(&shortcut.args)
+                    .unwrap_or_else(|_| shortcut.args.split_whitespace().map(str::to_string).collect());
+            }
+            item.icon = shortcut.icon.or(item.icon);
+            return item;
+        }
+        log::warn!("Could not resolve shortcut {}", path.display());
+    }
+
+    let mut item = DockItem::from_exe(path.to_path_buf());
+    if !is_ext("exe") {
+        // The icon still comes from the file; launching goes to its default app
+        item.command = Some(format!("start \"\" \"{}\"", path.display()));
+    }
+    item
+}
+
+/// Read a `.lnk` file's target, arguments and custom icon
+fn resolve(path: &Path) -> Option<Shortcut> {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let result = (|| {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER).ok()?;
+            let file: IPersistFile = link.cast().ok()?;
+            let wide: Vec<u16> = path.as_os_str().to_string_lossy().encode_utf16().chain(Some(0)).collect();
+            file.Load(PCWSTR(wide.as_ptr()), STGM_READ).ok()?;
+
+            let mut buf = [0u16; 1024];
+            link.GetPath(&mut buf, std::ptr::null_mut(), 0).ok()?;
+            let target = PathBuf::from(from_wide(&buf));
+            if target.as_os_str().is_empty() {
+                // Shortcuts to shell items like Control Panel have no file path
+                return None;
+            }
+
+            let mut buf = [0u16; 1024];
+            let args = match link.GetArguments(&mut buf) {
+                Ok(()) => from_wide(&buf),
+                Err(_) => String::new(),
+            };
+
+            // Only .ico files load as item icons; exe/dll icon resources
+            // are left to the target's own icon
+            let mut buf = [0u16; 1024];
+            let mut index = 0;
+            let icon = link.GetIconLocation(&mut buf, &mut index).ok()
+                .map(|()| PathBuf::from(from_wide(&buf)))
+                .filter(|icon| icon.extension().is_some_and(|e| e.eq_ignore_ascii_case("ico")) && icon.is_file());
+
+            Some(Shortcut { target, args, icon })
+        })();
+        CoUninitialize();
+        result
+    }
+}
+
+/// An existing file or folder on the clipboard: the first file copied in
+/// Explorer, or a path copied as text (quotes from "Copy as path" are fine)
+pub fn clipboard_path() -> Option<PathBuf> {
+    unsafe {
+        OpenClipboard(None).ok()?;
+        let path = if IsClipboardFormatAvailable(CF_HDROP.0 as u32).is_ok() {
+            GetClipboardData(CF_HDROP.0 as u32).ok().and_then(|data| {
+                let mut buf = [0u16; 1024];
+                let len = DragQueryFileW(HDROP(data.0), 0, Some(&mut buf)) as usize;
+                (len > 0).then(|| PathBuf::from(String::from_utf16_lossy(&buf[..len.min(buf.len())])))
+            })
+        } else if IsClipboardFormatAvailable(CF_UNICODETEXT.0 as u32).is_ok() {
+            GetClipboardData(CF_UNICODETEXT.0 as u32).ok().and_then(|data| {
+                let text = GlobalLock(HGLOBAL(data.0)) as *const u16;
+                if text.is_null() {
+                    return None;
+                }
+                let len = (0..).take_while(|&i| *text.add(i) != 0).count();
+                let text = String::from_utf16_lossy(std::slice::from_raw_parts(text, len));
+                let _ = GlobalUnlock(HGLOBAL(data.0));
+                text_path(&text)
+            })
+        } else {
+            None
+        };
+        let _ = CloseClipboard();
+        path.filter(|path| path.exists())
+    }
+}
+
+/// The path in copied text: one line, optionally quoted
+fn text_path(text: &str) -> Option<PathBuf> {
+    let line = text.trim();
+    if line.is_empty() || line.contains('\n') {
+        return None;
+    }
+    let line = line.strip_prefix('"').and_then(|l| l.strip_suffix('"')).unwrap_or(line);
+    Some(PathBuf::from(line))
+}
+
+fn from_wide(buf: &[u16]) -> String {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..len])
+}
@@ -0,0 +1,53 @@
+//! The chime for `reveal_feedback = "sound"`
+//!
+//! Rather than shipping a .wav file, the chime is synthesized once into an
+//! in-memory WAV and handed to `PlaySoundW`, which plays it asynchronously.
+
+use std::sync::OnceLock;
+use windows::core::PCWSTR;
+use windows::Win32::Media::Audio::{PlaySoundW, SND_ASYNC, SND_MEMORY, SND_NODEFAULT};
+
+const SAMPLE_RATE: u32 = 22050;
+const DURATION_MS: u32 = 80;
+const FREQUENCY: f32 = 1320.0;
+/// Peak amplitude as a fraction of full scale; a hint, not an alert
+const VOLUME: f32 = 0.2;
+
+/// Start the reveal chime and return without waiting for it
+pub fn play_reveal() {
+    static WAV: OnceLock<Vec<u8>> = OnceLock::new();
+    let wav = WAV.get_or_init(reveal_wav);
+    // With SND_MEMORY the "name" is the WAV data itself, which has to
+    // outlive the asynchronous playback; the static does
+    unsafe {
+        let _ = PlaySoundW(PCWSTR(wav.as_ptr() as *const u16), None, SND_MEMORY | SND_ASYNC | SND_NODEFAULT);
+    }
+}
+
+/// A short sine blip as a 16-bit mono PCM WAV file
+fn reveal_wav() -> Vec<u8> {
+    let count = SAMPLE_RATE * DURATION_MS / 1000;
+    let data_len = count * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // bytes per second
+    wav.extend_from_slice(&2u16.to_le_bytes()); // bytes per sample
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for i in 0..count {
+        let progress = i as f32 / count as f32;
+        // Fast attack and a falling tail, so neither end clicks
+        let envelope = (progress * 20.0).min(1.0) * (1.0 - progress).powi(2);
+        let phase = i as f32 / SAMPLE_RATE as f32 * FREQUENCY * std::f32::consts::TAU;
+        let sample = phase.sin() * envelope * VOLUME * i16::MAX as f32;
+        wav.extend_from_slice(&(sample as i16).to_le_bytes());
+    }
+    wav
+}
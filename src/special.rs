@@ -0,0 +1,91 @@
+//! What each `special` item does, as data
+//!
+//! `special_action` maps a special item's name to the program it runs, or to
+//! one of the actions the dock handles itself, without running anything. The
+//! dock spawns the result; `--doctor` uses it to flag unknown names.
+
+/// Ctrl+Esc opens the Start Menu
+const START_MENU_SCRIPT: &str = r#"Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('^{ESC}')"#;
+
+/// Win+A opens Windows 11 Quick Settings
+const QUICK_SETTINGS_SCRIPT: &str = r#"$sig = '[DllImport("user32.dll")] public static extern void keybd_event(byte bVk, byte bScan, uint dwFlags, int dwExtraInfo);'; $kb = Add-Type -MemberDefinition $sig -Name KB -PassThru; $kb::keybd_event(0x5B,0,0,0); $kb::keybd_event(0x41,0,0,0); $kb::keybd_event(0x41,0,2,0); $kb::keybd_event(0x5B,0,2,0)"#;
+
+/// Special items that only open a program: names, program, arguments
+const SPECIAL_COMMANDS: &[(&[&str], &str, &[&str])] = &[
+    (&["start_menu"], "powershell", &["-NoProfile", "-WindowStyle", "Hidden", "-Command", START_MENU_SCRIPT]),
+    (&["recycle_bin"], "explorer", &["shell:RecycleBinFolder"]),
+    (&["settings"], "cmd", &["/c", "start", "ms-settings:"]),
+    // Shell.Application COM object
+    (&["show_desktop"], "powershell", &["-Command", "(New-Object -ComObject Shell.Application).ToggleDesktop()"]),
+    (&["task_view"], "explorer", &["shell:::{3080F90E-D7AD-11D9-BD98-0000947B0257}"]),
+    (&["action_center", "notification_center"], "explorer", &["ms-actioncenter:"]),
+    (&["quick_settings"], "powershell", &["-NoProfile", "-WindowStyle", "Hidden", "-Command", QUICK_SETTINGS_SCRIPT]),
+    (&["file_explorer"], "explorer", &[","]),
+    (&["control_panel"], "control", &[]),
+    // Run dialog
+    (&["run_dialog"], "rundll32", &["shell32.dll,#61"]),
+    (&["this_pc", "my_computer"], "explorer", &["shell:MyComputerFolder"]),
+    (&["documents"], "explorer", &["shell:Personal"]),
+    (&["downloads"], "explorer", &["shell:Downloads"]),
+    (&["network"], "explorer", &["shell:NetworkPlacesFolder"]),
+    (&["user_folder", "home"], "explorer", &["shell:UsersFilesFolder"]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecialAction {
+    /// Spawn a program with these arguments
+    Run { program: &'static str, args: &'static [&'static str] },
+    /// Show the custom tray overflow popup at the cursor
+    TrayPopup,
+    /// Minimize every window, or restore the ones the last click minimized
+    ToggleDesktop,
+    /// Pop up the recently launched apps
+    RecentApps,
+    /// Open the searchable list of installed apps
+    AppLauncher,
+}
+
+/// Look up what the special item `special` does
+pub fn special_action(special: &str) -> std::io::Result<SpecialAction> {
+    match special {
+        "system_tray" => return Ok(SpecialAction::TrayPopup),
+        "toggle_desktop" => return Ok(SpecialAction::ToggleDesktop),
+        "recent_apps" => return Ok(SpecialAction::RecentApps),
+        "app_launcher" => return Ok(SpecialAction::AppLauncher),
+        _ => {}
+    }
+    SPECIAL_COMMANDS.iter()
+        .find(|(names, _, _)| names.contains(&special))
+        .map(|&(_, program, args)| SpecialAction::Run { program, args })
+        .ok_or_else(|| std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Unknown special item: {}", special),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_opens_ms_settings() {
+        assert_eq!(
+            special_action("settings").unwrap(),
+            SpecialAction::Run { program: "cmd", args: &["/c", "start", "ms-settings:"] },
+        );
+    }
+
+    #[test]
+    fn aliases_share_an_action() {
+        assert_eq!(special_action("home").unwrap(), special_action("user_folder").unwrap());
+        assert_eq!(special_action("my_computer").unwrap(), special_action("this_pc").unwrap());
+        assert_eq!(special_action("system_tray").unwrap(), SpecialAction::TrayPopup);
+    }
+
+    #[test]
+    fn unknown_special_is_an_error() {
+        let err = special_action("no_such_special").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("no_such_special"));
+    }
+}
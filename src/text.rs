@@ -0,0 +1,228 @@
+//! Tiny built-in bitmap font for drawing short labels into the dock buffer
+//!
+//! Glyphs are 5x7 pixels stored column-major (one byte per column, bit 0 at
+//! the top) covering printable ASCII. Anything else renders as '?'.
+//! `draw_text` blends a string straight into an ARGB buffer; callers that
+//! need to transform the glyphs (rotated captions) use `for_each_pixel`.
+
+use crate::renderer::alpha_blend;
+
+pub const GLYPH_WIDTH: u32 = 5;
+pub const GLYPH_HEIGHT: u32 = 7;
+/// Horizontal distance between glyph origins (glyph plus 1px gap)
+pub const GLYPH_ADVANCE: u32 = GLYPH_WIDTH + 1;
+
+const FIRST_CHAR: u32 = 0x20;
+
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+fn glyph(ch: char) -> &'static [u8; 5] {
+    let code = ch as u32;
+    if (FIRST_CHAR..FIRST_CHAR + FONT.len() as u32).contains(&code) {
+        &FONT[(code - FIRST_CHAR) as usize]
+    } else {
+        &FONT[('?' as u32 - FIRST_CHAR) as usize]
+    }
+}
+
+/// Width in pixels of `text` laid out horizontally at the given integer scale.
+pub fn text_width(text: &str, scale: u32) -> u32 {
+    let chars = text.chars().count() as u32;
+    if chars == 0 {
+        return 0;
+    }
+    (chars * GLYPH_ADVANCE - 1) * scale
+}
+
+/// Width and height in pixels of `text` at the given integer scale
+pub fn measure_text(text: &str, scale: u32) -> (u32, u32) {
+    let height = if text.is_empty() { 0 } else { GLYPH_HEIGHT * scale };
+    (text_width(text, scale), height)
+}
+
+/// Blend `text` into `buffer` (`buf_width` pixels per row) with its top-left
+/// corner at (x, y), each font pixel a `scale` x `scale` block of `color`
+/// (ARGB, straight alpha). Pixels outside the buffer on any side are skipped,
+/// so text may start off-screen or run past an edge.
+pub fn draw_text(buffer: &mut [u32], buf_width: usize, x: i32, y: i32, text: &str, color: u32, scale: u32) {
+    if buf_width == 0 {
+        return;
+    }
+    let buf_height = (buffer.len() / buf_width) as i64;
+    for_each_pixel(text, scale, |tx, ty| {
+        let px = x as i64 + tx as i64;
+        let py = y as i64 + ty as i64;
+        if px < 0 || py < 0 || px >= buf_width as i64 || py >= buf_height {
+            return;
+        }
+        let idx = py as usize * buf_width + px as usize;
+        buffer[idx] = alpha_blend(buffer[idx], color);
+    });
+}
+
+/// Call `plot(x, y)` for every lit pixel of `text` laid out horizontally from
+/// the origin, each font pixel expanded to a `scale` x `scale` block. Callers
+/// map the coordinates (e.g. rotate them) and blend into their own buffer.
+pub fn for_each_pixel(text: &str, scale: u32, mut plot: impl FnMut(u32, u32)) {
+    for (i, ch) in text.chars().enumerate() {
+        let origin_x = i as u32 * GLYPH_ADVANCE * scale;
+        for (col, bits) in glyph(ch).iter().enumerate() {
+            for row in 0..GLYPH_HEIGHT {
+                if bits & (1 << row) == 0 {
+                    continue;
+                }
+                for sy in 0..scale {
+                    for sx in 0..scale {
+                        plot(origin_x + col as u32 * scale + sx, row * scale + sy);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `draw_text` lights exactly the glyph pixels, offset to where it's drawn,
+    /// and drops the ones past any edge of the buffer
+    #[test]
+    fn draw_text_lights_the_glyph_pixels() {
+        const W: usize = 16;
+        const H: usize = 12;
+        let white = 0xFFFF_FFFF;
+        let lit_at = |x: i32, y: i32, text: &str, scale: u32| {
+            let mut buffer = vec![0u32; W * H];
+            draw_text(&mut buffer, W, x, y, text, white, scale);
+            buffer
+        };
+        let expected_at = |x: i32, y: i32, text: &str, scale: u32| {
+            let mut buffer = vec![0u32; W * H];
+            for_each_pixel(text, scale, |tx, ty| {
+                let (px, py) = (x + tx as i32, y + ty as i32);
+                if (0..W as i32).contains(&px) && (0..H as i32).contains(&py) {
+                    buffer[py as usize * W + px as usize] = white;
+                }
+            });
+            buffer
+        };
+
+        assert_eq!(measure_text("Ab", 2), (22, 14));
+        assert_eq!(measure_text("", 3), (0, 0));
+
+        // Placement: 'L' is a left column and a bottom row
+        let drawn = lit_at(3, 2, "L", 1);
+        assert_eq!(drawn, expected_at(3, 2, "L", 1));
+        assert!((2..9).all(|y| drawn[y * W + 3] == white), "L's column misplaced");
+        assert!((3..8).all(|x| drawn[8 * W + x] == white), "L's base misplaced");
+        assert_eq!(drawn.iter().filter(|&&p| p == white).count(), 11);
+
+        // Clipping on every side, including text that starts off the buffer
+        for (x, y) in [(-3, 0), (0, -4), (W as i32 - 4, 0), (0, H as i32 - 3), (-20, -20), (W as i32, H as i32)] {
+            assert_eq!(lit_at(x, y, "Wq", 2), expected_at(x, y, "Wq", 2), "clipping at ({x}, {y})");
+        }
+        assert!(lit_at(-20, 0, "W", 1).iter().all(|&p| p == 0));
+
+        // Translucent text blends instead of overwriting
+        let mut buffer = vec![0xFF00_0000u32; W * H];
+        draw_text(&mut buffer, W, 0, 0, "I", 0x80FF_FFFF, 1);
+        assert_eq!(buffer[W + 2], alpha_blend(0xFF00_0000, 0x80FF_FFFF));
+    }
+}
@@ -1,9 +1,9 @@
 //! Tooltip support for dock items - styled popup window
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::sync::Once;
 use windows::core::PCWSTR;
-use windows::Win32::Foundation::{HWND, LPARAM, WPARAM, LRESULT, COLORREF, SIZE};
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM, LRESULT, COLORREF, POINT, RECT};
 use windows::Win32::Graphics::Gdi::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
@@ -11,12 +11,27 @@ use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 static REGISTER_CLASS: Once = Once::new();
 const TOOLTIP_CLASS: &str = "RDockTooltip";
 const CORNER_RADIUS: i32 = 6;
+// Horizontal and vertical padding around the text
+const PADDING_X: i32 = 16;
+const PADDING_Y: i32 = 6;
+// Names wider than this wrap onto a second line
+const MAX_TEXT_WIDTH: i32 = 360;
+const MAX_LINES: i32 = 2;
+// Gap between the tooltip and the point it's anchored to
+const ANCHOR_GAP: i32 = 10;
+// Closest the tooltip gets to the edge of the work area
+const SCREEN_MARGIN: i32 = 4;
+// DrawText flags for wrapped names; the last line is cut with an ellipsis
+const WRAP_FORMAT: DRAW_TEXT_FORMAT = DRAW_TEXT_FORMAT(
+    DT_CENTER.0 | DT_WORDBREAK.0 | DT_EDITCONTROL.0 | DT_END_ELLIPSIS.0 | DT_NOPREFIX.0,
+);
 
 // Thread-local storage for tooltip state
 thread_local! {
     static TOOLTIP_BG: RefCell<u32> = const { RefCell::new(0x2E1E1E) };
     static TOOLTIP_TEXT: RefCell<u32> = const { RefCell::new(0xE0E0E0) };
     static TOOLTIP_FONT: RefCell<HFONT> = const { RefCell::new(HFONT(std::ptr::null_mut())) };
+    static TOOLTIP_WRAPPED: Cell<bool> = const { Cell::new(false) };
 }
 
 pub struct Tooltip {
@@ -71,9 +86,16 @@ unsafe extern "system" fn tooltip_wnd_proc(
                 
                 // Draw text centered
                 let mut text_rect = rect;
-                text_rect.left += 12;
-                text_rect.right -= 12;
-                let _ = DrawTextW(hdc, &mut buf, &mut text_rect, DT_CENTER | DT_VCENTER | DT_SINGLELINE);
+                text_rect.left += PADDING_X;
+                text_rect.right -= PADDING_X;
+                let len = buf.len() - 1;
+                if TOOLTIP_WRAPPED.with(|w| w.get()) {
+                    text_rect.top += PADDING_Y;
+                    text_rect.bottom -= PADDING_Y;
+                    let _ = DrawTextW(hdc, &mut buf[..len], &mut text_rect, WRAP_FORMAT);
+                } else {
+                    let _ = DrawTextW(hdc, &mut buf[..len], &mut text_rect, DT_CENTER | DT_VCENTER | DT_SINGLELINE | DT_NOPREFIX);
+                }
                 
                 SelectObject(hdc, old_font);
             }
@@ -114,17 +136,16 @@ fn register_class() {
 
 /// Parse a hex color string like "#1e1e2e" to BGR u32 for Windows
 fn parse_color_bgr(hex: &str) -> u32 {
-    let hex = hex.trim_start_matches('#');
-    if hex.len() >= 6 {
-        if let Ok(rgb) = u32::from_str_radix(hex, 16) {
-            // Convert RGB to BGR for Windows
+    match crate::config::parse_color(hex) {
+        Ok(rgb) => {
+            // Convert RGB to BGR for Windows; GDI has no alpha
             let r = (rgb >> 16) & 0xFF;
             let g = (rgb >> 8) & 0xFF;
             let b = rgb & 0xFF;
-            return (b << 16) | (g << 8) | r;
+            (b << 16) | (g << 8) | r
         }
+        Err(_) => 0x2E1E1E, // fallback dark color
     }
-    0x2E1E1E // fallback dark color
 }
 
 /// Lighten a BGR color for the border
@@ -136,6 +157,44 @@ fn lighten_color(bgr: u32, amount: u32) -> u32 {
     (b << 16) | (g << 8) | r
 }
 
+/// Work area of the monitor containing (x, y)
+fn work_area_at(x: i32, y: i32) -> RECT {
+    unsafe {
+        let monitor = MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST);
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        if GetMonitorInfoW(monitor, &mut info).as_bool() {
+            info.rcWork
+        } else {
+            RECT {
+                left: 0,
+                top: 0,
+                right: GetSystemMetrics(SM_CXSCREEN),
+                bottom: GetSystemMetrics(SM_CYSCREEN),
+            }
+        }
+    }
+}
+
+/// Top-left corner for a `width` x `height` tooltip anchored at (x, y):
+/// centered above the anchor, shifted sideways to stay inside `work`, and
+/// flipped below the anchor when there's no room above.
+fn tooltip_position(x: i32, y: i32, width: i32, height: i32, work: &RECT) -> (i32, i32) {
+    let min_x = work.left + SCREEN_MARGIN;
+    let max_x = (work.right - SCREEN_MARGIN - width).max(min_x);
+    let tip_x = (x - width / 2).clamp(min_x, max_x);
+
+    let above = y - height - ANCHOR_GAP;
+    let tip_y = if above >= work.top + SCREEN_MARGIN {
+        above
+    } else {
+        (y + ANCHOR_GAP).min((work.bottom - SCREEN_MARGIN - height).max(work.top))
+    };
+    (tip_x, tip_y)
+}
+
 impl Tooltip {
     /// Create a new tooltip with colors derived from background_color
     pub fn new_with_color(_parent_hwnd: HWND, background_color: &str) -> Option<Self> {
@@ -216,21 +275,32 @@ impl Tooltip {
                 let text_wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
                 let _ = SetWindowTextW(self.hwnd, PCWSTR(text_wide.as_ptr()));
                 
-                // Calculate size needed for text with our font
+                let work = work_area_at(x, y);
+                let max_text_width = MAX_TEXT_WIDTH
+                    .min(work.right - work.left - 2 * (SCREEN_MARGIN + PADDING_X))
+                    .max(1);
+
+                // Measure on one line; names too wide for that wrap instead
                 let hdc = GetDC(self.hwnd);
                 let old_font = SelectObject(hdc, self.font);
-                let mut size = SIZE::default();
-                let _ = GetTextExtentPoint32W(hdc, &text_wide[..text_wide.len()-1], &mut size);
+                let mut text_buf = text_wide[..text_wide.len() - 1].to_vec();
+                let mut line = RECT::default();
+                DrawTextW(hdc, &mut text_buf, &mut line, DT_CALCRECT | DT_SINGLELINE | DT_NOPREFIX);
+                let wrapped = line.right > max_text_width;
+                let (text_width, text_height) = if wrapped {
+                    let mut bounds = RECT { right: max_text_width, ..Default::default() };
+                    DrawTextW(hdc, &mut text_buf, &mut bounds, WRAP_FORMAT | DT_CALCRECT);
+                    (bounds.right.min(max_text_width), bounds.bottom.min(line.bottom * MAX_LINES))
+                } else {
+                    (line.right, line.bottom)
+                };
                 SelectObject(hdc, old_font);
                 let _ = ReleaseDC(self.hwnd, hdc);
-                
-                // Add generous padding to ensure text fits
-                let width = size.cx + 32;
-                let height = size.cy + 12;
-                
-                // Position above cursor, centered on x
-                let tip_x = x - width / 2;
-                let tip_y = y - height - 10;
+                TOOLTIP_WRAPPED.with(|w| w.set(wrapped));
+
+                let width = text_width + 2 * PADDING_X;
+                let height = text_height + 2 * PADDING_Y;
+                let (tip_x, tip_y) = tooltip_position(x, y, width, height, &work);
                 
                 // Move and resize
                 let _ = SetWindowPos(
@@ -1,18 +1,28 @@
 //! Window focus utilities - find and activate existing app windows
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use windows::core::{BSTR, GUID};
 use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+use windows::Win32::UI::Shell::PropertiesSystem::{IPropertyStore, SHGetPropertyStoreForWindow, PROPERTYKEY};
 use windows::Win32::UI::WindowsAndMessaging::*;
-use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
-use windows::Win32::System::ProcessStatus::GetModuleFileNameExW;
+use windows::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_FORMAT, PROCESS_QUERY_LIMITED_INFORMATION,
+};
 
 /// Try to find and focus an existing window for the given executable path.
-/// Returns true if a window was found and focused, false otherwise.
-pub fn focus_existing_window(exe_path: &Path) -> bool {
-    let exe_name = match exe_path.file_name().and_then(|n| n.to_str()) {
-        Some(name) => name.to_lowercase(),
-        None => return false,
-    };
+/// Returns the HWND that was focused, or None if the app has no windows.
+///
+/// With several windows open, the choice is:
+/// - app already in the foreground: the bottom-most of its windows, so repeated
+///   clicks cycle through all of them
+/// - `last_focused` still open: that window
+/// - otherwise: the top-most (most recently active) window
+///
+/// Packaged (Store/UWP) apps run in windows owned by ApplicationFrameHost.exe,
+/// so a `shell:AppsFolder\<AppUserModelID>` path that matches no executable
+/// finds the app's windows by their AppUserModelID instead.
+pub fn focus_existing_window(exe_path: &Path, last_focused: Option<isize>) -> Option<isize> {
+    let exe_name = exe_path.file_name().and_then(|n| n.to_str())?.to_lowercase();
     
     // Collect all visible top-level windows
     let mut windows: Vec<HWND> = Vec::new();
@@ -24,18 +34,173 @@ pub fn focus_existing_window(exe_path: &Path) -> bool {
         );
     }
     
-    // Find a window belonging to our target process
+    // Windows belonging to our target process, top of the Z-order first
+    // (EnumWindows reports top-level windows in Z-order)
+    let mut candidates: Vec<isize> = windows.iter()
+        .filter(|&&hwnd| {
+            get_window_exe_name(hwnd)
+                .map(|window_exe| window_exe.to_lowercase() == exe_name)
+                .unwrap_or(false)
+        })
+        .map(|hwnd| hwnd.0 as isize)
+        .collect();
+    
+    if candidates.is_empty() {
+        if let Some(app_id) = apps_folder_id(exe_path) {
+            candidates = windows.iter()
+                .filter(|&&hwnd| {
+                    get_window_app_id(hwnd)
+                        .map(|window_id| window_id.eq_ignore_ascii_case(app_id))
+                        .unwrap_or(false)
+                })
+                .map(|hwnd| hwnd.0 as isize)
+                .collect();
+        }
+    }
+    
+    let foreground = unsafe { GetForegroundWindow().0 as isize };
+    let target = choose_window(&candidates, foreground, last_focused)?;
+    focus_window(HWND(target as *mut _));
+    Some(target)
+}
+
+/// The AppUserModelID in a packaged app's path, e.g.
+/// `Microsoft.WindowsCalculator_8wekyb3d8bbwe!App` from
+/// `shell:AppsFolder\Microsoft.WindowsCalculator_8wekyb3d8bbwe!App`
+pub fn apps_folder_id(path: &Path) -> Option<&str> {
+    let path = path.to_str()?;
+    let prefix = path.get(..APPS_FOLDER_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(APPS_FOLDER_PREFIX) {
+        return None;
+    }
+    Some(&path[APPS_FOLDER_PREFIX.len()..]).filter(|id| !id.is_empty())
+}
+
+const APPS_FOLDER_PREFIX: &str = r"shell:AppsFolder\";
+
+/// PKEY_AppUserModel_ID, written out so the EnhancedStorage bindings
+/// aren't needed for one key
+const PKEY_APP_USER_MODEL_ID: PROPERTYKEY = PROPERTYKEY {
+    fmtid: GUID::from_u128(0x9f4c2855_9f79_4b39_a8d0_e1d42de1d5f3),
+    pid: 5,
+};
+
+/// Pick which of an app's windows to focus. `candidates` is in Z-order, top first.
+fn choose_window(candidates: &[isize], foreground: isize, last_focused: Option<isize>) -> Option<isize> {
+    if candidates.contains(&foreground) {
+        return candidates.last().copied();
+    }
+    if let Some(last) = last_focused.filter(|hwnd| candidates.contains(hwnd)) {
+        return Some(last);
+    }
+    candidates.first().copied()
+}
+
+/// Executables that own at least one taskbar-style window (visible, titled,
+/// unowned, not a tool window), in Z-order with duplicates removed.
+pub fn windowed_executables() -> Vec<PathBuf> {
+    let mut windows: Vec<HWND> = Vec::new();
+    
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_windows_callback),
+            LPARAM(&mut windows as *mut Vec<HWND> as isize),
+        );
+    }
+    
+    let shell = unsafe { GetShellWindow() };
+    let mut exes: Vec<PathBuf> = Vec::new();
     for hwnd in windows {
-        if let Some(window_exe) = get_window_exe_name(hwnd) {
-            if window_exe.to_lowercase() == exe_name {
-                // Found a matching window - focus it
-                focus_window(hwnd);
-                return true;
+        if hwnd == shell || !is_app_window(hwnd) {
+            continue;
+        }
+        if let Some(path) = get_window_exe_path(hwnd) {
+            if !exes.contains(&path) {
+                exes.push(path);
             }
         }
     }
+    exes
+}
+
+/// Executable of the foreground window, unless it's one of rDock's own
+pub fn foreground_executable() -> Option<PathBuf> {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0.is_null() {
+            return None;
+        }
+        let mut pid: u32 = 0;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if pid == windows::Win32::System::Threading::GetCurrentProcessId() {
+            return None;
+        }
+        get_window_exe_path(hwnd)
+    }
+}
+
+/// Minimize every app window that isn't already minimized. Returns them in
+/// Z-order, top first, for `restore_windows`.
+pub fn minimize_all_windows() -> Vec<isize> {
+    let mut windows: Vec<HWND> = Vec::new();
+    
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_windows_callback),
+            LPARAM(&mut windows as *mut Vec<HWND> as isize),
+        );
+    }
+    
+    let shell = unsafe { GetShellWindow() };
+    let mut minimized = Vec::new();
+    for hwnd in windows {
+        if hwnd == shell || !is_app_window(hwnd) || unsafe { IsIconic(hwnd).as_bool() } {
+            continue;
+        }
+        // Async so a hung window can't stall the dock
+        unsafe {
+            let _ = ShowWindowAsync(hwnd, SW_SHOWMINNOACTIVE);
+        }
+        minimized.push(hwnd.0 as isize);
+    }
+    minimized
+}
+
+/// Restore the windows `minimize_all_windows` returned that are still
+/// minimized, bottom first so the old Z-order comes back, and focus the top
+/// one. Returns how many were restored.
+pub fn restore_windows(windows: &[isize]) -> usize {
+    let still_minimized: Vec<HWND> = windows.iter()
+        .map(|&hwnd| HWND(hwnd as *mut _))
+        .filter(|&hwnd| unsafe { IsWindow(hwnd).as_bool() && IsIconic(hwnd).as_bool() })
+        .collect();
     
-    false
+    for &hwnd in still_minimized.iter().rev() {
+        unsafe {
+            // SW_RESTORE brings maximized windows back maximized
+            let _ = ShowWindowAsync(hwnd, SW_RESTORE);
+        }
+    }
+    if let Some(&top) = still_minimized.first() {
+        unsafe {
+            let _ = SetForegroundWindow(top);
+        }
+    }
+    still_minimized.len()
+}
+
+/// Whether a window would get a taskbar button.
+fn is_app_window(hwnd: HWND) -> bool {
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+        if ex_style & WS_EX_TOOLWINDOW.0 != 0 {
+            return false;
+        }
+        if ex_style & WS_EX_APPWINDOW.0 != 0 {
+            return true;
+        }
+        GetWindow(hwnd, GW_OWNER).map(|owner| owner.is_invalid()).unwrap_or(true)
+    }
 }
 
 unsafe extern "system" fn enum_windows_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
@@ -52,6 +217,24 @@ unsafe extern "system" fn enum_windows_callback(hwnd: HWND, lparam: LPARAM) -> B
 }
 
 fn get_window_exe_name(hwnd: HWND) -> Option<String> {
+    get_window_exe_path(hwnd)?
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|s| s.to_string())
+}
+
+/// The AppUserModelID the shell groups a window's taskbar button by, set
+/// for packaged apps and for desktop apps that choose their own
+fn get_window_app_id(hwnd: HWND) -> Option<String> {
+    unsafe {
+        let store: IPropertyStore = SHGetPropertyStoreForWindow(hwnd).ok()?;
+        let value = store.GetValue(&PKEY_APP_USER_MODEL_ID).ok()?;
+        let id = BSTR::try_from(&value).ok()?.to_string();
+        (!id.is_empty()).then_some(id)
+    }
+}
+
+fn get_window_exe_path(hwnd: HWND) -> Option<PathBuf> {
     unsafe {
         let mut pid: u32 = 0;
         GetWindowThreadProcessId(hwnd, Some(&mut pid));
@@ -60,22 +243,21 @@ fn get_window_exe_name(hwnd: HWND) -> Option<String> {
             return None;
         }
         
+        // QueryFullProcessImageNameW only needs limited query rights, unlike
+        // GetModuleFileNameExW, so it also works for elevated processes
         let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
         
-        let mut buf = [0u16; 260];
-        let len = GetModuleFileNameExW(process, None, &mut buf);
+        let mut buf = [0u16; 1024];
+        let mut len = buf.len() as u32;
+        let ok = QueryFullProcessImageNameW(process, PROCESS_NAME_FORMAT(0), windows::core::PWSTR(buf.as_mut_ptr()), &mut len).is_ok();
         
         let _ = windows::Win32::Foundation::CloseHandle(process);
         
-        if len == 0 {
+        if !ok || len == 0 {
             return None;
         }
         
-        let path = String::from_utf16_lossy(&buf[..len as usize]);
-        std::path::Path::new(&path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map(|s| s.to_string())
+        Some(PathBuf::from(String::from_utf16_lossy(&buf[..len as usize])))
     }
 }
 
@@ -93,3 +275,43 @@ fn focus_window(hwnd: HWND) {
         let _ = BringWindowToTop(hwnd);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foreground_app_cycles_to_its_bottom_window() {
+        assert_eq!(choose_window(&[1, 2, 3], 1, Some(2)), Some(3));
+        assert_eq!(choose_window(&[3, 1, 2], 3, None), Some(2));
+    }
+
+    #[test]
+    fn last_focused_window_wins_when_still_open() {
+        assert_eq!(choose_window(&[1, 2, 3], 99, Some(2)), Some(2));
+    }
+
+    #[test]
+    fn falls_back_to_the_top_window() {
+        assert_eq!(choose_window(&[1, 2, 3], 99, Some(4)), Some(1));
+        assert_eq!(choose_window(&[1, 2, 3], 99, None), Some(1));
+        assert_eq!(choose_window(&[], 99, Some(4)), None);
+    }
+
+    #[test]
+    fn apps_folder_id_reads_the_aumid() {
+        let calculator = "Microsoft.WindowsCalculator_8wekyb3d8bbwe!App";
+        assert_eq!(apps_folder_id(Path::new(&format!(r"shell:AppsFolder\{}", calculator))), Some(calculator));
+        assert_eq!(apps_folder_id(Path::new(&format!(r"SHELL:appsfolder\{}", calculator))), Some(calculator));
+        let (package, entry) = apps_folder_id(Path::new(r"shell:AppsFolder\Vendor.App_abc!Main")).unwrap().split_once('!').unwrap();
+        assert_eq!((package, entry), ("Vendor.App_abc", "Main"));
+    }
+
+    #[test]
+    fn apps_folder_id_needs_an_apps_folder_path() {
+        assert_eq!(apps_folder_id(Path::new(r"shell:AppsFolder\")), None);
+        assert_eq!(apps_folder_id(Path::new(r"shell:Apps")), None);
+        assert_eq!(apps_folder_id(Path::new(r"shell:Startup\Tool.lnk")), None);
+        assert_eq!(apps_folder_id(Path::new(r"C:\Windows\notepad.exe")), None);
+    }
+}